@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// Errors reported when running a method comparison or writing its report.
+#[derive(Debug)]
+pub enum ComparisonError {
+    /// The problem has no reference solution to measure the errors against.
+    NoReference(String),
+    /// The report file could not be written.
+    Io(std::io::Error),
+    /// The file extension does not name a supported report format.
+    UnknownFormat(String),
+}
+
+impl fmt::Display for ComparisonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ComparisonError::NoReference(problem) => {
+                write!(f, "the problem `{}` has no reference solution", problem)
+            }
+            ComparisonError::Io(err) => write!(f, "could not write the report: {}", err),
+            ComparisonError::UnknownFormat(path) => write!(
+                f,
+                "unknown report format for `{}` (expected .md, .csv or .html)",
+                path
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ComparisonError {}
+
+impl From<std::io::Error> for ComparisonError {
+    fn from(err: std::io::Error) -> Self {
+        ComparisonError::Io(err)
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{
+    comparison::comparison_error::ComparisonError,
+    ode_solvers::{
+        method_registry::{build_solver, MethodInfo},
+        ode_error::OdeError,
+        ode_solution::{OdeSolution, SolverStats, SolverStatus},
+        ode_solver::{OdeSolver, OdeSolverParams},
+    },
+    problems::benchmark_problem::BenchmarkProblem,
+};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Relative tolerances of adaptive methods, from loose to tight.
+pub const DEFAULT_TOLERANCES: [f64; 6] = [1e-3, 1e-4, 1e-5, 1e-6, 1e-7, 1e-8];
+/// Numbers of steps of fixed step methods over the time span.
+pub const DEFAULT_NUM_STEPS: [usize; 6] = [100, 200, 400, 800, 1600, 3200];
+
+/// What is swept in a comparison: the tolerances of the adaptive methods and the number
+/// of steps of the fixed step methods.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonSettings {
+    /// Relative tolerances of the adaptive methods
+    pub rel_tolerances: Vec<f64>,
+    /// The absolute tolerance of a run is its relative tolerance times this factor
+    pub abs_tolerance_factor: f64,
+    /// Numbers of equal steps of the fixed step methods
+    pub num_steps: Vec<usize>,
+    /// Newton tolerance of fixed step implicit methods
+    pub newton_tolerance: f64,
+    pub max_iters: i32,
+}
+
+impl Default for ComparisonSettings {
+    fn default() -> Self {
+        let defaults = OdeSolverParams::default();
+        ComparisonSettings {
+            rel_tolerances: DEFAULT_TOLERANCES.to_vec(),
+            abs_tolerance_factor: 1e-3,
+            num_steps: DEFAULT_NUM_STEPS.to_vec(),
+            newton_tolerance: defaults.tolerance,
+            max_iters: defaults.max_iters,
+        }
+    }
+}
+
+/// Setting of one run: the tolerance of an adaptive method or the step size of a fixed
+/// step method.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunSetting {
+    RelTolerance(f64),
+    TimeStep(f64),
+}
+
+impl RunSetting {
+    /// Short text for tables, e.g. `rtol=1e-6` or `h=1.25e-2`.
+    pub fn label(&self) -> String {
+        match self {
+            RunSetting::RelTolerance(tolerance) => format!("rtol={:e}", tolerance),
+            RunSetting::TimeStep(time_step) => format!("h={:e}", time_step),
+        }
+    }
+}
+
+/// What a successful run measured.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunMeasurement {
+    /// Largest absolute error of the final state against the reference
+    pub error: f64,
+    pub stats: SolverStats,
+    pub wall_time: Duration,
+}
+
+/// One solve of a [`MethodComparison`], with the reason of its failure if it failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonRun {
+    pub method: &'static MethodInfo,
+    pub setting: RunSetting,
+    pub outcome: Result<RunMeasurement, String>,
+}
+
+/// The cost of a run, against which the errors are shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WorkMeasure {
+    RhsEvaluations,
+    WallTime,
+}
+
+impl WorkMeasure {
+    pub fn label(&self) -> &'static str {
+        match self {
+            WorkMeasure::RhsEvaluations => "RHS evaluations",
+            WorkMeasure::WallTime => "wall time [s]",
+        }
+    }
+
+    fn of(&self, measurement: &RunMeasurement) -> f64 {
+        match self {
+            WorkMeasure::RhsEvaluations => measurement.stats.num_rhs_evals as f64,
+            WorkMeasure::WallTime => measurement.wall_time.as_secs_f64(),
+        }
+    }
+}
+
+/// Error against work of several methods on one problem, each run over a range of
+/// tolerances or step sizes, to choose a method from data: the work-precision diagram
+/// of the problem.
+///
+/// ```ignore
+/// let robertson = problem("robertson").unwrap();
+/// let methods = ["bdf", "implicit-euler"].map(|id| find_method(id).unwrap());
+/// let comparison = MethodComparison::run(&robertson, &methods, &ComparisonSettings::default())?;
+/// write_report(&comparison, "solver_results/robertson.md")?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct MethodComparison {
+    /// Id of the problem
+    pub problem: String,
+    pub description: String,
+    pub runs: Vec<ComparisonRun>,
+}
+
+impl MethodComparison {
+    /// Solves `problem` with every method of `methods` at every setting of `settings`,
+    /// measuring the error at the final time against the reference of the problem.
+    /// Failed runs are recorded, not returned as errors.
+    pub fn run(
+        problem: &BenchmarkProblem,
+        methods: &[&'static MethodInfo],
+        settings: &ComparisonSettings,
+    ) -> Result<Self, ComparisonError> {
+        let reference: Vec<f64> = problem
+            .reference_final_state()
+            .ok_or_else(|| ComparisonError::NoReference(problem.id.to_string()))?;
+        let mut runs: Vec<ComparisonRun> = Vec::new();
+        for &method in methods {
+            let run_params: Vec<(RunSetting, OdeSolverParams)> = if method.adaptive {
+                settings
+                    .rel_tolerances
+                    .iter()
+                    .map(|&rel_tolerance| {
+                        let params = OdeSolverParams {
+                            time_span: problem.time_span,
+                            tolerance: rel_tolerance * settings.abs_tolerance_factor,
+                            rel_tolerance,
+                            max_iters: settings.max_iters,
+                            ..Default::default()
+                        };
+                        (RunSetting::RelTolerance(rel_tolerance), params)
+                    })
+                    .collect()
+            } else {
+                settings
+                    .num_steps
+                    .iter()
+                    .map(|&num_steps| {
+                        let time_step: f64 = problem.time_span.step_for(num_steps);
+                        let params = OdeSolverParams {
+                            time_span: problem.time_span,
+                            time_step,
+                            tolerance: settings.newton_tolerance,
+                            max_iters: settings.max_iters,
+                            ..Default::default()
+                        };
+                        (RunSetting::TimeStep(time_step), params)
+                    })
+                    .collect()
+            };
+            for (setting, params) in run_params {
+                let outcome = measure(problem, method, &params, &reference);
+                debug!(
+                    method = method.id,
+                    setting = %setting.label(),
+                    ok = outcome.is_ok(),
+                    "comparison run"
+                );
+                runs.push(ComparisonRun {
+                    method,
+                    setting,
+                    outcome,
+                });
+            }
+        }
+        Ok(MethodComparison {
+            problem: problem.id.to_string(),
+            description: problem.description.clone(),
+            runs,
+        })
+    }
+
+    /// The compared methods, in the order they were run.
+    pub fn methods(&self) -> Vec<&'static MethodInfo> {
+        let mut methods: Vec<&'static MethodInfo> = Vec::new();
+        for run in &self.runs {
+            if !methods.iter().any(|method| method.id == run.method.id) {
+                methods.push(run.method);
+            }
+        }
+        methods
+    }
+
+    /// Work and error of the successful runs of `method`, the points of its curve in
+    /// the work-precision diagram.
+    pub fn work_precision(&self, method: &MethodInfo, work: WorkMeasure) -> (Vec<f64>, Vec<f64>) {
+        self.runs
+            .iter()
+            .filter(|run| run.method.id == method.id)
+            .filter_map(|run| run.outcome.as_ref().ok())
+            .map(|measurement| (work.of(measurement), measurement.error))
+            .unzip()
+    }
+
+    /// Number of failed runs.
+    pub fn num_failures(&self) -> usize {
+        self.runs.iter().filter(|run| run.outcome.is_err()).count()
+    }
+
+    /// Log-log figure of the error against `work`, one curve per method.
+    #[cfg(any(feature = "plot", feature = "static-plot"))]
+    pub fn work_precision_plot(
+        &self,
+        work: WorkMeasure,
+    ) -> crate::plotting::convergence_plot::ConvergencePlot {
+        let mut plot = crate::plotting::convergence_plot::ConvergencePlot::new(&format!(
+            "{}: {}",
+            self.problem, self.description
+        ))
+        .with_x_label(work.label())
+        .with_y_label("error at tf");
+        for method in self.methods() {
+            let (work, errors) = self.work_precision(method, work);
+            if !work.is_empty() {
+                plot = plot.with_series(method.name, &work, &errors);
+            }
+        }
+        plot
+    }
+}
+
+/// Solves the problem once and measures the run, or says why it failed.
+fn measure(
+    problem: &BenchmarkProblem,
+    method: &MethodInfo,
+    params: &OdeSolverParams,
+    reference: &[f64],
+) -> Result<RunMeasurement, String> {
+    let start = Instant::now();
+    let solver = OdeSolver::new(method.name, problem.system.as_ref(), params);
+    let result: Result<OdeSolution, OdeError> =
+        build_solver(method.id, solver).and_then(|solver| solver.solve(&problem.initial_state));
+    let wall_time: Duration = start.elapsed();
+    let solution: OdeSolution = result.map_err(|err| err.to_string())?;
+
+    let (t, state) = solution.last().ok_or("empty solution")?;
+    if solution.status != SolverStatus::Success {
+        return Err(format!("{:?} at t = {}", solution.status, t));
+    }
+    if !state.iter().all(|value| value.is_finite()) {
+        return Err(format!("diverged before t = {}", t));
+    }
+    let error: f64 = state
+        .iter()
+        .zip(reference)
+        .map(|(value, reference)| (value - reference).abs())
+        .fold(0.0, f64::max);
+    Ok(RunMeasurement {
+        error,
+        stats: solution.stats,
+        wall_time,
+    })
+}
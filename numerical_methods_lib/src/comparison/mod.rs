@@ -0,0 +1,3 @@
+pub mod comparison_error;
+pub mod method_comparison;
+pub mod report;
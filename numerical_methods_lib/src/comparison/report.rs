@@ -0,0 +1,205 @@
+use crate::comparison::{
+    comparison_error::ComparisonError,
+    method_comparison::{ComparisonRun, MethodComparison},
+};
+use std::{
+    fs::{create_dir_all, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// File format of a comparison report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    /// A Markdown table, e.g. for a README or a pull request.
+    Markdown,
+    /// One line per run, for spreadsheets and scripts.
+    Csv,
+    /// A standalone HTML page with the table and, with the `static-plot` feature, the
+    /// work-precision chart.
+    Html,
+}
+
+impl ReportFormat {
+    /// Format given by the extension of `file_path`.
+    pub fn from_path(file_path: &Path) -> Option<ReportFormat> {
+        let extension: String = file_path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "md" | "markdown" => Some(ReportFormat::Markdown),
+            "csv" => Some(ReportFormat::Csv),
+            "html" | "htm" => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+const COLUMNS: [&str; 9] = [
+    "method",
+    "setting",
+    "steps",
+    "rejected",
+    "RHS evals",
+    "Jac evals",
+    "error at tf",
+    "time [ms]",
+    "status",
+];
+
+/// The cells of a run, in the order of `COLUMNS`. A failed run has empty measurements
+/// and its reason as status.
+fn cells(run: &ComparisonRun) -> Vec<String> {
+    let mut cells: Vec<String> = vec![run.method.id.to_string(), run.setting.label()];
+    match &run.outcome {
+        Ok(measurement) => cells.extend([
+            measurement.stats.accepted_steps.to_string(),
+            measurement.stats.rejected_steps.to_string(),
+            measurement.stats.num_rhs_evals.to_string(),
+            measurement.stats.num_jacobian_evals.to_string(),
+            format!("{:.3e}", measurement.error),
+            format!("{:.3}", measurement.wall_time.as_secs_f64() * 1e3),
+            "ok".to_string(),
+        ]),
+        Err(reason) => {
+            cells.extend(vec![String::new(); 6]);
+            cells.push(format!("failed: {}", reason));
+        }
+    }
+    cells
+}
+
+/// Markdown report: a heading, the number of failures and the table of the runs.
+pub fn to_markdown(comparison: &MethodComparison) -> String {
+    let mut text: String = format!(
+        "# Work-precision: {}\n\n{}, {} runs, {} failed.\n\n",
+        comparison.problem,
+        comparison.description,
+        comparison.runs.len(),
+        comparison.num_failures()
+    );
+    text += &format!("| {} |\n", COLUMNS.join(" | "));
+    // Numbers are right aligned
+    let alignment: Vec<&str> = COLUMNS
+        .iter()
+        .map(|&column| match column {
+            "method" | "setting" | "status" => "---",
+            _ => "---:",
+        })
+        .collect();
+    text += &format!("| {} |\n", alignment.join(" | "));
+    for run in &comparison.runs {
+        let row: Vec<String> = cells(run)
+            .iter()
+            .map(|cell| cell.replace('|', "\\|"))
+            .collect();
+        text += &format!("| {} |\n", row.join(" | "));
+    }
+    text
+}
+
+/// CSV report: a header, then one line per run with the problem id in the first column.
+pub fn to_csv(comparison: &MethodComparison) -> String {
+    let quote = |cell: &str| -> String {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    };
+    let mut text: String = format!("problem,{}\n", COLUMNS.join(","));
+    for run in &comparison.runs {
+        let row: Vec<String> = cells(run).iter().map(|cell| quote(cell)).collect();
+        text += &format!("{},{}\n", quote(&comparison.problem), row.join(","));
+    }
+    text
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Work-precision chart of the RHS evaluations as an inline SVG image, or a note saying
+/// why it could not be drawn.
+#[cfg(feature = "static-plot")]
+fn work_precision_svg(comparison: &MethodComparison) -> String {
+    use crate::{comparison::method_comparison::WorkMeasure, plotting::static_export::to_svg};
+
+    if comparison.num_failures() == comparison.runs.len() {
+        return String::new();
+    }
+    let plot = comparison.work_precision_plot(WorkMeasure::RhsEvaluations);
+    match to_svg(&plot.to_figure()) {
+        Ok(svg) => format!("<figure>\n{}\n</figure>\n", svg.trim_end()),
+        Err(err) => format!(
+            "<p>The chart could not be drawn: {}</p>\n",
+            escape_html(&err.to_string())
+        ),
+    }
+}
+
+/// HTML report: a standalone page with the table, failed runs highlighted, followed by
+/// the work-precision chart.
+pub fn to_html(comparison: &MethodComparison) -> String {
+    let title: String = escape_html(&format!("Work-precision: {}", comparison.problem));
+    let mut html: String = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\n\
+         table {{ border-collapse: collapse; font-family: sans-serif; }}\n\
+         th, td {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}\n\
+         td:first-child, td:nth-child(2), td:last-child {{ text-align: left; }}\n\
+         tr.failed {{ background: #fdd; }}\n\
+         </style>\n</head>\n<body>\n<h1>{}</h1>\n<p>{}, {} runs, {} failed.</p>\n<table>\n<tr>",
+        title,
+        title,
+        escape_html(&comparison.description),
+        comparison.runs.len(),
+        comparison.num_failures()
+    );
+    for column in COLUMNS {
+        html += &format!("<th>{}</th>", escape_html(column));
+    }
+    html += "</tr>\n";
+    for run in &comparison.runs {
+        html += if run.outcome.is_ok() {
+            "<tr>"
+        } else {
+            "<tr class=\"failed\">"
+        };
+        for cell in cells(run) {
+            html += &format!("<td>{}</td>", escape_html(&cell));
+        }
+        html += "</tr>\n";
+    }
+    html += "</table>\n";
+    #[cfg(feature = "static-plot")]
+    {
+        html += &work_precision_svg(comparison);
+    }
+    html += "</body>\n</html>\n";
+    html
+}
+
+/// Writes the report of `comparison` in the format given by the extension of
+/// `file_path` (`.md`, `.csv` or `.html`), creating the parent directories.
+pub fn write_report(
+    comparison: &MethodComparison,
+    file_path: impl AsRef<Path>,
+) -> Result<(), ComparisonError> {
+    let file_path: &Path = file_path.as_ref();
+    let format: ReportFormat = ReportFormat::from_path(file_path)
+        .ok_or_else(|| ComparisonError::UnknownFormat(file_path.display().to_string()))?;
+    let text: String = match format {
+        ReportFormat::Markdown => to_markdown(comparison),
+        ReportFormat::Csv => to_csv(comparison),
+        ReportFormat::Html => to_html(comparison),
+    };
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(file_path)?);
+    writer.write_all(text.as_bytes())?;
+    writer.flush()?;
+    Ok(())
+}
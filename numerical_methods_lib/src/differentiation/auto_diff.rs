@@ -0,0 +1,97 @@
+use crate::{
+    differentiation::dual_numbers::{Dual, Scalar},
+    ode_solvers::ode_system::OdeSystem,
+};
+
+/// Right hand side written once for any [`Scalar`], so the Jacobian can be computed
+/// exactly with dual numbers. Wrap it in [`AutoDiff`] to use it as an [`OdeSystem`].
+pub trait AutoDiffSystem {
+    /// Number of state components.
+    fn dimension(&self) -> usize;
+
+    /// Writes `f(t, y)` into `dydt`.
+    fn rhs<S: Scalar>(&self, t: S, y: &[S], dydt: &mut [S]);
+}
+
+/// OdeSystem whose Jacobian is computed by forward-mode automatic differentiation of
+/// the generic right hand side, one column per pass.
+pub struct AutoDiff<A>(pub A);
+
+impl<A: AutoDiffSystem> OdeSystem for AutoDiff<A> {
+    fn dimension(&self) -> usize {
+        self.0.dimension()
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        self.0.rhs(t, y, dydt)
+    }
+
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        dual_jacobian(|t, y, dydt| self.0.rhs(t, y, dydt), t, y, jac);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// OdeSystem built from a closure over dual numbers.
+///
+/// Closures cannot be generic, so the right hand side is written for [`Dual`] only;
+/// `rhs` evaluates it with zero derivatives and `jacobian` is exact.
+pub struct AutoDiffClosure<F> {
+    dimension: usize,
+    rhs: F,
+}
+
+impl<F> AutoDiffClosure<F>
+where
+    F: Fn(Dual, &[Dual], &mut [Dual]),
+{
+    pub fn new(dimension: usize, rhs: F) -> Self {
+        AutoDiffClosure { dimension, rhs }
+    }
+}
+
+impl<F> OdeSystem for AutoDiffClosure<F>
+where
+    F: Fn(Dual, &[Dual], &mut [Dual]),
+{
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        let y_dual: Vec<Dual> = y.iter().map(|&y_j| Dual::constant(y_j)).collect();
+        let mut dydt_dual: Vec<Dual> = vec![Dual::default(); y.len()];
+        (self.rhs)(Dual::constant(t), &y_dual, &mut dydt_dual);
+        for (value, dual) in dydt.iter_mut().zip(dydt_dual) {
+            *value = dual.value;
+        }
+    }
+
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        dual_jacobian(&self.rhs, t, y, jac);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Exact Jacobian (row-major, N x N) of `rhs` at `(t, y)`: column `col` is the
+/// derivative part of `rhs` evaluated with `y[col]` seeded as the variable.
+pub fn dual_jacobian(rhs: impl Fn(Dual, &[Dual], &mut [Dual]), t: f64, y: &[f64], jac: &mut [f64]) {
+    let dim: usize = y.len();
+    let mut y_dual: Vec<Dual> = y.iter().map(|&y_j| Dual::constant(y_j)).collect();
+    let mut dydt_dual: Vec<Dual> = vec![Dual::default(); dim];
+
+    for col in 0..dim {
+        y_dual[col].derivative = 1.0;
+        rhs(Dual::constant(t), &y_dual, &mut dydt_dual);
+        y_dual[col].derivative = 0.0;
+        for row in 0..dim {
+            jac[row * dim + col] = dydt_dual[row].derivative;
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Dual number `value + derivative * eps` with `eps^2 = 0`, for forward-mode
+/// automatic differentiation.
+///
+/// Evaluating a function on `Dual::variable(x)` gives `f(x)` in `value` and the exact
+/// `f'(x)` in `derivative`. Comparisons only look at `value`, so branches in the
+/// differentiated code take the same path as for plain `f64`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Dual { value, derivative }
+    }
+
+    /// Constant, its derivative is zero.
+    pub fn constant(value: f64) -> Self {
+        Dual::new(value, 0.0)
+    }
+
+    /// Independent variable, its derivative is one.
+    pub fn variable(value: f64) -> Self {
+        Dual::new(value, 1.0)
+    }
+
+    /// Applies a function with value `f` and derivative `f_dash` at `self.value` (chain rule).
+    fn chain(self, f: f64, f_dash: f64) -> Self {
+        Dual::new(f, f_dash * self.derivative)
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Self {
+        Dual::constant(value)
+    }
+}
+
+impl PartialEq for Dual {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl PartialOrd for Dual {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl Neg for Dual {
+    type Output = Dual;
+
+    fn neg(self) -> Dual {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+
+impl Add for Dual {
+    type Output = Dual;
+
+    fn add(self, rhs: Dual) -> Dual {
+        Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Dual;
+
+    fn sub(self, rhs: Dual) -> Dual {
+        Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Dual;
+
+    fn mul(self, rhs: Dual) -> Dual {
+        Dual::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Dual;
+
+    fn div(self, rhs: Dual) -> Dual {
+        Dual::new(
+            self.value / rhs.value,
+            (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value),
+        )
+    }
+}
+
+// Mixed operations with f64 and the compound assignments, in terms of the ones above
+macro_rules! impl_mixed_ops {
+    ($($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident);*) => {
+        $(
+            impl $trait<f64> for Dual {
+                type Output = Dual;
+
+                fn $method(self, rhs: f64) -> Dual {
+                    self.$method(Dual::constant(rhs))
+                }
+            }
+
+            impl $trait<Dual> for f64 {
+                type Output = Dual;
+
+                fn $method(self, rhs: Dual) -> Dual {
+                    Dual::constant(self).$method(rhs)
+                }
+            }
+
+            impl $assign_trait for Dual {
+                fn $assign_method(&mut self, rhs: Dual) {
+                    *self = self.$method(rhs);
+                }
+            }
+
+            impl $assign_trait<f64> for Dual {
+                fn $assign_method(&mut self, rhs: f64) {
+                    *self = self.$method(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_mixed_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+/// Real number type the right hand side can be written in, so the same code runs on
+/// `f64` for values and on [`Dual`] for exact derivatives.
+pub trait Scalar:
+    Copy
+    + Debug
+    + PartialOrd
+    + From<f64>
+    + Neg<Output = Self>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    /// The plain value, without derivative information.
+    fn value(self) -> f64;
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn powf(self, n: f64) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn atan(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+}
+
+impl Scalar for f64 {
+    fn value(self) -> f64 {
+        self
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        f64::powi(self, n)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        f64::powf(self, n)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        f64::tan(self)
+    }
+
+    fn atan(self) -> Self {
+        f64::atan(self)
+    }
+
+    fn sinh(self) -> Self {
+        f64::sinh(self)
+    }
+
+    fn cosh(self) -> Self {
+        f64::cosh(self)
+    }
+
+    fn tanh(self) -> Self {
+        f64::tanh(self)
+    }
+}
+
+impl Scalar for Dual {
+    fn value(self) -> f64 {
+        self.value
+    }
+
+    fn abs(self) -> Self {
+        // The derivative at 0 is taken as 0 (subgradient)
+        let sign: f64 = if self.value == 0.0 {
+            0.0
+        } else {
+            self.value.signum()
+        };
+        self.chain(self.value.abs(), sign)
+    }
+
+    fn sqrt(self) -> Self {
+        let root: f64 = self.value.sqrt();
+        self.chain(root, 0.5 / root)
+    }
+
+    fn exp(self) -> Self {
+        let exp: f64 = self.value.exp();
+        self.chain(exp, exp)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1.0 / self.value)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Dual::constant(1.0);
+        }
+        self.chain(self.value.powi(n), n as f64 * self.value.powi(n - 1))
+    }
+
+    fn powf(self, n: f64) -> Self {
+        if n == 0.0 {
+            return Dual::constant(1.0);
+        }
+        self.chain(self.value.powf(n), n * self.value.powf(n - 1.0))
+    }
+
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    fn tan(self) -> Self {
+        let tan: f64 = self.value.tan();
+        self.chain(tan, 1.0 + tan * tan)
+    }
+
+    fn atan(self) -> Self {
+        self.chain(self.value.atan(), 1.0 / (1.0 + self.value * self.value))
+    }
+
+    fn sinh(self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    fn cosh(self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    fn tanh(self) -> Self {
+        let tanh: f64 = self.value.tanh();
+        self.chain(tanh, 1.0 - tanh * tanh)
+    }
+}
@@ -0,0 +1,287 @@
+use crate::ode_solvers::{ode_error::OdeError, ode_system::OdeSystem};
+
+/// Difference quotient used for each Jacobian column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DifferenceScheme {
+    /// `(f(x + d) - f(x)) / d`, first order, one evaluation per column group.
+    Forward,
+    /// `(f(x + d) - f(x - d)) / 2d`, second order, two evaluations per column group.
+    Central,
+}
+
+/// Structurally nonzero entries of a Jacobian, stored per column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparsityPattern {
+    dim: usize,
+    /// `columns[col]` holds the rows with a nonzero entry in column `col`
+    columns: Vec<Vec<usize>>,
+}
+
+impl SparsityPattern {
+    /// Pattern from the `(row, col)` positions of the nonzero entries, all of which must
+    /// lie within the `dim` x `dim` matrix.
+    pub fn new(dim: usize, nonzeros: &[(usize, usize)]) -> Result<Self, OdeError> {
+        let mut columns: Vec<Vec<usize>> = vec![Vec::new(); dim];
+        for &(row, col) in nonzeros {
+            if row >= dim || col >= dim {
+                return Err(OdeError::InvalidParameter {
+                    name: "sparsity",
+                    reason: format!("entry ({}, {}) outside {}x{}", row, col, dim, dim),
+                });
+            }
+            columns[col].push(row);
+        }
+        for rows in &mut columns {
+            rows.sort_unstable();
+            rows.dedup();
+        }
+        Ok(SparsityPattern { dim, columns })
+    }
+
+    /// Every entry is nonzero.
+    pub fn dense(dim: usize) -> Self {
+        SparsityPattern {
+            dim,
+            columns: vec![(0..dim).collect(); dim],
+        }
+    }
+
+    /// Band matrix with `lower` sub- and `upper` super-diagonals.
+    pub fn banded(dim: usize, lower: usize, upper: usize) -> Self {
+        let columns: Vec<Vec<usize>> = (0..dim)
+            .map(|col| (col.saturating_sub(upper)..(col + lower + 1).min(dim)).collect())
+            .collect();
+        SparsityPattern { dim, columns }
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dim
+    }
+
+    /// Rows with a nonzero entry in column `col`.
+    pub fn rows(&self, col: usize) -> &[usize] {
+        &self.columns[col]
+    }
+
+    /// Curtis-Powell-Reid grouping: columns in a group share no nonzero row, so one
+    /// perturbation of the whole group recovers all of them.
+    ///
+    /// Columns are assigned greedily to the first group they fit in.
+    pub fn column_groups(&self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        // Rows already covered by each group
+        let mut covered: Vec<Vec<bool>> = Vec::new();
+
+        for col in 0..self.dim {
+            let rows: &[usize] = &self.columns[col];
+            let group: usize =
+                match (0..groups.len()).find(|&g| rows.iter().all(|&r| !covered[g][r])) {
+                    Some(group) => group,
+                    None => {
+                        groups.push(Vec::new());
+                        covered.push(vec![false; self.dim]);
+                        groups.len() - 1
+                    }
+                };
+            groups[group].push(col);
+            for &row in rows {
+                covered[group][row] = true;
+            }
+        }
+        groups
+    }
+}
+
+/// Finite difference approximation of the Jacobian of `f: R^N -> R^N`.
+///
+/// Perturbations are scaled with the size of each component. With a sparsity pattern
+/// the columns are perturbed in Curtis-Powell-Reid groups, so a banded Jacobian needs
+/// only bandwidth + 1 evaluations of `f` (forward differences) whatever its size.
+#[derive(Clone, Debug)]
+pub struct FiniteDifferenceJacobian {
+    pub scheme: DifferenceScheme,
+    sparsity: Option<SparsityPattern>,
+    groups: Option<Vec<Vec<usize>>>,
+}
+
+impl Default for FiniteDifferenceJacobian {
+    fn default() -> Self {
+        FiniteDifferenceJacobian::new(DifferenceScheme::Forward)
+    }
+}
+
+impl FiniteDifferenceJacobian {
+    /// Dense Jacobian, one column at a time.
+    pub fn new(scheme: DifferenceScheme) -> Self {
+        FiniteDifferenceJacobian {
+            scheme,
+            sparsity: None,
+            groups: None,
+        }
+    }
+
+    /// Only fills the entries of `pattern`, perturbing structurally independent columns
+    /// together. The other entries of the Jacobian are set to zero.
+    pub fn with_sparsity(self, pattern: SparsityPattern) -> Self {
+        FiniteDifferenceJacobian {
+            groups: Some(pattern.column_groups()),
+            sparsity: Some(pattern),
+            ..self
+        }
+    }
+
+    /// Number of column groups, i.e. perturbed evaluations per scheme direction.
+    pub fn num_groups(&self, dim: usize) -> usize {
+        self.groups.as_ref().map_or(dim, |groups| groups.len())
+    }
+
+    /// Number of evaluations of `f` one Jacobian costs.
+    pub fn evaluations_per_jacobian(&self, dim: usize) -> usize {
+        match self.scheme {
+            DifferenceScheme::Forward => 1 + self.num_groups(dim),
+            DifferenceScheme::Central => 2 * self.num_groups(dim),
+        }
+    }
+
+    /// Perturbation of component `x_j`, rounded so that `x_j + delta` is exact.
+    fn perturbation(&self, x_j: f64) -> f64 {
+        let relative: f64 = match self.scheme {
+            DifferenceScheme::Forward => f64::EPSILON.sqrt(),
+            DifferenceScheme::Central => f64::EPSILON.cbrt(),
+        };
+        let delta: f64 = relative * x_j.abs().max(1.0);
+        (x_j + delta) - x_j
+    }
+
+    /// Writes the Jacobian of `f` at `x` (row-major, N x N) into `jac` and returns the
+    /// number of evaluations of `f`.
+    ///
+    /// Panics if the sparsity pattern or `jac` do not match the dimension of `x`.
+    pub fn jacobian(&self, f: impl Fn(&[f64], &mut [f64]), x: &[f64], jac: &mut [f64]) -> usize {
+        let dim: usize = x.len();
+        if let Some(pattern) = &self.sparsity {
+            assert_eq!(
+                pattern.dimension(),
+                dim,
+                "sparsity pattern of dimension {} used for a state of dimension {}",
+                pattern.dimension(),
+                dim
+            );
+        }
+        assert_eq!(
+            jac.len(),
+            dim * dim,
+            "Jacobian of length {} for a state of dimension {}",
+            jac.len(),
+            dim
+        );
+        let dense_groups: Vec<Vec<usize>>;
+        let groups: &[Vec<usize>] = match &self.groups {
+            Some(groups) => groups,
+            None => {
+                dense_groups = (0..dim).map(|col| vec![col]).collect();
+                &dense_groups
+            }
+        };
+        let deltas: Vec<f64> = x.iter().map(|&x_j| self.perturbation(x_j)).collect();
+        let mut f_plus: Vec<f64> = vec![0.0; dim];
+        let mut f_minus: Vec<f64> = vec![0.0; dim];
+        let mut x_perturbed: Vec<f64> = x.to_vec();
+        let mut evaluations: usize = 0;
+
+        jac.fill(0.0);
+        if self.scheme == DifferenceScheme::Forward {
+            f(x, &mut f_minus);
+            evaluations += 1;
+        }
+
+        for group in groups {
+            for &col in group {
+                x_perturbed[col] = x[col] + deltas[col];
+            }
+            f(&x_perturbed, &mut f_plus);
+            evaluations += 1;
+            if self.scheme == DifferenceScheme::Central {
+                for &col in group {
+                    x_perturbed[col] = x[col] - deltas[col];
+                }
+                f(&x_perturbed, &mut f_minus);
+                evaluations += 1;
+            }
+            for &col in group {
+                x_perturbed[col] = x[col];
+            }
+
+            let width: f64 = match self.scheme {
+                DifferenceScheme::Forward => 1.0,
+                DifferenceScheme::Central => 2.0,
+            };
+            for &col in group {
+                let rows: Vec<usize> = match &self.sparsity {
+                    Some(pattern) => pattern.rows(col).to_vec(),
+                    None => (0..dim).collect(),
+                };
+                for row in rows {
+                    jac[row * dim + col] = (f_plus[row] - f_minus[row]) / (width * deltas[col]);
+                }
+            }
+        }
+        evaluations
+    }
+
+    /// Jacobian `df/dy` of an ODE right hand side at `(t, y)`.
+    pub fn ode_jacobian<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t: f64,
+        y: &[f64],
+        jac: &mut [f64],
+    ) -> usize {
+        self.jacobian(|y: &[f64], dydt: &mut [f64]| system.rhs(t, y, dydt), y, jac)
+    }
+}
+
+/// OdeSystem whose Jacobian is approximated by finite differences of its right hand
+/// side, e.g. to give implicit solvers a sparsity pattern or central differences.
+pub struct FiniteDifferenceSystem<S> {
+    system: S,
+    jacobian: FiniteDifferenceJacobian,
+}
+
+impl<S: OdeSystem> FiniteDifferenceSystem<S> {
+    /// Fails if the sparsity pattern of `jacobian` is not of the dimension of `system`.
+    pub fn new(system: S, jacobian: FiniteDifferenceJacobian) -> Result<Self, OdeError> {
+        if let Some(pattern) = &jacobian.sparsity {
+            if pattern.dimension() != system.dimension() {
+                return Err(OdeError::InvalidParameter {
+                    name: "sparsity",
+                    reason: format!(
+                        "pattern of dimension {} for a system of dimension {}",
+                        pattern.dimension(),
+                        system.dimension()
+                    ),
+                });
+            }
+        }
+        Ok(FiniteDifferenceSystem { system, jacobian })
+    }
+}
+
+impl<S: OdeSystem> OdeSystem for FiniteDifferenceSystem<S> {
+    fn dimension(&self) -> usize {
+        self.system.dimension()
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        self.system.rhs(t, y, dydt)
+    }
+
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        self.jacobian.ode_jacobian(&self.system, t, y, jac);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        self.jacobian
+            .evaluations_per_jacobian(self.system.dimension())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod auto_diff;
+pub mod dual_numbers;
+pub mod finite_difference;
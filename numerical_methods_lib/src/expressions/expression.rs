@@ -0,0 +1,398 @@
+use std::{
+    f64::consts::LN_10,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Functions of one argument that expressions can call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Exp,
+    /// Natural logarithm, also spelt `log`
+    Ln,
+    Log10,
+    Sqrt,
+    Abs,
+    /// -1, 0 or 1; the derivative of `abs`
+    Sign,
+}
+
+impl Function {
+    /// Every function, in the order of [`Function::name`].
+    pub const ALL: [Function; 15] = [
+        Function::Sin,
+        Function::Cos,
+        Function::Tan,
+        Function::Asin,
+        Function::Acos,
+        Function::Atan,
+        Function::Sinh,
+        Function::Cosh,
+        Function::Tanh,
+        Function::Exp,
+        Function::Ln,
+        Function::Log10,
+        Function::Sqrt,
+        Function::Abs,
+        Function::Sign,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Tan => "tan",
+            Function::Asin => "asin",
+            Function::Acos => "acos",
+            Function::Atan => "atan",
+            Function::Sinh => "sinh",
+            Function::Cosh => "cosh",
+            Function::Tanh => "tanh",
+            Function::Exp => "exp",
+            Function::Ln => "ln",
+            Function::Log10 => "log10",
+            Function::Sqrt => "sqrt",
+            Function::Abs => "abs",
+            Function::Sign => "sign",
+        }
+    }
+
+    /// The function called `name` in an expression.
+    pub fn from_name(name: &str) -> Option<Function> {
+        match name {
+            "log" => Some(Function::Ln),
+            _ => Function::ALL
+                .iter()
+                .copied()
+                .find(|function| function.name() == name),
+        }
+    }
+
+    pub fn apply(self, x: f64) -> f64 {
+        match self {
+            Function::Sin => x.sin(),
+            Function::Cos => x.cos(),
+            Function::Tan => x.tan(),
+            Function::Asin => x.asin(),
+            Function::Acos => x.acos(),
+            Function::Atan => x.atan(),
+            Function::Sinh => x.sinh(),
+            Function::Cosh => x.cosh(),
+            Function::Tanh => x.tanh(),
+            Function::Exp => x.exp(),
+            Function::Ln => x.ln(),
+            Function::Log10 => x.log10(),
+            Function::Sqrt => x.sqrt(),
+            Function::Abs => x.abs(),
+            Function::Sign => {
+                if x == 0.0 || x.is_nan() {
+                    x
+                } else {
+                    x.signum()
+                }
+            }
+        }
+    }
+}
+
+/// Parsed arithmetic expression.
+///
+/// Variables are indices into the slice of values given to [`Expr::eval`], so the names
+/// are resolved once when parsing and not on every evaluation. The operators `+ - * /`
+/// and the constructors `pow` and `call` fold constants and drop neutral terms, which
+/// keeps the derivatives short.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Variable(usize),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, Box<Expr>),
+    Call(Function, Box<Expr>),
+}
+
+impl Expr {
+    pub fn number(value: f64) -> Expr {
+        Expr::Number(value)
+    }
+
+    pub fn variable(index: usize) -> Expr {
+        Expr::Variable(index)
+    }
+
+    fn constant(&self) -> Option<f64> {
+        match self {
+            Expr::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn pow(a: Expr, b: Expr) -> Expr {
+        match (a.constant(), b.constant()) {
+            (Some(x), Some(y)) => Expr::Number(x.powf(y)),
+            (_, Some(0.0)) => Expr::Number(1.0),
+            (_, Some(1.0)) => a,
+            _ => Expr::Pow(Box::new(a), Box::new(b)),
+        }
+    }
+
+    pub fn call(function: Function, a: Expr) -> Expr {
+        match a.constant() {
+            Some(x) => Expr::Number(function.apply(x)),
+            None => Expr::Call(function, Box::new(a)),
+        }
+    }
+
+    /// Value of the expression, with variable `i` set to `values[i]`.
+    ///
+    /// # Panics
+    /// If the expression has a variable index beyond `values`.
+    pub fn eval(&self, values: &[f64]) -> f64 {
+        match self {
+            Expr::Number(value) => *value,
+            Expr::Variable(index) => values[*index],
+            Expr::Neg(a) => -a.eval(values),
+            Expr::Add(a, b) => a.eval(values) + b.eval(values),
+            Expr::Sub(a, b) => a.eval(values) - b.eval(values),
+            Expr::Mul(a, b) => a.eval(values) * b.eval(values),
+            Expr::Div(a, b) => a.eval(values) / b.eval(values),
+            Expr::Pow(a, b) => {
+                let exponent: f64 = b.eval(values);
+                // Integer powers stay exact and defined for negative bases
+                if exponent.fract() == 0.0 && exponent.abs() <= i32::MAX as f64 {
+                    a.eval(values).powi(exponent as i32)
+                } else {
+                    a.eval(values).powf(exponent)
+                }
+            }
+            Expr::Call(function, a) => function.apply(a.eval(values)),
+        }
+    }
+
+    /// Whether the expression changes with variable `index`.
+    pub fn depends_on(&self, index: usize) -> bool {
+        match self {
+            Expr::Number(_) => false,
+            Expr::Variable(variable) => *variable == index,
+            Expr::Neg(a) | Expr::Call(_, a) => a.depends_on(index),
+            Expr::Add(a, b)
+            | Expr::Sub(a, b)
+            | Expr::Mul(a, b)
+            | Expr::Div(a, b)
+            | Expr::Pow(a, b) => a.depends_on(index) || b.depends_on(index),
+        }
+    }
+
+    /// Symbolic partial derivative with respect to variable `index`.
+    pub fn derivative(&self, index: usize) -> Expr {
+        if !self.depends_on(index) {
+            return Expr::Number(0.0);
+        }
+        let d = |a: &Expr| a.derivative(index);
+        let number = Expr::Number;
+        match self {
+            Expr::Number(_) => number(0.0),
+            Expr::Variable(_) => number(1.0),
+            Expr::Neg(a) => -d(a),
+            Expr::Add(a, b) => d(a) + d(b),
+            Expr::Sub(a, b) => d(a) - d(b),
+            // (ab)' = a'b + ab'
+            Expr::Mul(a, b) => d(a) * (**b).clone() + (**a).clone() * d(b),
+            // (a/b)' = a'/b - a b'/b^2
+            Expr::Div(a, b) => {
+                d(a) / (**b).clone() - (**a).clone() * d(b) / Expr::pow((**b).clone(), number(2.0))
+            }
+            Expr::Pow(a, b) if !b.depends_on(index) => {
+                // (a^c)' = c a^(c-1) a'
+                (**b).clone() * Expr::pow((**a).clone(), (**b).clone() - number(1.0)) * d(a)
+            }
+            Expr::Pow(a, b) => {
+                // (a^b)' = a^b (b' ln a + b a'/a)
+                let ln_a: Expr = Expr::call(Function::Ln, (**a).clone());
+                self.clone() * (d(b) * ln_a + (**b).clone() * d(a) / (**a).clone())
+            }
+            Expr::Call(function, a) => {
+                let u = || (**a).clone();
+                let square = |e: Expr| Expr::pow(e, number(2.0));
+                let outer: Expr = match function {
+                    Function::Sin => Expr::call(Function::Cos, u()),
+                    Function::Cos => -Expr::call(Function::Sin, u()),
+                    Function::Tan => number(1.0) / square(Expr::call(Function::Cos, u())),
+                    Function::Asin => {
+                        number(1.0) / Expr::call(Function::Sqrt, number(1.0) - square(u()))
+                    }
+                    Function::Acos => {
+                        number(-1.0) / Expr::call(Function::Sqrt, number(1.0) - square(u()))
+                    }
+                    Function::Atan => number(1.0) / (number(1.0) + square(u())),
+                    Function::Sinh => Expr::call(Function::Cosh, u()),
+                    Function::Cosh => Expr::call(Function::Sinh, u()),
+                    Function::Tanh => number(1.0) - square(Expr::call(Function::Tanh, u())),
+                    Function::Exp => Expr::call(Function::Exp, u()),
+                    Function::Ln => number(1.0) / u(),
+                    Function::Log10 => number(1.0) / (number(LN_10) * u()),
+                    Function::Sqrt => number(0.5) / Expr::call(Function::Sqrt, u()),
+                    Function::Abs => Expr::call(Function::Sign, u()),
+                    Function::Sign => number(0.0),
+                };
+                outer * d(a)
+            }
+        }
+    }
+
+    /// The expression as text, with variable `i` written `names[i]`. Parsing the text
+    /// with the same names gives back an equal expression.
+    pub fn format(&self, names: &[&str]) -> String {
+        self.format_with_precedence(names, 0)
+    }
+
+    // Binding strength of the outermost operation, parenthesised below `context`
+    fn format_with_precedence(&self, names: &[&str], context: u8) -> String {
+        let (precedence, text): (u8, String) = match self {
+            Expr::Number(value) if *value < 0.0 => (2, format!("-{}", -value)),
+            Expr::Number(value) => (5, value.to_string()),
+            Expr::Variable(index) => (5, names[*index].to_string()),
+            Expr::Neg(a) => (3, format!("-{}", a.format_with_precedence(names, 3))),
+            Expr::Add(a, b) => (
+                1,
+                format!(
+                    "{} + {}",
+                    a.format_with_precedence(names, 1),
+                    b.format_with_precedence(names, 2)
+                ),
+            ),
+            Expr::Sub(a, b) => (
+                1,
+                format!(
+                    "{} - {}",
+                    a.format_with_precedence(names, 1),
+                    b.format_with_precedence(names, 2)
+                ),
+            ),
+            Expr::Mul(a, b) => (
+                2,
+                format!(
+                    "{}*{}",
+                    a.format_with_precedence(names, 2),
+                    b.format_with_precedence(names, 3)
+                ),
+            ),
+            Expr::Div(a, b) => (
+                2,
+                format!(
+                    "{}/{}",
+                    a.format_with_precedence(names, 2),
+                    b.format_with_precedence(names, 3)
+                ),
+            ),
+            // Right associative, and binds tighter than a leading minus: -x^2 = -(x^2)
+            Expr::Pow(a, b) => (
+                4,
+                format!(
+                    "{}^{}",
+                    a.format_with_precedence(names, 5),
+                    b.format_with_precedence(names, 3)
+                ),
+            ),
+            Expr::Call(function, a) => (
+                5,
+                format!(
+                    "{}({})",
+                    function.name(),
+                    a.format_with_precedence(names, 0)
+                ),
+            ),
+        };
+        if precedence < context {
+            format!("({})", text)
+        } else {
+            text
+        }
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        match self {
+            Expr::Number(value) => Expr::Number(-value),
+            Expr::Neg(inner) => *inner,
+            a => Expr::Neg(Box::new(a)),
+        }
+    }
+}
+
+impl Add for Expr {
+    type Output = Expr;
+
+    fn add(self, b: Expr) -> Expr {
+        match (self.constant(), b.constant()) {
+            (Some(x), Some(y)) => Expr::Number(x + y),
+            (Some(0.0), _) => b,
+            (_, Some(0.0)) => self,
+            _ => match b {
+                Expr::Neg(b) => Expr::Sub(Box::new(self), b),
+                b => Expr::Add(Box::new(self), Box::new(b)),
+            },
+        }
+    }
+}
+
+impl Sub for Expr {
+    type Output = Expr;
+
+    fn sub(self, b: Expr) -> Expr {
+        match (self.constant(), b.constant()) {
+            (Some(x), Some(y)) => Expr::Number(x - y),
+            (Some(0.0), _) => -b,
+            (_, Some(0.0)) => self,
+            _ => match b {
+                Expr::Neg(b) => Expr::Add(Box::new(self), b),
+                b => Expr::Sub(Box::new(self), Box::new(b)),
+            },
+        }
+    }
+}
+
+impl Mul for Expr {
+    type Output = Expr;
+
+    fn mul(self, b: Expr) -> Expr {
+        match (self.constant(), b.constant()) {
+            (Some(x), Some(y)) => Expr::Number(x * y),
+            (Some(0.0), _) | (_, Some(0.0)) => Expr::Number(0.0),
+            (Some(1.0), _) => b,
+            (_, Some(1.0)) => self,
+            (Some(-1.0), _) => -b,
+            (_, Some(-1.0)) => -self,
+            // Constants go first, so `x*2` and `2*x` print the same
+            (None, Some(_)) => Expr::Mul(Box::new(b), Box::new(self)),
+            _ => Expr::Mul(Box::new(self), Box::new(b)),
+        }
+    }
+}
+
+impl Div for Expr {
+    type Output = Expr;
+
+    fn div(self, b: Expr) -> Expr {
+        match (self.constant(), b.constant()) {
+            (Some(x), Some(y)) => Expr::Number(x / y),
+            (Some(0.0), _) => Expr::Number(0.0),
+            (_, Some(1.0)) => self,
+            _ => Expr::Div(Box::new(self), Box::new(b)),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// Errors reported when parsing an expression or building a system from equations.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionError {
+    /// The text is not a valid expression; `position` counts characters from 0.
+    Syntax {
+        text: String,
+        position: usize,
+        reason: String,
+    },
+    /// A name is neither the time, a state variable, a parameter nor a constant.
+    UnknownName { text: String, name: String },
+    /// A name followed by `(` is not one of the supported functions.
+    UnknownFunction { text: String, name: String },
+    /// An equation is not of the form `dx = ...` (or `x' = ...`), or the equations do
+    /// not describe a system.
+    InvalidEquation { equation: String, reason: String },
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpressionError::Syntax {
+                text,
+                position,
+                reason,
+            } => write!(f, "{} at position {} of `{}`", reason, position, text),
+            ExpressionError::UnknownName { text, name } => {
+                write!(f, "unknown name `{}` in `{}`", name, text)
+            }
+            ExpressionError::UnknownFunction { text, name } => {
+                write!(f, "unknown function `{}` in `{}`", name, text)
+            }
+            ExpressionError::InvalidEquation { equation, reason } => {
+                write!(f, "invalid equation `{}`: {}", equation, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
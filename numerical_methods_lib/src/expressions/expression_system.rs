@@ -0,0 +1,204 @@
+use crate::{
+    expressions::{
+        expression::{Expr, Function},
+        expression_error::ExpressionError,
+        parser::parse_expression,
+    },
+    ode_solvers::ode_system::OdeSystem,
+};
+
+/// Name of the time in the equations.
+pub const TIME_NAME: &str = "t";
+
+/// OdeSystem parsed at runtime from equations such as `dx = sigma*(y - x)`, so a new
+/// right hand side needs no rebuild.
+///
+/// The Jacobian is the symbolic derivative of the equations, exact like the one of
+/// [`AutoDiffClosure`](crate::differentiation::auto_diff::AutoDiffClosure).
+///
+/// ```ignore
+/// let lorenz = ExpressionSystem::new(
+///     &["dx = sigma*(y - x)", "dy = x*(rho - z) - y", "dz = x*y - beta*z"],
+///     &[("sigma", 10.0), ("rho", 28.0), ("beta", 8.0 / 3.0)],
+/// )?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpressionSystem {
+    /// The time, the state variables, then the parameters
+    names: Vec<String>,
+    parameters: Vec<f64>,
+    rhs: Vec<Expr>,
+    /// Row-major `d rhs[i] / d y[j]`
+    jacobian: Vec<Expr>,
+}
+
+impl ExpressionSystem {
+    /// Scalar equation `y' = rhs`, in the time `t` and the state `y`.
+    pub fn scalar(rhs: &str, parameters: &[(&str, f64)]) -> Result<Self, ExpressionError> {
+        ExpressionSystem::new(&[&format!("dy = {}", rhs)], parameters)
+    }
+
+    /// System of one equation per state variable, `dx = ...` or `x' = ...` (also
+    /// `dx/dt = ...`), in the order of the state. The right hand sides may use the time
+    /// `t`, the state variables, the `parameters` and the constants `pi` and `e`.
+    pub fn new(equations: &[&str], parameters: &[(&str, f64)]) -> Result<Self, ExpressionError> {
+        if equations.is_empty() {
+            return Err(ExpressionError::InvalidEquation {
+                equation: String::new(),
+                reason: "a system needs at least one equation".to_string(),
+            });
+        }
+        let mut names: Vec<String> = vec![TIME_NAME.to_string()];
+        let mut right_sides: Vec<&str> = Vec::with_capacity(equations.len());
+        for equation in equations {
+            let (state, rhs) = split_equation(equation)?;
+            if names.contains(&state) {
+                return Err(ExpressionError::InvalidEquation {
+                    equation: equation.to_string(),
+                    reason: format!("`{}` is already a variable of the system", state),
+                });
+            }
+            names.push(state);
+            right_sides.push(rhs);
+        }
+        for (parameter, _) in parameters {
+            if !is_name(parameter) || names.iter().any(|name| name == parameter) {
+                return Err(ExpressionError::InvalidEquation {
+                    equation: parameter.to_string(),
+                    reason: "parameter names must be new names".to_string(),
+                });
+            }
+            names.push(parameter.to_string());
+        }
+
+        let name_refs: Vec<&str> = names.iter().map(String::as_str).collect();
+        let rhs: Vec<Expr> = right_sides
+            .iter()
+            .map(|text| parse_expression(text, &name_refs))
+            .collect::<Result<_, _>>()?;
+        let dimension: usize = equations.len();
+        let jacobian: Vec<Expr> = rhs
+            .iter()
+            .flat_map(|f_i| (0..dimension).map(move |j| f_i.derivative(1 + j)))
+            .collect();
+
+        Ok(ExpressionSystem {
+            names,
+            parameters: parameters.iter().map(|&(_, value)| value).collect(),
+            rhs,
+            jacobian,
+        })
+    }
+
+    /// Names of the state variables, in the order of the state.
+    pub fn state_names(&self) -> Vec<&str> {
+        self.names[1..=self.rhs.len()]
+            .iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Changes the value of a parameter, e.g. for a parameter sweep.
+    pub fn set_parameter(&mut self, name: &str, value: f64) -> Result<(), ExpressionError> {
+        let first_parameter: usize = 1 + self.rhs.len();
+        match self.names[first_parameter..]
+            .iter()
+            .position(|parameter| parameter == name)
+        {
+            Some(index) => {
+                self.parameters[index] = value;
+                Ok(())
+            }
+            None => Err(ExpressionError::UnknownName {
+                text: String::new(),
+                name: name.to_string(),
+            }),
+        }
+    }
+
+    /// Right hand side of equation `row` as text.
+    pub fn rhs_text(&self, row: usize) -> String {
+        self.rhs[row].format(&self.name_refs())
+    }
+
+    /// Jacobian entry `d rhs[row] / d y[col]` as text, e.g. to check it by eye.
+    pub fn jacobian_text(&self, row: usize, col: usize) -> String {
+        self.jacobian[row * self.rhs.len() + col].format(&self.name_refs())
+    }
+
+    fn name_refs(&self) -> Vec<&str> {
+        self.names.iter().map(String::as_str).collect()
+    }
+
+    /// Values of the variables in the order of `names`.
+    fn values(&self, t: f64, y: &[f64]) -> Vec<f64> {
+        let mut values: Vec<f64> = Vec::with_capacity(self.names.len());
+        values.push(t);
+        values.extend_from_slice(y);
+        values.extend_from_slice(&self.parameters);
+        values
+    }
+}
+
+impl OdeSystem for ExpressionSystem {
+    fn dimension(&self) -> usize {
+        self.rhs.len()
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        let values: Vec<f64> = self.values(t, y);
+        for (value, f_i) in dydt.iter_mut().zip(&self.rhs) {
+            *value = f_i.eval(&values);
+        }
+    }
+
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        let values: Vec<f64> = self.values(t, y);
+        for (value, df_ij) in jac.iter_mut().zip(&self.jacobian) {
+            *value = df_ij.eval(&values);
+        }
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+fn is_name(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && Function::from_name(text).is_none()
+}
+
+/// Splits `dx = rhs` into the state name and the right hand side.
+fn split_equation(equation: &str) -> Result<(String, &str), ExpressionError> {
+    let invalid = |reason: &str| ExpressionError::InvalidEquation {
+        equation: equation.to_string(),
+        reason: reason.to_string(),
+    };
+    let (lhs, rhs) = equation
+        .split_once('=')
+        .ok_or_else(|| invalid("expected `dx = ...` or `x' = ...`"))?;
+    let lhs: &str = lhs.trim();
+    let state: &str = if let Some(state) = lhs.strip_suffix('\'') {
+        state
+    } else if let Some(state) = lhs
+        .strip_prefix('d')
+        .and_then(|rest| rest.strip_suffix("/dt"))
+    {
+        state
+    } else if let Some(state) = lhs.strip_prefix('d') {
+        state
+    } else {
+        return Err(invalid("the left hand side must be `dx`, `dx/dt` or `x'`"));
+    };
+    let state: &str = state.trim();
+    if !is_name(state) || state == TIME_NAME {
+        return Err(invalid(&format!(
+            "`{}` cannot name a state variable",
+            state
+        )));
+    }
+    Ok((state.to_string(), rhs.trim()))
+}
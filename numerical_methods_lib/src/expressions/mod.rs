@@ -0,0 +1,4 @@
+pub mod expression;
+pub mod expression_error;
+pub mod expression_system;
+pub mod parser;
@@ -0,0 +1,210 @@
+use crate::expressions::{
+    expression::{Expr, Function},
+    expression_error::ExpressionError,
+};
+use std::f64::consts::{E, PI};
+
+/// Named constants, usable wherever they are not shadowed by a variable.
+pub const CONSTANTS: [(&str, f64); 2] = [("pi", PI), ("e", E)];
+
+/// Parses `text` into an expression over the variables `names`: a name `names[i]`
+/// becomes [`Expr::Variable`]`(i)`.
+///
+/// The grammar has numbers (`2`, `0.5`, `1e-3`), names, the operators `+ - * / ^` (also
+/// `**`) with the usual precedence, `^` binding tighter than a leading minus and
+/// associating to the right, parentheses, the calls of [`Function`] (`sin(x)`, `exp(-t)`,
+/// ...) and the constants `pi` and `e`.
+///
+/// ```ignore
+/// let rhs = parse_expression("-20*t*y^2", &["t", "y"])?;
+/// assert_eq!(rhs.eval(&[0.5, 1.0]), -10.0);
+/// ```
+pub fn parse_expression(text: &str, names: &[&str]) -> Result<Expr, ExpressionError> {
+    let mut parser = Parser {
+        text,
+        chars: text.chars().collect(),
+        position: 0,
+        names,
+    };
+    let expr: Expr = parser.sum()?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(expr),
+        Some(')') => Err(parser.syntax_error("unmatched `)`")),
+        Some(c) => Err(parser.syntax_error(&format!("expected an operator, found `{}`", c))),
+    }
+}
+
+/// Recursive descent parser, one method per precedence level.
+struct Parser<'t> {
+    text: &'t str,
+    chars: Vec<char>,
+    position: usize,
+    names: &'t [&'t str],
+}
+
+impl<'t> Parser<'t> {
+    fn syntax_error(&self, reason: &str) -> ExpressionError {
+        ExpressionError::Syntax {
+            text: self.text.to_string(),
+            position: self.position,
+            reason: reason.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    /// Consumes `token` if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let matches: bool = token
+            .chars()
+            .enumerate()
+            .all(|(offset, c)| self.chars.get(self.position + offset) == Some(&c));
+        if matches {
+            self.position += token.chars().count();
+        }
+        matches
+    }
+
+    // sum := product (('+' | '-') product)*
+    fn sum(&mut self) -> Result<Expr, ExpressionError> {
+        let mut expr: Expr = self.product()?;
+        loop {
+            if self.eat("+") {
+                expr = expr + self.product()?;
+            } else if self.eat("-") {
+                expr = expr - self.product()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // product := unary (('*' | '/') unary)*
+    fn product(&mut self) -> Result<Expr, ExpressionError> {
+        let mut expr: Expr = self.unary()?;
+        loop {
+            self.skip_whitespace();
+            // `**` is the power, handled below
+            if self.peek() == Some('*') && self.chars.get(self.position + 1) != Some(&'*') {
+                self.position += 1;
+                expr = expr * self.unary()?;
+            } else if self.eat("/") {
+                expr = expr / self.unary()?;
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    // unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.eat("-") {
+            Ok(-self.unary()?)
+        } else if self.eat("+") {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    // power := primary (('^' | '**') unary)?
+    fn power(&mut self) -> Result<Expr, ExpressionError> {
+        let base: Expr = self.primary()?;
+        if self.eat("^") || self.eat("**") {
+            Ok(Expr::pow(base, self.unary()?))
+        } else {
+            Ok(base)
+        }
+    }
+
+    // primary := number | name | name '(' sum ')' | '(' sum ')'
+    fn primary(&mut self) -> Result<Expr, ExpressionError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('(') => {
+                self.position += 1;
+                let expr: Expr = self.sum()?;
+                if self.eat(")") {
+                    Ok(expr)
+                } else {
+                    Err(self.syntax_error("expected `)`"))
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.name(),
+            Some(c) => Err(self.syntax_error(&format!("expected a value, found `{}`", c))),
+            None => Err(self.syntax_error("unexpected end of the expression")),
+        }
+    }
+
+    fn number(&mut self) -> Result<Expr, ExpressionError> {
+        let start: usize = self.position;
+        let digits = |parser: &mut Parser| {
+            while parser.peek().is_some_and(|c| c.is_ascii_digit()) {
+                parser.position += 1;
+            }
+        };
+        digits(self);
+        if self.peek() == Some('.') {
+            self.position += 1;
+            digits(self);
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            // An exponent needs digits, otherwise `2e` is left for the next token
+            let mut end: usize = self.position + 1;
+            if matches!(self.chars.get(end), Some('+' | '-')) {
+                end += 1;
+            }
+            if self.chars.get(end).is_some_and(|c| c.is_ascii_digit()) {
+                self.position = end;
+                digits(self);
+            }
+        }
+        let literal: String = self.chars[start..self.position].iter().collect();
+        literal.parse::<f64>().map(Expr::number).map_err(|_| {
+            self.position = start;
+            self.syntax_error(&format!("invalid number `{}`", literal))
+        })
+    }
+
+    fn name(&mut self) -> Result<Expr, ExpressionError> {
+        let start: usize = self.position;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+        let name: String = self.chars[start..self.position].iter().collect();
+
+        if self.eat("(") {
+            let function: Function =
+                Function::from_name(&name).ok_or_else(|| ExpressionError::UnknownFunction {
+                    text: self.text.to_string(),
+                    name: name.clone(),
+                })?;
+            let argument: Expr = self.sum()?;
+            if !self.eat(")") {
+                return Err(self.syntax_error("expected `)` after the function argument"));
+            }
+            return Ok(Expr::call(function, argument));
+        }
+        if let Some(index) = self.names.iter().position(|&variable| variable == name) {
+            return Ok(Expr::variable(index));
+        }
+        match CONSTANTS.iter().find(|(constant, _)| *constant == name) {
+            Some(&(_, value)) => Ok(Expr::number(value)),
+            None => Err(ExpressionError::UnknownName {
+                text: self.text.to_string(),
+                name,
+            }),
+        }
+    }
+}
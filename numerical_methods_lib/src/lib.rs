@@ -1,2 +1,10 @@
+pub mod comparison;
+pub mod differentiation;
+pub mod expressions;
+pub mod linear_solvers;
 pub mod ode_solvers;
+pub mod output;
+#[cfg(any(feature = "plot", feature = "static-plot"))]
+pub mod plotting;
+pub mod problems;
 pub mod root_finders;
@@ -0,0 +1,66 @@
+/// LU decomposition with partial pivoting of a dense N x N matrix.
+///
+/// The matrix is stored row-major in a flat slice, the same layout used for
+/// Jacobians in `OdeSystem::jacobian`.
+pub struct LuDecomposition {
+    dim: usize,
+    lu: Vec<f64>,
+    pivots: Vec<usize>,
+}
+
+impl LuDecomposition {
+    /// Factorises `matrix` (row-major, `dim` x `dim`).
+    /// Returns `None` if the matrix is singular.
+    pub fn new(matrix: &[f64], dim: usize) -> Option<Self> {
+        let mut lu = matrix.to_vec();
+        let mut pivots: Vec<usize> = (0..dim).collect();
+
+        for col in 0..dim {
+            // Pick the largest entry in the column as pivot for numerical stability
+            let pivot_row = (col..dim)
+                .max_by(|&a, &b| lu[a * dim + col].abs().total_cmp(&lu[b * dim + col].abs()))
+                .unwrap();
+            if lu[pivot_row * dim + col] == 0.0 || !lu[pivot_row * dim + col].is_finite() {
+                return None;
+            }
+            if pivot_row != col {
+                for k in 0..dim {
+                    lu.swap(col * dim + k, pivot_row * dim + k);
+                }
+                pivots.swap(col, pivot_row);
+            }
+
+            let pivot = lu[col * dim + col];
+            for row in (col + 1)..dim {
+                let factor = lu[row * dim + col] / pivot;
+                lu[row * dim + col] = factor;
+                for k in (col + 1)..dim {
+                    lu[row * dim + k] -= factor * lu[col * dim + k];
+                }
+            }
+        }
+
+        Some(LuDecomposition { dim, lu, pivots })
+    }
+
+    /// Solves `A x = rhs` in place, `rhs` is overwritten with `x`.
+    pub fn solve(&self, rhs: &mut [f64]) {
+        let dim = self.dim;
+        let permuted: Vec<f64> = self.pivots.iter().map(|&p| rhs[p]).collect();
+        rhs.copy_from_slice(&permuted);
+
+        // Forward substitution (L has a unit diagonal)
+        for row in 0..dim {
+            for k in 0..row {
+                rhs[row] -= self.lu[row * dim + k] * rhs[k];
+            }
+        }
+        // Backward substitution
+        for row in (0..dim).rev() {
+            for k in (row + 1)..dim {
+                rhs[row] -= self.lu[row * dim + k] * rhs[k];
+            }
+            rhs[row] /= self.lu[row * dim + row];
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod lu_decomposition;
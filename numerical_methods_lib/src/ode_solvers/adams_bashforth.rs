@@ -0,0 +1,208 @@
+use crate::ode_solvers::{
+    butcher_tableau::ButcherTableau,
+    dense_output::StepInterpolant,
+    events::{EventLocator, StepOutcome},
+    explicit_runge_kutta::explicit_rk_step,
+    ode_error::{check_finite, OdeError},
+    ode_solution::OdeSolution,
+    ode_solver::{OdeSolver, Printable, Solve},
+    ode_system::OdeSystem,
+    stepper::{OdeStepper, StepMethod},
+};
+use tracing::{info, info_span};
+
+pub const MAX_ADAMS_ORDER: usize = 5;
+
+// Adams-Bashforth weights of f_n, f_{n-1}, ... for orders 1 to 5
+const BASHFORTH: [&[f64]; MAX_ADAMS_ORDER] = [
+    &[1.0],
+    &[3.0 / 2.0, -1.0 / 2.0],
+    &[23.0 / 12.0, -16.0 / 12.0, 5.0 / 12.0],
+    &[55.0 / 24.0, -59.0 / 24.0, 37.0 / 24.0, -9.0 / 24.0],
+    &[
+        1901.0 / 720.0,
+        -2774.0 / 720.0,
+        2616.0 / 720.0,
+        -1274.0 / 720.0,
+        251.0 / 720.0,
+    ],
+];
+// Adams-Moulton weights of f_{n+1}, f_n, f_{n-1}, ... for orders 1 to 5
+const MOULTON: [&[f64]; MAX_ADAMS_ORDER] = [
+    &[1.0],
+    &[1.0 / 2.0, 1.0 / 2.0],
+    &[5.0 / 12.0, 8.0 / 12.0, -1.0 / 12.0],
+    &[9.0 / 24.0, 19.0 / 24.0, -5.0 / 24.0, 1.0 / 24.0],
+    &[
+        251.0 / 720.0,
+        646.0 / 720.0,
+        -264.0 / 720.0,
+        106.0 / 720.0,
+        -19.0 / 720.0,
+    ],
+];
+
+/// Implements the explicit Adams-Bashforth Methods of order 1 to 5.
+///
+/// One evaluation of f per step once `order` past derivatives are available; the
+/// starting values are computed with Runge Kutta 4 steps.
+pub struct AdamsBashforthSolver<'a> {
+    pub solver: Box<OdeSolver<'a>>,
+    pub order: usize,
+}
+
+/// Fixed step Adams loop shared by the Adams-Bashforth and the Adams-Moulton solvers.
+///
+/// Without `corrector` every step is an Adams-Bashforth step. With `corrector` the
+/// Adams-Bashforth result is used as predictor for an Adams-Moulton step of the same
+/// order (PECE: predict, evaluate, correct, evaluate). Steps without enough history and
+/// a final step shorter than `time_step` are taken with Runge Kutta 4.
+struct AdamsMethod<'s> {
+    system: &'s dyn OdeSystem,
+    bashforth: &'static [f64],
+    moulton: &'static [f64],
+    corrector: bool,
+    grid: Vec<f64>,
+    /// Index of the grid point the next step aims at
+    index: usize,
+    /// Signed regular step
+    h: f64,
+    /// Derivatives at the last `order` points, newest first
+    history: Vec<Vec<f64>>,
+    rk4: ButcherTableau,
+    stages: Vec<Vec<f64>>,
+}
+
+/// Stepper of the Adams method of the given order, with or without corrector.
+pub(crate) fn adams_stepper<'s>(
+    solver: &'s OdeSolver,
+    order: usize,
+    corrector: bool,
+    initial_state: &[f64],
+) -> Result<OdeStepper<'s>, OdeError> {
+    if !(1..=MAX_ADAMS_ORDER).contains(&order) {
+        return Err(OdeError::InvalidParameter {
+            name: "order",
+            reason: format!("must be between 1 and {}, got {}", MAX_ADAMS_ORDER, order),
+        });
+    }
+    solver.validate_initial_state(initial_state)?;
+    let dim: usize = initial_state.len();
+    let grid: Vec<f64> = solver.params.time_grid();
+    let rk4: ButcherTableau = ButcherTableau::rk4();
+
+    let mut solution = OdeSolution::new(grid[0], initial_state);
+    let mut history: Vec<Vec<f64>> = vec![vec![0.0; dim]];
+    solver.system.rhs(grid[0], initial_state, &mut history[0]);
+    solution.stats.num_rhs_evals += 1;
+
+    let method = AdamsMethod {
+        system: solver.system,
+        bashforth: BASHFORTH[order - 1],
+        moulton: MOULTON[order - 1],
+        corrector,
+        grid,
+        index: 1,
+        h: solver.params.time_span.direction() * solver.params.time_step,
+        history,
+        stages: vec![vec![0.0; dim]; rk4.stages()],
+        rk4,
+    };
+    Ok(OdeStepper::new(solver, solution, Box::new(method)))
+}
+
+impl<'s> StepMethod for AdamsMethod<'s> {
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError> {
+        if self.index >= self.grid.len() {
+            return Ok(false);
+        }
+        let order: usize = self.bashforth.len();
+        let dim: usize = self.history[0].len();
+        let t_next: f64 = self.grid[self.index];
+        let (t_i, y_i): (f64, Vec<f64>) = solution.last().map(|(t, y)| (t, y.clone())).unwrap();
+        let h_i: f64 = t_next - t_i;
+        let mut f_next: Vec<f64> = vec![0.0; dim];
+
+        let uniform: bool = (h_i - self.h).abs() <= 1e-9 * self.h.abs();
+        let sol: Vec<f64> = if self.history.len() < order || !uniform {
+            solution.stats.num_rhs_evals += self.rk4.stages();
+            explicit_rk_step(self.system, &self.rk4, t_i, &y_i, h_i, &mut self.stages)
+        } else {
+            let predictor: Vec<f64> = (0..dim)
+                .map(|j| {
+                    y_i[j]
+                        + h_i
+                            * self
+                                .bashforth
+                                .iter()
+                                .zip(&self.history)
+                                .map(|(beta, f)| beta * f[j])
+                                .sum::<f64>()
+                })
+                .collect();
+            if self.corrector {
+                self.system.rhs(t_next, &predictor, &mut f_next);
+                solution.stats.num_rhs_evals += 1;
+                (0..dim)
+                    .map(|j| {
+                        y_i[j]
+                            + h_i
+                                * (self.moulton[0] * f_next[j]
+                                    + self.moulton[1..]
+                                        .iter()
+                                        .zip(&self.history)
+                                        .map(|(beta, f)| beta * f[j])
+                                        .sum::<f64>())
+                    })
+                    .collect()
+            } else {
+                predictor
+            }
+        };
+        check_finite(t_next, &sol)?;
+
+        self.system.rhs(t_next, &sol, &mut f_next);
+        solution.stats.num_rhs_evals += 1;
+        solution.stats.accepted_steps += 1;
+        // Cubic Hermite from the derivatives already in the history
+        let f_i: &[f64] = &self.history[0];
+        let outcome: StepOutcome = events.advance_with(solution, t_next, sol, |sol| {
+            StepInterpolant::hermite(t_i, &y_i, f_i, t_next, sol, &f_next)
+        })?;
+        self.history.insert(0, f_next);
+        // The weights assume equally spaced points, a shorter step starts a new history
+        self.history.truncate(if uniform { order } else { 1 });
+
+        if outcome == StepOutcome::Reset {
+            // The past derivatives belong to the state before the reset, start over
+            let (t_reset, y_reset) = solution.last().unwrap();
+            self.history.truncate(1);
+            self.system.rhs(t_reset, y_reset, &mut self.history[0]);
+            solution.stats.num_rhs_evals += 1;
+        }
+        // After a reset before the grid point, the next step aims at the same point
+        if solution.last().unwrap().0 == t_next {
+            self.index += 1;
+        }
+        Ok(true)
+    }
+}
+
+impl<'a> Solve for AdamsBashforthSolver<'a> {
+    /// Solves the ODE with the Adams-Bashforth solver.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = "Adams-Bashforth").entered();
+        info!("starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
+    }
+
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        adams_stepper(&self.solver, self.order, false, initial_state)
+    }
+}
+
+impl<'a> Printable for AdamsBashforthSolver<'a> {}
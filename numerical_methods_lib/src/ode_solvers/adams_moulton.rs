@@ -0,0 +1,33 @@
+use crate::ode_solvers::{
+    adams_bashforth::adams_stepper,
+    ode_error::OdeError,
+    ode_solution::OdeSolution,
+    ode_solver::{OdeSolver, Printable, Solve},
+    stepper::OdeStepper,
+};
+use tracing::{info, info_span};
+
+/// Implements the Adams-Bashforth-Moulton predictor-corrector Methods of order 1 to 5.
+///
+/// Each step predicts with Adams-Bashforth and corrects once with Adams-Moulton of the
+/// same order (PECE), two evaluations of f per step. The starting values are computed
+/// with Runge Kutta 4 steps.
+pub struct AdamsMoultonSolver<'a> {
+    pub solver: Box<OdeSolver<'a>>,
+    pub order: usize,
+}
+
+impl<'a> Solve for AdamsMoultonSolver<'a> {
+    /// Solves the ODE with the Adams-Moulton predictor-corrector solver.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = "Adams-Moulton").entered();
+        info!("starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
+    }
+
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        adams_stepper(&self.solver, self.order, true, initial_state)
+    }
+}
+
+impl<'a> Printable for AdamsMoultonSolver<'a> {}
@@ -0,0 +1,429 @@
+use crate::{
+    linear_solvers::lu_decomposition::LuDecomposition,
+    ode_solvers::{
+        events::{EventLocator, StepOutcome},
+        ode_error::{check_finite, OdeError},
+        ode_solution::{NewtonStepInfo, OdeSolution, SolverStatus},
+        ode_solver::{OdeSolver, Printable, Solve},
+        stepper::{OdeStepper, StepMethod},
+    },
+    root_finders::newton_raphson_method::{
+        newton_raphson_chord_solve, NewtonOptions, TerminationReason,
+    },
+};
+use std::cell::Cell;
+use tracing::{debug, info, info_span, trace};
+
+const MAX_ORDER: usize = 5;
+const SAFETY: f64 = 0.9;
+const MIN_FACTOR: f64 = 0.2;
+// Variable step BDF formulas lose zero-stability for large step ratios
+const MAX_FACTOR: f64 = 2.0;
+// Step size changes below this ratio are skipped to keep the iteration matrix
+const MIN_CHANGE: f64 = 1.2;
+// Step reduction after the Newton iteration failed with a fresh Jacobian
+const NEWTON_FAILURE_FACTOR: f64 = 0.25;
+const MAX_NEWTON_ITERS: i32 = 7;
+const MAX_STEPS: usize = 100_000;
+
+/// Implements the variable-order (1 to 5), variable-step Backward Differentiation Formulas.
+///
+/// The integration runs over `time_span`, with `time_step` used as the initial step size.
+/// The BDF coefficients are computed from the actual past time points, so step size and
+/// order changes need no interpolation of the history. Each step solves the implicit
+/// system with a chord Newton iteration; the Jacobian is reused across steps and only
+/// re-evaluated when the iteration fails to converge. Steps are accepted when the local
+/// error estimate is within `tolerance` (absolute) and `rel_tolerance` (relative).
+pub struct BdfSolver<'a> {
+    pub solver: Box<OdeSolver<'a>>,
+}
+
+/// Coefficients `alpha` of `sum_j alpha[j] * y(nodes[j]) ~ y'(nodes[0])`, the derivative
+/// at `nodes[0]` of the polynomial interpolating the values at `nodes`.
+fn bdf_coefficients(nodes: &[f64]) -> Vec<f64> {
+    let x_0: f64 = nodes[0];
+    let mut alpha: Vec<f64> = vec![0.0; nodes.len()];
+    alpha[0] = nodes[1..].iter().map(|x_m| 1.0 / (x_0 - x_m)).sum();
+    for j in 1..nodes.len() {
+        let numerator: f64 = (1..nodes.len())
+            .filter(|&m| m != j)
+            .map(|m| x_0 - nodes[m])
+            .product();
+        let denominator: f64 = (0..nodes.len())
+            .filter(|&m| m != j)
+            .map(|m| nodes[j] - nodes[m])
+            .product();
+        alpha[j] = numerator / denominator;
+    }
+    alpha
+}
+
+/// Evaluates the polynomial interpolating `states` at `times` in `t`.
+fn lagrange_extrapolate(times: &[f64], states: &[Vec<f64>], t: f64) -> Vec<f64> {
+    let mut value: Vec<f64> = vec![0.0; states[0].len()];
+    for j in 0..times.len() {
+        let weight: f64 = (0..times.len())
+            .filter(|&m| m != j)
+            .map(|m| (t - times[m]) / (times[j] - times[m]))
+            .product();
+        for (v, y) in value.iter_mut().zip(&states[j]) {
+            *v += weight * y;
+        }
+    }
+    value
+}
+
+/// Highest order divided difference of `states` at `times`.
+fn divided_difference(times: &[f64], states: &[&[f64]]) -> Vec<f64> {
+    let mut table: Vec<Vec<f64>> = states.iter().map(|y| y.to_vec()).collect();
+    for level in 1..times.len() {
+        for j in 0..(times.len() - level) {
+            let dt: f64 = times[j + level] - times[j];
+            for c in 0..table[j].len() {
+                table[j][c] = (table[j + 1][c] - table[j][c]) / dt;
+            }
+        }
+    }
+    table.swap_remove(0)
+}
+
+/// Variable-order BDF loop. The history is the solution from index `history_start` on.
+struct BdfMethod<'s> {
+    solver: &'s OdeSolver<'s>,
+    /// Signed step, negative when integrating backwards
+    h: f64,
+    order: usize,
+    steps_at_order: usize,
+    /// f at the first point of the history, only needed for the predictor of the first step
+    f_initial: Vec<f64>,
+    /// The BDF history starts at this index of the solution, it is cleared by state resets
+    history_start: usize,
+    jacobian: Vec<f64>,
+    jacobian_current: bool,
+    /// Factorised iteration matrix I - gamma * J together with its gamma
+    iteration_matrix: Option<(f64, LuDecomposition)>,
+    /// Step attempts so far, accepted or rejected
+    attempts: usize,
+}
+
+impl<'s> BdfMethod<'s> {
+    /// Weighted RMS norm of an error vector, scaled by the tolerances.
+    fn error_norm(&self, error: &[f64], y_old: &[f64], y_new: &[f64]) -> f64 {
+        let params = self.solver.params;
+        ((0..error.len())
+            .map(|j| {
+                let scale: f64 =
+                    params.tolerance + params.rel_tolerance * y_old[j].abs().max(y_new[j].abs());
+                (error[j] / scale).powi(2)
+            })
+            .sum::<f64>()
+            / error.len() as f64)
+            .sqrt()
+    }
+
+    /// Local error estimate of order `order` for the last point of `solution`, from the
+    /// divided difference over the last `order + 2` points.
+    fn order_error(&self, solution: &OdeSolution, order: usize) -> f64 {
+        let n: usize = solution.len();
+        let times: &[f64] = &solution.times[n - order - 2..];
+        let states: Vec<&[f64]> = solution.states[n - order - 2..]
+            .iter()
+            .map(|y| y.as_slice())
+            .collect();
+        let t_new: f64 = times[order + 1];
+        let span: f64 = times[..=order].iter().map(|t| t_new - t).product();
+        let error: Vec<f64> = divided_difference(times, &states)
+            .iter()
+            .map(|d| d * span / (order + 1) as f64)
+            .collect();
+        self.error_norm(&error, states[order], states[order + 1])
+    }
+
+    /// Attempts steps until one is accepted, counting the evaluations of f in `rhs_evals`.
+    fn attempt_steps(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+        rhs_evals: &Cell<usize>,
+    ) -> Result<bool, OdeError> {
+        let params = self.solver.params;
+        let system = self.solver.system;
+        let t_final: f64 = params.time_span.tf;
+        let direction: f64 = params.time_span.direction();
+        let dim: usize = self.f_initial.len();
+        let (t, y): (f64, Vec<f64>) = solution.last().map(|(t, y)| (t, y.clone())).unwrap();
+
+        loop {
+            if direction * (t_final - t) <= 0.0 {
+                return Ok(false);
+            }
+            if self.attempts == MAX_STEPS {
+                solution.status = SolverStatus::MaxStepsReached;
+                return Ok(false);
+            }
+            self.attempts += 1;
+            // Do not step past the final time, nor stop short of it by less than a step
+            // can resolve. The last step ends exactly at the final time, t + (tf - t)
+            // may round to a neighbouring time.
+            let t_new: f64 = if direction * (t_final - t - self.h)
+                <= 16.0 * f64::EPSILON * t_final.abs().max(1.0)
+            {
+                self.h = t_final - t;
+                t_final
+            } else {
+                t + self.h
+            };
+            let h: f64 = self.h;
+            if h.abs() <= 16.0 * f64::EPSILON * t.abs().max(1.0) {
+                return Err(OdeError::StepSizeUnderflow { t, step: h });
+            }
+
+            let len: usize = solution.len();
+            let n: usize = len - self.history_start;
+            // Order k uses the k last points, the predictor one more
+            let k: usize = if n == 1 { 1 } else { self.order.min(n - 1) };
+            let mut nodes: Vec<f64> = vec![t_new];
+            nodes.extend(solution.times[len - k..].iter().rev());
+            let alpha: Vec<f64> = bdf_coefficients(&nodes);
+
+            // The step solves z = psi + gamma * f(t_new, z)
+            let gamma: f64 = 1.0 / alpha[0];
+            let mut psi: Vec<f64> = vec![0.0; dim];
+            for (j, y_j) in solution.states[len - k..].iter().rev().enumerate() {
+                for c in 0..dim {
+                    psi[c] -= gamma * alpha[j + 1] * y_j[c];
+                }
+            }
+            let predictor: Vec<f64> = if n == 1 {
+                (0..dim).map(|c| y[c] + h * self.f_initial[c]).collect()
+            } else {
+                lagrange_extrapolate(
+                    &solution.times[len - k - 1..],
+                    &solution.states[len - k - 1..],
+                    t_new,
+                )
+            };
+
+            // The factorisation is reused as long as gamma (step size and order) is unchanged
+            if !matches!(&self.iteration_matrix, Some((matrix_gamma, _)) if *matrix_gamma == gamma)
+            {
+                // I - gamma * J
+                let matrix: Vec<f64> = (0..dim * dim)
+                    .map(|index| {
+                        let identity = if index / dim == index % dim { 1.0 } else { 0.0 };
+                        identity - gamma * self.jacobian[index]
+                    })
+                    .collect();
+                self.iteration_matrix = LuDecomposition::new(&matrix, dim).map(|lu| (gamma, lu));
+            }
+            let lu: &LuDecomposition = match &self.iteration_matrix {
+                Some((_, lu)) => lu,
+                None => {
+                    // Singular iteration matrix, a smaller step changes gamma
+                    debug!(
+                        t,
+                        h,
+                        order = k,
+                        "singular iteration matrix, reducing the step"
+                    );
+                    self.h *= NEWTON_FAILURE_FACTOR;
+                    solution.stats.rejected_steps += 1;
+                    continue;
+                }
+            };
+
+            // g(z) = z - psi - gamma * f(t_new, z)
+            let g = |z: &[f64], residual: &mut [f64]| {
+                rhs_evals.set(rhs_evals.get() + 1);
+                system.rhs(t_new, z, residual);
+                for c in 0..dim {
+                    residual[c] = z[c] - psi[c] - gamma * residual[c];
+                }
+            };
+            let newton_result = newton_raphson_chord_solve(
+                g,
+                lu,
+                predictor.clone(),
+                &NewtonOptions {
+                    line_search: false,
+                    ..NewtonOptions::new(params.tolerance, params.max_iters.min(MAX_NEWTON_ITERS))
+                },
+            );
+            solution.stats.num_newton_iters += newton_result.iterations as usize;
+
+            if !newton_result.converged {
+                debug!(
+                    t,
+                    h,
+                    order = k,
+                    newton_iterations = newton_result.iterations,
+                    reason = ?newton_result.reason,
+                    "Newton iteration did not converge"
+                );
+                if newton_result.reason == TerminationReason::NonFinite || self.jacobian_current {
+                    // The Jacobian is up to date, only a smaller step can help
+                    self.h *= NEWTON_FAILURE_FACTOR;
+                } else {
+                    system.jacobian(t, &y, &mut self.jacobian);
+                    solution.stats.num_jacobian_evals += 1;
+                    solution.stats.num_rhs_evals += system.jacobian_rhs_evals();
+                    self.jacobian_current = true;
+                    self.iteration_matrix = None;
+                }
+                solution.stats.rejected_steps += 1;
+                continue;
+            }
+
+            let y_new: Vec<f64> = newton_result.root;
+            // Difference of corrector and predictor estimates the local error
+            let error: Vec<f64> = (0..dim)
+                .map(|c| (y_new[c] - predictor[c]) / (k + 1) as f64)
+                .collect();
+            let err: f64 = self.error_norm(&error, &y, &y_new);
+            trace!(
+                attempt = self.attempts,
+                t,
+                h,
+                order = k,
+                newton_iterations = newton_result.iterations,
+                error = err,
+                accepted = err <= 1.0,
+                "step attempt"
+            );
+
+            if err > 1.0 {
+                self.h *= (SAFETY * err.powf(-1.0 / (k + 1) as f64)).clamp(MIN_FACTOR, 1.0);
+                solution.stats.rejected_steps += 1;
+                continue;
+            }
+
+            check_finite(t_new, &y_new)?;
+            solution.newton_steps.push(NewtonStepInfo {
+                t: t_new,
+                iterations: newton_result.iterations,
+                residual: newton_result.residual,
+                reason: newton_result.reason,
+            });
+            solution.stats.accepted_steps += 1;
+            self.jacobian_current = false;
+            match events.advance(solution, t_new, y_new)? {
+                StepOutcome::Continue => {}
+                StepOutcome::Terminated => return Ok(true),
+                StepOutcome::Reset => {
+                    // Restart at order 1 from the reset state
+                    self.history_start = solution.len() - 1;
+                    let (t_reset, y_reset) = solution.last().unwrap();
+                    system.rhs(t_reset, y_reset, &mut self.f_initial);
+                    rhs_evals.set(rhs_evals.get() + 1);
+                    self.order = 1;
+                    self.steps_at_order = 0;
+                    return Ok(true);
+                }
+            }
+            self.steps_at_order += 1;
+            self.order = k;
+
+            // Step size factor of every candidate order, the order is only changed
+            // after k + 1 steps at the current one
+            let mut factor: f64 = if err == 0.0 {
+                MAX_FACTOR
+            } else {
+                SAFETY * err.powf(-1.0 / (k + 1) as f64)
+            };
+            if self.steps_at_order > k {
+                let mut candidates: Vec<usize> = Vec::new();
+                if k > 1 {
+                    candidates.push(k - 1);
+                }
+                if k < MAX_ORDER && solution.len() - self.history_start >= k + 3 {
+                    candidates.push(k + 1);
+                }
+                for candidate in candidates {
+                    let candidate_err: f64 = self.order_error(solution, candidate);
+                    let candidate_factor: f64 = if candidate_err == 0.0 {
+                        MAX_FACTOR
+                    } else {
+                        SAFETY * candidate_err.powf(-1.0 / (candidate + 1) as f64)
+                    };
+                    if candidate_factor > factor {
+                        factor = candidate_factor;
+                        self.order = candidate;
+                    }
+                }
+                if self.order != k {
+                    self.steps_at_order = 0;
+                }
+            }
+
+            let factor: f64 = factor.clamp(MIN_FACTOR, MAX_FACTOR);
+            if !(1.0..MIN_CHANGE).contains(&factor) {
+                self.h *= factor;
+            }
+            return Ok(true);
+        }
+    }
+}
+
+impl<'s> StepMethod for BdfMethod<'s> {
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError> {
+        let rhs_evals: Cell<usize> = Cell::new(0);
+        let accepted = self.attempt_steps(solution, events, &rhs_evals);
+        solution.stats.num_rhs_evals += rhs_evals.get();
+        accepted
+    }
+
+    /// The order selection looks at up to `MAX_ORDER + 2` points.
+    fn history(&self) -> usize {
+        MAX_ORDER + 2
+    }
+
+    fn dropped(&mut self, count: usize) {
+        self.history_start = self.history_start.saturating_sub(count);
+    }
+}
+
+impl<'a> Solve for BdfSolver<'a> {
+    /// Solves the ODE with the BDF solver.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = "BDF").entered();
+        info!("starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
+    }
+
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        let params = self.solver.params;
+        let system = self.solver.system;
+        self.solver.validate_initial_state(initial_state)?;
+        let dim: usize = initial_state.len();
+        let t0: f64 = params.time_span.t0;
+
+        let mut solution = OdeSolution::new(t0, initial_state);
+        let mut f_initial: Vec<f64> = vec![0.0; dim];
+        system.rhs(t0, initial_state, &mut f_initial);
+        solution.stats.num_rhs_evals += 1;
+        let mut jacobian: Vec<f64> = vec![0.0; dim * dim];
+        system.jacobian(t0, initial_state, &mut jacobian);
+        solution.stats.num_jacobian_evals += 1;
+        solution.stats.num_rhs_evals += system.jacobian_rhs_evals();
+
+        let method = BdfMethod {
+            solver: &self.solver,
+            h: params.time_span.direction() * params.time_step,
+            order: 1,
+            steps_at_order: 0,
+            f_initial,
+            history_start: 0,
+            jacobian,
+            jacobian_current: true,
+            iteration_matrix: None,
+            attempts: 0,
+        };
+        Ok(OdeStepper::new(&self.solver, solution, Box::new(method)))
+    }
+}
+
+impl<'a> Printable for BdfSolver<'a> {}
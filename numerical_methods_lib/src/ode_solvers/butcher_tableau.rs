@@ -0,0 +1,368 @@
+use crate::ode_solvers::ode_error::OdeError;
+
+/// Butcher tableau of an explicit Runge Kutta method.
+///
+/// ```text
+/// c | a
+/// --+---
+///   | b
+/// ```
+/// `a` is strictly lower triangular, stored as `a[i][j]` with `j < i`.
+#[derive(Clone, Debug)]
+pub struct ButcherTableau {
+    pub name: &'static str,
+    pub a: Vec<Vec<f64>>,
+    pub b: Vec<f64>,
+    pub c: Vec<f64>,
+    /// Theoretical order of accuracy of the method
+    pub order: usize,
+    /// Continuous extension `b_i(theta)`: `dense[i][m]` is the coefficient of `theta^(m + 1)`.
+    /// Without it dense output falls back to cubic Hermite interpolation.
+    pub dense: Option<Vec<Vec<f64>>>,
+}
+
+// Largest difference allowed between c[i] and the sum of row i of a
+const ROW_SUM_TOLERANCE: f64 = 1e-12;
+
+impl ButcherTableau {
+    /// Tableau of a user method, checked with [`ButcherTableau::validate`].
+    pub fn new(
+        name: &'static str,
+        a: Vec<Vec<f64>>,
+        b: Vec<f64>,
+        c: Vec<f64>,
+        order: usize,
+    ) -> Result<Self, OdeError> {
+        let tableau = ButcherTableau::from_coefficients(name, a, b, c, order);
+        tableau.validate()?;
+        Ok(tableau)
+    }
+
+    // The built-in tableaux are checked by the tests
+    fn from_coefficients(
+        name: &'static str,
+        a: Vec<Vec<f64>>,
+        b: Vec<f64>,
+        c: Vec<f64>,
+        order: usize,
+    ) -> Self {
+        ButcherTableau {
+            name,
+            a,
+            b,
+            c,
+            order,
+            dense: None,
+        }
+    }
+
+    /// Checks that `a`, `b`, `c` and the continuous extension have one entry per stage,
+    /// that `a` is strictly lower triangular and that `c[i]` is the sum of row `i` of `a`.
+    pub fn validate(&self) -> Result<(), OdeError> {
+        let invalid = |reason: String| {
+            Err(OdeError::InvalidParameter {
+                name: "tableau",
+                reason: format!("{}: {}", self.name, reason),
+            })
+        };
+        let stages: usize = self.stages();
+        if stages == 0 {
+            return invalid("b is empty, the method needs at least one stage".to_string());
+        }
+        if self.c.len() != stages {
+            return invalid(format!(
+                "c has {} entries for {} stages",
+                self.c.len(),
+                stages
+            ));
+        }
+        if self.a.len() != stages {
+            return invalid(format!("a has {} rows for {} stages", self.a.len(), stages));
+        }
+        if let Some(row) = self.a.iter().position(|row| row.len() > stages) {
+            return invalid(format!(
+                "row {} of a has {} entries for {} stages",
+                row,
+                self.a[row].len(),
+                stages
+            ));
+        }
+        if !self.is_explicit() {
+            return invalid("a is not strictly lower triangular".to_string());
+        }
+        for (i, row) in self.a.iter().enumerate() {
+            let row_sum: f64 = row.iter().sum();
+            if (row_sum - self.c[i]).abs() > ROW_SUM_TOLERANCE * self.c[i].abs().max(1.0) {
+                return invalid(format!(
+                    "c[{}] = {} is not the sum {} of row {} of a",
+                    i, self.c[i], row_sum, i
+                ));
+            }
+        }
+        if let Some(dense) = &self.dense {
+            if dense.len() != stages {
+                return invalid(format!(
+                    "the continuous extension has {} rows for {} stages",
+                    dense.len(),
+                    stages
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a continuous extension, see [`ButcherTableau::dense`].
+    pub fn with_dense_output(self, dense: Vec<Vec<f64>>) -> Self {
+        ButcherTableau {
+            dense: Some(dense),
+            ..self
+        }
+    }
+
+    /// Number of stages of the method.
+    pub fn stages(&self) -> usize {
+        self.b.len()
+    }
+
+    /// True if `a` is strictly lower triangular, i.e. every stage only depends on previous ones.
+    pub fn is_explicit(&self) -> bool {
+        self.a
+            .iter()
+            .enumerate()
+            .all(|(i, row)| row.iter().skip(i).all(|&a_ij| a_ij == 0.0))
+    }
+
+    /// Entry `a[i][j]`, missing entries of the ragged rows are zero.
+    pub fn a_ij(&self, i: usize, j: usize) -> f64 {
+        self.a
+            .get(i)
+            .and_then(|row| row.get(j))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Checks the order conditions `b . Phi(t) = 1 / gamma(t)` for every rooted tree `t`
+    /// with at most `order` vertices.
+    pub fn satisfies_order_conditions(&self, order: usize, tol: f64) -> bool {
+        let trees = rooted_trees(order);
+        let mut weights: Vec<Vec<f64>> = Vec::with_capacity(trees.len());
+        let mut densities: Vec<f64> = Vec::with_capacity(trees.len());
+
+        for tree in &trees {
+            // Phi_i(t) = prod over children u of (A Phi(u))_i, which is 1 for the single vertex
+            let mut phi: Vec<f64> = vec![1.0; self.stages()];
+            let mut gamma: f64 = tree.size as f64;
+            for &child in &tree.children {
+                for (i, phi_i) in phi.iter_mut().enumerate() {
+                    *phi_i *= (0..self.stages())
+                        .map(|j| self.a_ij(i, j) * weights[child][j])
+                        .sum::<f64>();
+                }
+                gamma *= densities[child];
+            }
+
+            let condition: f64 = self.b.iter().zip(&phi).map(|(b, p)| b * p).sum();
+            if (condition - 1.0 / gamma).abs() > tol {
+                return false;
+            }
+            weights.push(phi);
+            densities.push(gamma);
+        }
+
+        true
+    }
+
+    /// Highest order (up to `max_order`) for which all order conditions hold.
+    pub fn achieved_order(&self, max_order: usize) -> usize {
+        (1..=max_order)
+            .take_while(|&order| self.satisfies_order_conditions(order, 1e-12))
+            .last()
+            .unwrap_or(0)
+    }
+
+    pub fn explicit_euler() -> Self {
+        ButcherTableau::from_coefficients("explicit_euler", vec![vec![]], vec![1.0], vec![0.0], 1)
+    }
+
+    pub fn midpoint() -> Self {
+        ButcherTableau::from_coefficients(
+            "midpoint",
+            vec![vec![], vec![0.5]],
+            vec![0.0, 1.0],
+            vec![0.0, 0.5],
+            2,
+        )
+    }
+
+    pub fn heun() -> Self {
+        ButcherTableau::from_coefficients(
+            "heun",
+            vec![vec![], vec![1.0]],
+            vec![0.5, 0.5],
+            vec![0.0, 1.0],
+            2,
+        )
+    }
+
+    pub fn ralston() -> Self {
+        ButcherTableau::from_coefficients(
+            "ralston",
+            vec![vec![], vec![2.0 / 3.0]],
+            vec![0.25, 0.75],
+            vec![0.0, 2.0 / 3.0],
+            2,
+        )
+    }
+
+    /// Kutta's third order method.
+    pub fn rk3() -> Self {
+        ButcherTableau::from_coefficients(
+            "rk3",
+            vec![vec![], vec![0.5], vec![-1.0, 2.0]],
+            vec![1.0 / 6.0, 2.0 / 3.0, 1.0 / 6.0],
+            vec![0.0, 0.5, 1.0],
+            3,
+        )
+    }
+
+    /// Strong stability preserving third order method (Shu-Osher).
+    pub fn ssp_rk3() -> Self {
+        ButcherTableau::from_coefficients(
+            "ssp_rk3",
+            vec![vec![], vec![1.0], vec![0.25, 0.25]],
+            vec![1.0 / 6.0, 1.0 / 6.0, 2.0 / 3.0],
+            vec![0.0, 1.0, 0.5],
+            3,
+        )
+    }
+
+    /// Classical fourth order Runge Kutta method, with its third order natural
+    /// continuous extension.
+    pub fn rk4() -> Self {
+        ButcherTableau::from_coefficients(
+            "rk4",
+            vec![vec![], vec![0.5], vec![0.0, 0.5], vec![0.0, 0.0, 1.0]],
+            vec![1.0 / 6.0, 1.0 / 3.0, 1.0 / 3.0, 1.0 / 6.0],
+            vec![0.0, 0.5, 0.5, 1.0],
+            4,
+        )
+        .with_dense_output(vec![
+            vec![1.0, -1.5, 2.0 / 3.0],
+            vec![0.0, 1.0, -2.0 / 3.0],
+            vec![0.0, 1.0, -2.0 / 3.0],
+            vec![0.0, -0.5, 2.0 / 3.0],
+        ])
+    }
+
+    /// Kutta's 3/8-rule fourth order method.
+    pub fn rk4_three_eighths() -> Self {
+        ButcherTableau::from_coefficients(
+            "rk4_three_eighths",
+            vec![
+                vec![],
+                vec![1.0 / 3.0],
+                vec![-1.0 / 3.0, 1.0],
+                vec![1.0, -1.0, 1.0],
+            ],
+            vec![1.0 / 8.0, 3.0 / 8.0, 3.0 / 8.0, 1.0 / 8.0],
+            vec![0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0],
+            4,
+        )
+    }
+
+    /// Butcher's six stage fifth order method.
+    pub fn rk5_butcher() -> Self {
+        ButcherTableau::from_coefficients(
+            "rk5_butcher",
+            vec![
+                vec![],
+                vec![0.25],
+                vec![0.125, 0.125],
+                vec![0.0, -0.5, 1.0],
+                vec![3.0 / 16.0, 0.0, 0.0, 9.0 / 16.0],
+                vec![-3.0 / 7.0, 2.0 / 7.0, 12.0 / 7.0, -12.0 / 7.0, 8.0 / 7.0],
+            ],
+            vec![
+                7.0 / 90.0,
+                0.0,
+                32.0 / 90.0,
+                12.0 / 90.0,
+                32.0 / 90.0,
+                7.0 / 90.0,
+            ],
+            vec![0.0, 0.25, 0.25, 0.5, 0.75, 1.0],
+            5,
+        )
+    }
+
+    /// All built-in tableaux.
+    pub fn built_in() -> Vec<ButcherTableau> {
+        vec![
+            ButcherTableau::explicit_euler(),
+            ButcherTableau::midpoint(),
+            ButcherTableau::heun(),
+            ButcherTableau::ralston(),
+            ButcherTableau::rk3(),
+            ButcherTableau::ssp_rk3(),
+            ButcherTableau::rk4(),
+            ButcherTableau::rk4_three_eighths(),
+            ButcherTableau::rk5_butcher(),
+        ]
+    }
+}
+
+/// Rooted tree: a root vertex whose subtrees are given as indices into the tree list.
+struct RootedTree {
+    size: usize,
+    children: Vec<usize>,
+}
+
+/// All rooted trees with at most `max_size` vertices, ordered by size.
+/// Children indices are non-increasing, so every tree is generated exactly once.
+fn rooted_trees(max_size: usize) -> Vec<RootedTree> {
+    let mut trees: Vec<RootedTree> = Vec::new();
+    if max_size == 0 {
+        return trees;
+    }
+    trees.push(RootedTree {
+        size: 1,
+        children: vec![],
+    });
+
+    for size in 2..=max_size {
+        let mut forests: Vec<Vec<usize>> = Vec::new();
+        forests_of_size(&trees, size - 1, trees.len(), &mut vec![], &mut forests);
+        for children in forests {
+            trees.push(RootedTree { size, children });
+        }
+    }
+
+    trees
+}
+
+/// Collects multisets of trees (indices below `max_index`, non-increasing) with `remaining` vertices.
+fn forests_of_size(
+    trees: &[RootedTree],
+    remaining: usize,
+    max_index: usize,
+    current: &mut Vec<usize>,
+    forests: &mut Vec<Vec<usize>>,
+) {
+    if remaining == 0 {
+        forests.push(current.clone());
+        return;
+    }
+    for index in (0..max_index).rev() {
+        if trees[index].size <= remaining {
+            current.push(index);
+            forests_of_size(
+                trees,
+                remaining - trees[index].size,
+                index + 1,
+                current,
+                forests,
+            );
+            current.pop();
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use crate::ode_solvers::{
+    method_registry::{build_solver, find_method},
+    ode_error::OdeError,
+    ode_solution::{OdeSolution, SolverStats},
+    ode_solver::{OdeSolver, OdeSolverParams},
+    ode_system::OdeSystem,
+};
+
+/// Global errors of a solution against the exact solution, from the Euclidean norm of
+/// the state error at every time point of the solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GlobalErrors {
+    /// Largest error over the time points
+    pub max: f64,
+    /// `sqrt(integral of |error|^2 dt)` by the trapezoidal rule over the time points
+    pub l2: f64,
+    /// Error at the last time point
+    pub final_time: f64,
+}
+
+/// Selects one of the [`GlobalErrors`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorNorm {
+    Max,
+    L2,
+    FinalTime,
+}
+
+impl GlobalErrors {
+    /// Errors of `solution` against `exact`, the exact state at a given time.
+    pub fn new(solution: &OdeSolution, exact: impl Fn(f64) -> Vec<f64>) -> Self {
+        let errors: Vec<f64> = solution
+            .times
+            .iter()
+            .zip(&solution.states)
+            .map(|(&t, state)| {
+                state
+                    .iter()
+                    .zip(exact(t))
+                    .map(|(value, exact)| (value - exact).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+        let squared_integral: f64 = solution
+            .times
+            .windows(2)
+            .zip(errors.windows(2))
+            .map(|(t, e)| 0.5 * (t[1] - t[0]) * (e[0] * e[0] + e[1] * e[1]))
+            .sum();
+        GlobalErrors {
+            max: errors.iter().copied().fold(0.0, f64::max),
+            l2: squared_integral.sqrt(),
+            final_time: errors.last().copied().unwrap_or(0.0),
+        }
+    }
+
+    pub fn get(&self, norm: ErrorNorm) -> f64 {
+        match norm {
+            ErrorNorm::Max => self.max,
+            ErrorNorm::L2 => self.l2,
+            ErrorNorm::FinalTime => self.final_time,
+        }
+    }
+}
+
+/// One solve of a [`ConvergenceStudy`].
+#[derive(Clone, Debug)]
+pub struct ConvergenceLevel {
+    pub time_step: f64,
+    pub errors: GlobalErrors,
+    pub stats: SolverStats,
+}
+
+/// Errors of a method at a sequence of step sizes on a problem with a known exact
+/// solution, to compare the observed order of the method with its theoretical order.
+///
+/// ```ignore
+/// let study = ConvergenceStudy::run(
+///     "rk4",
+///     &system,
+///     &[1.0],
+///     |t| vec![(-t).exp()],
+///     &solver_params,
+///     &halving_step_sizes(0.1, 5),
+/// )?;
+/// assert!((study.observed_order(ErrorNorm::Max) - 4.0).abs() < 0.1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConvergenceStudy {
+    pub levels: Vec<ConvergenceLevel>,
+}
+
+impl ConvergenceStudy {
+    /// Solves the problem with the method named `method` (see
+    /// [`METHODS`](crate::ode_solvers::method_registry::METHODS)) once per step size of
+    /// `step_sizes`, the other parameters taken from `params`. Adaptive methods are
+    /// rejected: for them the step size is only the initial step.
+    pub fn run(
+        method: &str,
+        system: &dyn OdeSystem,
+        initial_state: &[f64],
+        exact: impl Fn(f64) -> Vec<f64>,
+        params: &OdeSolverParams,
+        step_sizes: &[f64],
+    ) -> Result<Self, OdeError> {
+        if step_sizes.len() < 2 {
+            return Err(OdeError::InvalidParameter {
+                name: "step_sizes",
+                reason: "an order needs at least two step sizes".to_string(),
+            });
+        }
+        if find_method(method).is_some_and(|info| info.adaptive) {
+            return Err(OdeError::InvalidParameter {
+                name: "method",
+                reason: format!(
+                    "`{}` chooses its own step size, an order needs a fixed step method",
+                    method
+                ),
+            });
+        }
+        let name: &str = find_method(method).map_or(method, |info| info.name);
+        let mut levels: Vec<ConvergenceLevel> = Vec::with_capacity(step_sizes.len());
+        for &time_step in step_sizes {
+            let level_params = OdeSolverParams {
+                time_step,
+                ..params.clone()
+            };
+            let solver = OdeSolver::new(name, system, &level_params);
+            let solution: OdeSolution = build_solver(method, solver)?.solve(initial_state)?;
+            levels.push(ConvergenceLevel {
+                time_step,
+                errors: GlobalErrors::new(&solution, &exact),
+                stats: solution.stats,
+            });
+        }
+        Ok(ConvergenceStudy { levels })
+    }
+
+    pub fn step_sizes(&self) -> Vec<f64> {
+        self.levels.iter().map(|level| level.time_step).collect()
+    }
+
+    pub fn errors(&self, norm: ErrorNorm) -> Vec<f64> {
+        self.levels
+            .iter()
+            .map(|level| level.errors.get(norm))
+            .collect()
+    }
+
+    /// Order fitted over every level, see [`fit_order`].
+    pub fn observed_order(&self, norm: ErrorNorm) -> f64 {
+        fit_order(&self.step_sizes(), &self.errors(norm))
+    }
+
+    /// Order observed between each level and the next, which shows where the errors
+    /// leave the asymptotic regime (large steps) or reach the round-off (small steps).
+    pub fn successive_orders(&self, norm: ErrorNorm) -> Vec<f64> {
+        let errors: Vec<f64> = self.errors(norm);
+        self.levels
+            .windows(2)
+            .zip(errors.windows(2))
+            .map(|(levels, errors)| {
+                (errors[0] / errors[1]).ln() / (levels[0].time_step / levels[1].time_step).ln()
+            })
+            .collect()
+    }
+}
+
+/// Step sizes `initial, initial/2, initial/4, ...`, `levels` of them.
+pub fn halving_step_sizes(initial: f64, levels: usize) -> Vec<f64> {
+    (0..levels)
+        .map(|level| initial / 2f64.powi(level as i32))
+        .collect()
+}
+
+/// Order `p` of the errors `e ~ C h^p`: the least squares slope of `ln e` against `ln h`.
+/// Returns NaN for fewer than two points or with a zero error.
+pub fn fit_order(step_sizes: &[f64], errors: &[f64]) -> f64 {
+    let points: Vec<(f64, f64)> = step_sizes
+        .iter()
+        .zip(errors)
+        .map(|(h, e)| (h.ln(), e.ln()))
+        .collect();
+    let n: f64 = points.len() as f64;
+    if points.len() < 2 || points.iter().any(|(_, ln_e)| !ln_e.is_finite()) {
+        return f64::NAN;
+    }
+    let mean_x: f64 = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y: f64 = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    covariance / variance
+}
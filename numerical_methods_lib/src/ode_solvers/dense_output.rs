@@ -0,0 +1,147 @@
+/// Continuous approximation of the solution over one step.
+///
+/// Stored as a polynomial in the normalised time `theta = (t - t_start) / h`:
+/// `y(t) = y_start + sum_m coefficients[m] * theta^(m + 1)`, where `h` is the full step
+/// even if the interpolant is only valid up to an event before the step end.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepInterpolant {
+    pub t_start: f64,
+    /// End of the validity interval
+    pub t_end: f64,
+    h: f64,
+    y_start: Vec<f64>,
+    coefficients: Vec<Vec<f64>>,
+}
+
+impl StepInterpolant {
+    /// Cubic Hermite interpolant of the states and derivatives at both ends of the step.
+    /// Third order accurate, continuous with continuous first derivative across steps.
+    pub fn hermite(t: f64, y: &[f64], f: &[f64], t_new: f64, y_new: &[f64], f_new: &[f64]) -> Self {
+        let h: f64 = t_new - t;
+        let dim: usize = y.len();
+        let mut coefficients: Vec<Vec<f64>> = vec![vec![0.0; dim]; 3];
+        for j in 0..dim {
+            let delta: f64 = y_new[j] - y[j];
+            coefficients[0][j] = h * f[j];
+            coefficients[1][j] = 3.0 * delta - 2.0 * h * f[j] - h * f_new[j];
+            coefficients[2][j] = -2.0 * delta + h * f[j] + h * f_new[j];
+        }
+        StepInterpolant {
+            t_start: t,
+            t_end: t_new,
+            h,
+            y_start: y.to_vec(),
+            coefficients,
+        }
+    }
+
+    /// Continuous extension of a Runge Kutta step: `y(t + theta h) = y + h sum_i b_i(theta) k_i`
+    /// with `weights[i][m]` the coefficient of `theta^(m + 1)` in `b_i(theta)`.
+    pub fn from_stages(
+        t: f64,
+        y: &[f64],
+        t_new: f64,
+        stages: &[Vec<f64>],
+        weights: &[&[f64]],
+    ) -> Self {
+        let h: f64 = t_new - t;
+        let dim: usize = y.len();
+        let degree: usize = weights.iter().map(|w| w.len()).max().unwrap_or(0);
+        let mut coefficients: Vec<Vec<f64>> = vec![vec![0.0; dim]; degree];
+        for (k, w) in stages.iter().zip(weights) {
+            for (m, &w_m) in w.iter().enumerate() {
+                if w_m == 0.0 {
+                    continue;
+                }
+                for j in 0..dim {
+                    coefficients[m][j] += h * w_m * k[j];
+                }
+            }
+        }
+        StepInterpolant {
+            t_start: t,
+            t_end: t_new,
+            h,
+            y_start: y.to_vec(),
+            coefficients,
+        }
+    }
+
+    /// The interpolant restricted to end at `t_end`, e.g. at an event.
+    pub(crate) fn truncated(&self, t_end: f64) -> Self {
+        StepInterpolant {
+            t_end,
+            ..self.clone()
+        }
+    }
+
+    /// Approximate state at time `t`, which should lie within the step.
+    pub fn evaluate(&self, t: f64) -> Vec<f64> {
+        let theta: f64 = (t - self.t_start) / self.h;
+        (0..self.y_start.len())
+            .map(|j| {
+                // Horner scheme, the constant term is y_start
+                let poly: f64 = self
+                    .coefficients
+                    .iter()
+                    .rev()
+                    .fold(0.0, |acc, c| (acc + c[j]) * theta);
+                self.y_start[j] + poly
+            })
+            .collect()
+    }
+}
+
+/// Piecewise polynomial approximation of the solution over the whole integration,
+/// one [`StepInterpolant`] per accepted step.
+///
+/// At a time shared by two pieces (a step boundary, or an event that reset the state)
+/// the later piece is used.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DenseOutput {
+    pub pieces: Vec<StepInterpolant>,
+}
+
+impl DenseOutput {
+    pub fn push(&mut self, piece: StepInterpolant) {
+        self.pieces.push(piece);
+    }
+
+    /// Sign of the direction of integration.
+    fn direction(&self) -> f64 {
+        match (self.pieces.first(), self.pieces.last()) {
+            (Some(first), Some(last)) if last.t_end < first.t_start => -1.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Drops the part of the approximation after time `t`.
+    pub fn truncate(&mut self, t: f64) {
+        let direction: f64 = self.direction();
+        self.pieces
+            .retain(|piece| direction * (piece.t_start - t) < 0.0);
+        if let Some(last) = self.pieces.last_mut() {
+            if direction * (last.t_end - t) > 0.0 {
+                last.t_end = t;
+            }
+        }
+    }
+
+    /// Time interval covered, `None` before the first step.
+    pub fn time_span(&self) -> Option<(f64, f64)> {
+        Some((self.pieces.first()?.t_start, self.pieces.last()?.t_end))
+    }
+
+    /// Approximate state at time `t`, or `None` if `t` lies outside the integrated interval.
+    pub fn evaluate(&self, t: f64) -> Option<Vec<f64>> {
+        let (t_start, t_end) = self.time_span()?;
+        let direction: f64 = self.direction();
+        if direction * (t - t_start) < 0.0 || direction * (t - t_end) > 0.0 {
+            return None;
+        }
+        let index: usize = self
+            .pieces
+            .partition_point(|piece| direction * (piece.t_start - t) <= 0.0);
+        Some(self.pieces[index.max(1) - 1].evaluate(t))
+    }
+}
@@ -0,0 +1,271 @@
+use crate::ode_solvers::{
+    dense_output::StepInterpolant,
+    events::{EventLocator, StepOutcome},
+    ode_error::{check_finite, OdeError},
+    ode_solution::{OdeSolution, SolverStatus},
+    ode_solver::{OdeSolver, Printable, Solve},
+    stepper::{OdeStepper, StepMethod},
+};
+use tracing::{info, info_span, trace};
+
+// Dormand-Prince 5(4) coefficients
+const C: [f64; 7] = [0.0, 1.0 / 5.0, 3.0 / 10.0, 4.0 / 5.0, 8.0 / 9.0, 1.0, 1.0];
+const A: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [
+        19372.0 / 6561.0,
+        -25360.0 / 2187.0,
+        64448.0 / 6561.0,
+        -212.0 / 729.0,
+        0.0,
+        0.0,
+    ],
+    [
+        9017.0 / 3168.0,
+        -355.0 / 33.0,
+        46732.0 / 5247.0,
+        49.0 / 176.0,
+        -5103.0 / 18656.0,
+        0.0,
+    ],
+    [
+        35.0 / 384.0,
+        0.0,
+        500.0 / 1113.0,
+        125.0 / 192.0,
+        -2187.0 / 6784.0,
+        11.0 / 84.0,
+    ],
+];
+// Difference between the 5th and the embedded 4th order weights
+const E: [f64; 7] = [
+    71.0 / 57600.0,
+    0.0,
+    -71.0 / 16695.0,
+    71.0 / 1920.0,
+    -17253.0 / 339200.0,
+    22.0 / 525.0,
+    -1.0 / 40.0,
+];
+// Fourth order continuous extension (Shampine): P[i][m] is the coefficient of theta^(m + 1)
+// in the weight b_i(theta) of stage i
+const P: [[f64; 4]; 7] = [
+    [
+        1.0,
+        -8048581381.0 / 2820520608.0,
+        8663915743.0 / 2820520608.0,
+        -12715105075.0 / 11282082432.0,
+    ],
+    [0.0, 0.0, 0.0, 0.0],
+    [
+        0.0,
+        131558114200.0 / 32700410799.0,
+        -68118460800.0 / 10900136933.0,
+        87487479700.0 / 32700410799.0,
+    ],
+    [
+        0.0,
+        -1754552775.0 / 470086768.0,
+        14199869525.0 / 1410260304.0,
+        -10690763975.0 / 1880347072.0,
+    ],
+    [
+        0.0,
+        127303824393.0 / 49829197408.0,
+        -318862633887.0 / 49829197408.0,
+        701980252875.0 / 199316789632.0,
+    ],
+    [
+        0.0,
+        -282668133.0 / 205662961.0,
+        2019193451.0 / 616988883.0,
+        -1453857185.0 / 822651844.0,
+    ],
+    [
+        0.0,
+        40617522.0 / 29380423.0,
+        -110615467.0 / 29380423.0,
+        69997945.0 / 29380423.0,
+    ],
+];
+
+const SAFETY: f64 = 0.9;
+const MIN_FACTOR: f64 = 0.2;
+const MAX_FACTOR: f64 = 5.0;
+const MAX_STEPS: usize = 100_000;
+
+/// Implements the adaptive Dormand-Prince 5(4) Method.
+///
+/// The integration runs over `time_span`, with `time_step` used as the initial step size. Steps are accepted when the
+/// embedded error estimate is within `tolerance` (absolute) and `rel_tolerance` (relative).
+pub struct DormandPrinceSolver<'a> {
+    pub solver: Box<OdeSolver<'a>>,
+}
+
+/// Adaptive Dormand-Prince loop. The stages `k` always start with f at the last point
+/// of the solution (first same as last).
+struct DormandPrinceMethod<'s> {
+    solver: &'s OdeSolver<'s>,
+    /// Signed step, negative when integrating backwards
+    h: f64,
+    k: Vec<Vec<f64>>,
+    y_stage: Vec<f64>,
+    rejected_last: bool,
+    /// Step attempts so far, accepted or rejected
+    attempts: usize,
+}
+
+impl<'s> StepMethod for DormandPrinceMethod<'s> {
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError> {
+        let params = self.solver.params;
+        let t_final: f64 = params.time_span.tf;
+        let direction: f64 = params.time_span.direction();
+        let (t, y): (f64, Vec<f64>) = solution.last().map(|(t, y)| (t, y.clone())).unwrap();
+        let dim: usize = y.len();
+        let (k, y_stage) = (&mut self.k, &mut self.y_stage);
+
+        loop {
+            if direction * (t_final - t) <= 0.0 {
+                return Ok(false);
+            }
+            if self.attempts == MAX_STEPS {
+                solution.status = SolverStatus::MaxStepsReached;
+                return Ok(false);
+            }
+            self.attempts += 1;
+            // Do not step past the final time, nor stop short of it by less than a step
+            // can resolve. The last step ends exactly at the final time, t + (tf - t)
+            // may round to a neighbouring time.
+            let t_new: f64 = if direction * (t_final - t - self.h)
+                <= 16.0 * f64::EPSILON * t_final.abs().max(1.0)
+            {
+                self.h = t_final - t;
+                t_final
+            } else {
+                t + self.h
+            };
+            let h: f64 = self.h;
+            if h.abs() <= 16.0 * f64::EPSILON * t.abs().max(1.0) {
+                return Err(OdeError::StepSizeUnderflow { t, step: h });
+            }
+
+            for stage in 1..7 {
+                for j in 0..dim {
+                    y_stage[j] = y[j] + h * (0..stage).map(|s| A[stage][s] * k[s][j]).sum::<f64>();
+                }
+                self.solver
+                    .system
+                    .rhs(t + C[stage] * h, y_stage, &mut k[stage]);
+            }
+            solution.stats.num_rhs_evals += 6;
+            // The 7th stage is evaluated at the 5th order solution (FSAL)
+            let y_new: Vec<f64> = y_stage.clone();
+
+            let err: f64 = ((0..dim)
+                .map(|j| {
+                    let err_j: f64 = h * (0..7).map(|s| E[s] * k[s][j]).sum::<f64>();
+                    let scale: f64 =
+                        params.tolerance + params.rel_tolerance * y[j].abs().max(y_new[j].abs());
+                    (err_j / scale).powi(2)
+                })
+                .sum::<f64>()
+                / dim as f64)
+                .sqrt();
+
+            let factor: f64 = if err == 0.0 {
+                MAX_FACTOR
+            } else if !err.is_finite() {
+                // The trial step produced NaN or inf, retry with the smallest allowed step
+                MIN_FACTOR
+            } else {
+                (SAFETY * err.powf(-0.2)).clamp(MIN_FACTOR, MAX_FACTOR)
+            };
+
+            trace!(
+                attempt = self.attempts,
+                t,
+                h,
+                error = err,
+                accepted = err <= 1.0,
+                "step attempt"
+            );
+            if err > 1.0 || !err.is_finite() {
+                self.h *= factor;
+                self.rejected_last = true;
+                solution.stats.rejected_steps += 1;
+                continue;
+            }
+
+            check_finite(t_new, &y_new)?;
+            solution.stats.accepted_steps += 1;
+            let stages: &[Vec<f64>] = k;
+            let interpolant = |_: &[f64]| {
+                let weights: Vec<&[f64]> = P.iter().map(|w| w.as_slice()).collect();
+                StepInterpolant::from_stages(t, &y, t_new, stages, &weights)
+            };
+            match events.advance_with(solution, t_new, y_new, interpolant)? {
+                StepOutcome::Continue => k.swap(0, 6),
+                StepOutcome::Terminated => {}
+                StepOutcome::Reset => {
+                    // The FSAL stage belongs to the state before the reset, and the
+                    // step size control starts over after the discontinuity
+                    let (t_reset, y_reset) = solution.last().unwrap();
+                    self.solver.system.rhs(t_reset, y_reset, &mut k[0]);
+                    solution.stats.num_rhs_evals += 1;
+                    self.h = direction * params.time_step;
+                    self.rejected_last = false;
+                    return Ok(true);
+                }
+            }
+            // Do not grow the step right after a rejection
+            self.h *= if self.rejected_last {
+                factor.min(1.0)
+            } else {
+                factor
+            };
+            self.rejected_last = false;
+            return Ok(true);
+        }
+    }
+}
+
+impl<'a> Solve for DormandPrinceSolver<'a> {
+    /// Solves the ODE with the Dormand-Prince solver.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = "Dormand-Prince").entered();
+        info!("starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
+    }
+
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        let params = self.solver.params;
+        self.solver.validate_initial_state(initial_state)?;
+        let dim: usize = initial_state.len();
+
+        let mut solution = OdeSolution::new(params.time_span.t0, initial_state);
+        let mut k: Vec<Vec<f64>> = vec![vec![0.0; dim]; 7];
+        self.solver
+            .system
+            .rhs(params.time_span.t0, initial_state, &mut k[0]);
+        solution.stats.num_rhs_evals += 1;
+
+        let method = DormandPrinceMethod {
+            solver: &self.solver,
+            h: params.time_span.direction() * params.time_step,
+            k,
+            y_stage: vec![0.0; dim],
+            rejected_last: false,
+            attempts: 0,
+        };
+        Ok(OdeStepper::new(&self.solver, solution, Box::new(method)))
+    }
+}
+
+impl<'a> Printable for DormandPrinceSolver<'a> {}
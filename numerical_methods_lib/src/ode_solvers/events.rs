@@ -0,0 +1,307 @@
+use crate::{
+    ode_solvers::{
+        dense_output::{DenseOutput, StepInterpolant},
+        ode_error::{check_finite, OdeError},
+        ode_solution::{EventRecord, OdeSolution, SolverStatus},
+        ode_solver::OdeSolver,
+        ode_system::OdeSystem,
+    },
+    root_finders::brent_method::brent_method_solve,
+};
+use tracing::debug;
+
+const MAX_BRENT_ITERATIONS: i32 = 100;
+
+/// Event function `g(t, y)`.
+pub type EventFunction<'a> = Box<dyn Fn(f64, &[f64]) -> f64 + 'a>;
+/// Modifies the state `y` in place at the event time `t`.
+pub type ResetFunction<'a> = Box<dyn Fn(f64, &mut [f64]) + 'a>;
+
+/// Which zero crossings of the event function trigger the event.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventDirection {
+    /// Any sign change.
+    Both,
+    /// `g` goes from negative to non-negative.
+    Increasing,
+    /// `g` goes from positive to non-positive.
+    Decreasing,
+}
+
+/// What happens when an event is located.
+pub enum EventAction<'a> {
+    /// Stop the integration at the event.
+    Terminate,
+    /// Record the event time and state and continue.
+    Record,
+    /// Record the event, then modify the state in place and restart the integration
+    /// from the modified state (e.g. reverse the velocity of a bouncing ball).
+    Reset(ResetFunction<'a>),
+}
+
+impl<'a> EventAction<'a> {
+    pub fn reset(reset: impl Fn(f64, &mut [f64]) + 'a) -> Self {
+        EventAction::Reset(Box::new(reset))
+    }
+}
+
+/// Event function `g(t, y)` whose zero crossings are located during the integration.
+pub struct Event<'a> {
+    pub g: EventFunction<'a>,
+    pub direction: EventDirection,
+    pub action: EventAction<'a>,
+}
+
+impl<'a> Event<'a> {
+    /// Event triggered by sign changes of `g` in both directions.
+    pub fn new(g: impl Fn(f64, &[f64]) -> f64 + 'a, action: EventAction<'a>) -> Self {
+        Event {
+            g: Box::new(g),
+            direction: EventDirection::Both,
+            action,
+        }
+    }
+
+    pub fn with_direction(self, direction: EventDirection) -> Self {
+        Event { direction, ..self }
+    }
+
+    fn is_triggered(&self, g_start: f64, g_end: f64) -> bool {
+        let increasing: bool = g_start < 0.0 && g_end >= 0.0;
+        let decreasing: bool = g_start > 0.0 && g_end <= 0.0;
+        match self.direction {
+            EventDirection::Both => increasing || decreasing,
+            EventDirection::Increasing => increasing,
+            EventDirection::Decreasing => decreasing,
+        }
+    }
+}
+
+/// What the solver loop has to do after handing a step to the [`EventLocator`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum StepOutcome {
+    /// The step was appended to the solution.
+    Continue,
+    /// A terminal event was appended, stop integrating.
+    Terminated,
+    /// The state was reset at an event time, continue from the last point of the
+    /// solution and discard any step history.
+    Reset,
+}
+
+/// Hands every accepted step to the solution.
+///
+/// When the solution needs to be known between the step end points (events, dense
+/// output or `t_eval`), each step comes with an interpolant: the solver's own continuous
+/// extension, or else the cubic Hermite interpolant of the states and derivatives. It is
+/// stored as dense output, and event times are located on it with Brent's method, so no
+/// step has to be shortened to find them. Otherwise the steps are appended unchanged and
+/// no extra right hand side evaluations are made.
+pub(crate) struct EventLocator<'s> {
+    events: &'s [Event<'s>],
+    system: &'s dyn OdeSystem,
+    t_eval: &'s [f64],
+    dense_output: bool,
+    /// Event functions at the last point of the solution
+    g_start: Vec<f64>,
+    /// Derivative at the last point of the solution, if known from the previous step
+    f_start: Option<Vec<f64>>,
+}
+
+impl<'s> EventLocator<'s> {
+    pub(crate) fn new(solver: &'s OdeSolver, solution: &mut OdeSolution) -> Self {
+        let mut locator = EventLocator {
+            events: solver.events,
+            system: solver.system,
+            t_eval: solver.t_eval,
+            dense_output: solver.params.dense_output,
+            g_start: Vec::new(),
+            f_start: None,
+        };
+        if locator.dense_output || !locator.t_eval.is_empty() {
+            solution.dense_output = Some(DenseOutput::default());
+        }
+        locator.restart(solution);
+        locator
+    }
+
+    /// Whether steps need an interpolant.
+    fn interpolates(&self, solution: &OdeSolution) -> bool {
+        !self.events.is_empty() || solution.dense_output.is_some()
+    }
+
+    /// Re-evaluates the event functions at the last point of the solution.
+    fn restart(&mut self, solution: &OdeSolution) {
+        let (t, y) = solution.last().unwrap();
+        self.g_start = self.events.iter().map(|event| (event.g)(t, y)).collect();
+        self.f_start = None;
+    }
+
+    /// Appends the step from the last point of the solution to `(t_new, y_new)`, or the
+    /// part of it up to the first terminal or reset event, interpolated by cubic Hermite
+    /// interpolation.
+    pub(crate) fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        t_new: f64,
+        y_new: Vec<f64>,
+    ) -> Result<StepOutcome, OdeError> {
+        if !self.interpolates(solution) {
+            solution.push(t_new, y_new);
+            return Ok(StepOutcome::Continue);
+        }
+
+        let (t, y): (f64, Vec<f64>) = solution.last().map(|(t, y)| (t, y.clone())).unwrap();
+        let f_start: Vec<f64> = match self.f_start.take() {
+            Some(f_start) => f_start,
+            None => {
+                let mut f_start: Vec<f64> = vec![0.0; y.len()];
+                self.system.rhs(t, &y, &mut f_start);
+                solution.stats.num_rhs_evals += 1;
+                f_start
+            }
+        };
+        let mut f_new: Vec<f64> = vec![0.0; y.len()];
+        self.system.rhs(t_new, &y_new, &mut f_new);
+        solution.stats.num_rhs_evals += 1;
+
+        let interpolant = StepInterpolant::hermite(t, &y, &f_start, t_new, &y_new, &f_new);
+        let outcome: StepOutcome = self.record(solution, t_new, y_new, interpolant)?;
+        if outcome == StepOutcome::Continue {
+            self.f_start = Some(f_new);
+        }
+        Ok(outcome)
+    }
+
+    /// Same as [`EventLocator::advance`] with the solver's own interpolant of the step,
+    /// which is only built (from `y_new`) when needed.
+    pub(crate) fn advance_with(
+        &mut self,
+        solution: &mut OdeSolution,
+        t_new: f64,
+        y_new: Vec<f64>,
+        interpolant: impl FnOnce(&[f64]) -> StepInterpolant,
+    ) -> Result<StepOutcome, OdeError> {
+        if !self.interpolates(solution) {
+            solution.push(t_new, y_new);
+            return Ok(StepOutcome::Continue);
+        }
+        let interpolant: StepInterpolant = interpolant(&y_new);
+        self.record(solution, t_new, y_new, interpolant)
+    }
+
+    fn record(
+        &mut self,
+        solution: &mut OdeSolution,
+        t_new: f64,
+        y_new: Vec<f64>,
+        interpolant: StepInterpolant,
+    ) -> Result<StepOutcome, OdeError> {
+        let t: f64 = interpolant.t_start;
+        let g_new: Vec<f64> = self
+            .events
+            .iter()
+            .map(|event| (event.g)(t_new, &y_new))
+            .collect();
+        // (event time, event index) of every triggered event
+        let mut crossings: Vec<(f64, usize)> = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
+            if !event.is_triggered(self.g_start[index], g_new[index]) {
+                continue;
+            }
+            let t_event: f64 = if g_new[index] == 0.0 {
+                t_new
+            } else {
+                let tol: f64 = 4.0 * f64::EPSILON * t.abs().max(t_new.abs()).max(1.0);
+                let result = brent_method_solve(
+                    |s: f64| (event.g)(s, &interpolant.evaluate(s)),
+                    t,
+                    t_new,
+                    tol,
+                    MAX_BRENT_ITERATIONS,
+                );
+                if result.converged {
+                    result.root
+                } else {
+                    // g is only known to change sign over the whole step
+                    debug!(
+                        event = index,
+                        t = t_new,
+                        reason = ?result.reason,
+                        "event not located within the step, using its end"
+                    );
+                    t_new
+                }
+            };
+            crossings.push((t_event, index));
+        }
+        // In the direction of integration
+        let direction: f64 = (t_new - t).signum();
+        crossings.sort_by(|a, b| (direction * a.0).total_cmp(&(direction * b.0)));
+
+        for (t_event, index) in crossings {
+            let y_event: Vec<f64> = if t_event == t_new {
+                y_new.clone()
+            } else {
+                interpolant.evaluate(t_event)
+            };
+            debug!(event = index, t = t_event, "event located");
+            solution.events.push(EventRecord {
+                event: index,
+                t: t_event,
+                state: y_event.clone(),
+            });
+
+            match &self.events[index].action {
+                EventAction::Record => {}
+                EventAction::Terminate => {
+                    if let Some(dense) = &mut solution.dense_output {
+                        dense.push(interpolant.truncated(t_event));
+                    }
+                    solution.push(t_event, y_event);
+                    solution.status = SolverStatus::EventTerminated;
+                    return Ok(StepOutcome::Terminated);
+                }
+                EventAction::Reset(reset) => {
+                    let mut y_reset: Vec<f64> = y_event.clone();
+                    reset(t_event, &mut y_reset);
+                    check_finite(t_event, &y_reset)?;
+                    if let Some(dense) = &mut solution.dense_output {
+                        dense.push(interpolant.truncated(t_event));
+                    }
+                    solution.push(t_event, y_event);
+                    solution.push(t_event, y_reset);
+                    self.restart(solution);
+                    return Ok(StepOutcome::Reset);
+                }
+            }
+        }
+
+        if let Some(dense) = &mut solution.dense_output {
+            dense.push(interpolant);
+        }
+        solution.push(t_new, y_new);
+        self.g_start = g_new;
+        Ok(StepOutcome::Continue)
+    }
+
+    /// Replaces the steps by the requested `t_eval` output, if any, and drops the dense
+    /// output if it was only kept for that.
+    pub(crate) fn finish(self, mut solution: OdeSolution) -> OdeSolution {
+        if !self.t_eval.is_empty() {
+            if let Some(dense) = &solution.dense_output {
+                let (times, states): (Vec<f64>, Vec<Vec<f64>>) = self
+                    .t_eval
+                    .iter()
+                    .filter_map(|&t| dense.evaluate(t).map(|y| (t, y)))
+                    .unzip();
+                solution.times = times;
+                solution.states = states;
+            }
+        }
+        if !self.dense_output {
+            solution.dense_output = None;
+        }
+        solution
+    }
+}
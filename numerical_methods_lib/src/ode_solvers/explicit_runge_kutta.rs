@@ -0,0 +1,139 @@
+use crate::ode_solvers::{
+    butcher_tableau::ButcherTableau,
+    dense_output::StepInterpolant,
+    events::EventLocator,
+    ode_error::{check_finite, OdeError},
+    ode_solution::OdeSolution,
+    ode_solver::{OdeSolver, Printable, Solve},
+    ode_system::OdeSystem,
+    stepper::{OdeStepper, StepMethod},
+};
+use tracing::{info, info_span};
+
+/// Implements a generic explicit Runge Kutta Method driven by a Butcher tableau.
+pub struct ExplicitRkSolver<'a> {
+    pub solver: Box<OdeSolver<'a>>,
+    pub tableau: ButcherTableau,
+}
+
+/// Advances `y` by one step of size `h` from `t` with the given tableau.
+/// `stages` holds one buffer per stage for the stage derivatives `k_i`.
+pub(crate) fn explicit_rk_step(
+    system: &dyn OdeSystem,
+    tableau: &ButcherTableau,
+    t: f64,
+    y: &[f64],
+    h: f64,
+    stages: &mut [Vec<f64>],
+) -> Vec<f64> {
+    let dim: usize = y.len();
+    let mut y_stage: Vec<f64> = vec![0.0; dim];
+
+    for stage in 0..tableau.stages() {
+        for j in 0..dim {
+            y_stage[j] = y[j]
+                + h * (0..stage)
+                    .map(|s| tableau.a_ij(stage, s) * stages[s][j])
+                    .sum::<f64>();
+        }
+        system.rhs(t + tableau.c[stage] * h, &y_stage, &mut stages[stage]);
+    }
+
+    (0..dim)
+        .map(|j| {
+            y[j] + h * tableau
+                .b
+                .iter()
+                .zip(stages.iter())
+                .map(|(b, k)| b * k[j])
+                .sum::<f64>()
+        })
+        .collect()
+}
+
+/// Fixed step explicit Runge Kutta loop shared by every explicit one-step solver.
+pub(crate) struct ExplicitRkMethod<'s> {
+    system: &'s dyn OdeSystem,
+    tableau: ButcherTableau,
+    grid: Vec<f64>,
+    /// Index of the grid point the next step aims at
+    index: usize,
+    /// Stage derivatives `k_i` of the last step
+    stages: Vec<Vec<f64>>,
+}
+
+/// Stepper of the explicit Runge Kutta method given by `tableau`.
+pub(crate) fn explicit_rk_stepper<'s>(
+    solver: &'s OdeSolver,
+    tableau: ButcherTableau,
+    initial_state: &[f64],
+) -> Result<OdeStepper<'s>, OdeError> {
+    solver.validate_initial_state(initial_state)?;
+    // The fields of a tableau are public, so it may have changed since it was built
+    tableau.validate()?;
+    let grid: Vec<f64> = solver.params.time_grid();
+    let solution = OdeSolution::new(grid[0], initial_state);
+    let method = ExplicitRkMethod {
+        system: solver.system,
+        stages: vec![vec![0.0; initial_state.len()]; tableau.stages()],
+        tableau,
+        grid,
+        index: 1,
+    };
+    Ok(OdeStepper::new(solver, solution, Box::new(method)))
+}
+
+impl<'s> StepMethod for ExplicitRkMethod<'s> {
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError> {
+        if self.index >= self.grid.len() {
+            return Ok(false);
+        }
+        let t_next: f64 = self.grid[self.index];
+        let (t_i, y_i): (f64, Vec<f64>) = solution.last().map(|(t, y)| (t, y.clone())).unwrap();
+        let sol: Vec<f64> = explicit_rk_step(
+            self.system,
+            &self.tableau,
+            t_i,
+            &y_i,
+            t_next - t_i,
+            &mut self.stages,
+        );
+        check_finite(t_next, &sol)?;
+        solution.stats.num_rhs_evals += self.tableau.stages();
+        solution.stats.accepted_steps += 1;
+
+        // Continuous extension of the tableau if it has one, cubic Hermite otherwise
+        let stages: &[Vec<f64>] = &self.stages;
+        match &self.tableau.dense {
+            Some(dense) => events.advance_with(solution, t_next, sol, |_| {
+                let weights: Vec<&[f64]> = dense.iter().map(|w| w.as_slice()).collect();
+                StepInterpolant::from_stages(t_i, &y_i, t_next, stages, &weights)
+            })?,
+            None => events.advance(solution, t_next, sol)?,
+        };
+        // After a reset before the grid point, the next step aims at the same point
+        if solution.last().unwrap().0 == t_next {
+            self.index += 1;
+        }
+        Ok(true)
+    }
+}
+
+impl<'a> Solve for ExplicitRkSolver<'a> {
+    /// Solves the ODE with the explicit Runge Kutta method given by the tableau.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = %self.tableau.name).entered();
+        info!("starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
+    }
+
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        explicit_rk_stepper(&self.solver, self.tableau.clone(), initial_state)
+    }
+}
+
+impl<'a> Printable for ExplicitRkSolver<'a> {}
@@ -1,131 +1,134 @@
 use crate::{
-    ode_solvers::ode_solver::{
-        OdeSolver, PlotSolution, Printable, Solve, SolverChoice, WriteSolution,
+    ode_solvers::{
+        events::EventLocator,
+        ode_error::{check_finite, OdeError},
+        ode_solution::{NewtonStepInfo, OdeSolution},
+        ode_solver::{OdeSolver, Printable, Solve},
+        stepper::{OdeStepper, StepMethod},
     },
-    root_finders::newton_raphson_method::*,
+    root_finders::newton_raphson_method::{newton_raphson_system_solve, NewtonOptions},
 };
-use plotly::{
-    common::{Marker, Mode},
-    layout::Axis,
-    Layout, Plot, Scatter,
-};
-use std::{fs::File, io::Write};
+use std::cell::Cell;
+use tracing::{info, info_span, trace};
 
 pub struct ImplicitEulerSolver<'a> {
     pub solver: Box<OdeSolver<'a>>,
 }
 
-impl<'a> ImplicitEulerSolver<'a> {
-    fn implicit_euler_method(&self, solution: &mut Vec<f64>) {
-        println!("\n Using Newton Raphson method to find roots ...");
-        for index in 0..(self.solver.params.num_steps - 1) {
-            let g = |z: f64| {
-                z - solution.get(index as usize).unwrap()
-                    - self.solver.params.time_step
-                        * (self.solver.params.f)(
-                            self.solver.params.t_initial as f64
-                                + self.solver.params.time_step * ((index + 1) as f64),
-                            z,
-                        )
-            };
-            let g_dash = |z: f64| {
-                1.0 - self.solver.params.time_step
-                    * (self.solver.params.f_dash)(
-                        self.solver.params.t_initial as f64
-                            + self.solver.params.time_step * ((index + 1) as f64),
-                        z,
-                    )
-            };
-            // Note: since for g and g_dash we are using f and f_dash which are outside the fn,
-            // we need to use the Fn trait in the newton_method.rs file
-            // Else, we get the error: closures can only be coerced to `fn` types if
-            // they do not capture any variables rustc E0308.
-            // Check solution at:
-            // https://www.reddit.com/r/learnrust/comments/xvxpy2/is_there_a_workaround_for_variable_capturing_in/
-
-            let newton_sol = newton_raphson_method_root(
-                g,
-                g_dash,
-                *solution.get(index as usize).unwrap(),
-                self.solver.params.tolerance,
-                self.solver.params.max_iters,
-            );
+/// Fixed step implicit Euler loop, each step solved with Newton Raphson.
+struct ImplicitEulerMethod<'s> {
+    solver: &'s OdeSolver<'s>,
+    grid: Vec<f64>,
+    /// Index of the grid point the next step aims at
+    index: usize,
+}
 
-            solution.push(newton_sol);
+impl<'s> StepMethod for ImplicitEulerMethod<'s> {
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError> {
+        if self.index >= self.grid.len() {
+            return Ok(false);
         }
-    }
-}
+        let dim: usize = self.solver.system.dimension();
+        // Newton evaluates g and g' through the closures below, so count the calls there
+        let rhs_evals: Cell<usize> = Cell::new(0);
+        let jacobian_evals: Cell<usize> = Cell::new(0);
 
-impl<'a> Solve for ImplicitEulerSolver<'a> {
-    fn solve(&self, solution: &mut Vec<f64>) {
-        println!("\n Starting Implicit Euler Method ...");
-        self.implicit_euler_method(solution);
-    }
-}
+        let t_next: f64 = self.grid[self.index];
+        let (t_i, y_i) = solution.last().unwrap();
+        let h: f64 = t_next - t_i;
 
-impl<'a> Printable for ImplicitEulerSolver<'a> {
-    fn print_val(&self, solution: &Vec<f64>) {
-        for (index, value) in solution.iter().enumerate() {
-            println!(
-                "time: {:.3} \t value: {:.3}",
-                (self.solver.params.t_initial as f64)
-                    + (index as f64 * self.solver.params.time_step),
-                *value,
-            )
-        }
-    }
-}
+        // g(z) = z - y_i - h * f(t_{i+1}, z)
+        let g = |z: &[f64], residual: &mut [f64]| {
+            rhs_evals.set(rhs_evals.get() + 1);
+            self.solver.system.rhs(t_next, z, residual);
+            for j in 0..dim {
+                residual[j] = z[j] - y_i[j] - h * residual[j];
+            }
+        };
+        // g'(z) = I - h * f'(t_{i+1}, z)
+        let g_dash = |z: &[f64], jac: &mut [f64]| {
+            jacobian_evals.set(jacobian_evals.get() + 1);
+            rhs_evals.set(rhs_evals.get() + self.solver.system.jacobian_rhs_evals());
+            self.solver.system.jacobian(t_next, z, jac);
+            for row in 0..dim {
+                for col in 0..dim {
+                    let identity = if row == col { 1.0 } else { 0.0 };
+                    jac[row * dim + col] = identity - h * jac[row * dim + col];
+                }
+            }
+        };
+        // Note: since for g and g_dash we are using the system which is outside the fn,
+        // we need to use the Fn trait in the newton_method.rs file
+        // Else, we get the error: closures can only be coerced to `fn` types if
+        // they do not capture any variables rustc E0308.
+        // Check solution at:
+        // https://www.reddit.com/r/learnrust/comments/xvxpy2/is_there_a_workaround_for_variable_capturing_in/
 
-impl<'a> PlotSolution for ImplicitEulerSolver<'a> {
-    fn plot_solution(&self, solution: &Vec<f64>) {
-        let t_array: Vec<f64> = solution
-            .iter()
-            .enumerate()
-            .map(|(index, _)| {
-                (self.solver.params.t_initial as f64)
-                    + (index as f64 * self.solver.params.time_step)
-            })
-            .collect();
+        let newton_result = newton_raphson_system_solve(
+            g,
+            g_dash,
+            y_i.clone(),
+            &NewtonOptions::new(self.solver.params.tolerance, self.solver.params.max_iters),
+        );
+        solution.newton_steps.push(NewtonStepInfo {
+            t: t_next,
+            iterations: newton_result.iterations,
+            residual: newton_result.residual,
+            reason: newton_result.reason,
+        });
+        solution.stats.num_newton_iters += newton_result.iterations as usize;
+        trace!(
+            t = t_next,
+            h,
+            newton_iterations = newton_result.iterations,
+            residual = newton_result.residual,
+            "Newton step"
+        );
+        if !newton_result.converged {
+            return Err(OdeError::NewtonNonConvergence {
+                t: t_next,
+                iterations: newton_result.iterations,
+                reason: newton_result.reason,
+            });
+        }
+        let newton_sol: Vec<f64> = newton_result.root;
+        check_finite(t_next, &newton_sol)?;
 
-        let sol_trace = Scatter::new(t_array, solution.to_vec())
-            .mode(Mode::Markers)
-            .marker(Marker::new().size(1));
-        let mut scatter_plot = Plot::new();
-        scatter_plot.add_trace(sol_trace);
-        let plot_layout = Layout::new()
-            .title("Implicit Euler Plot")
-            .x_axis(Axis::new().title("solution"))
-            .y_axis(Axis::new().title("time"));
-        scatter_plot.set_layout(plot_layout);
+        solution.stats.num_rhs_evals += rhs_evals.get();
+        solution.stats.num_jacobian_evals += jacobian_evals.get();
+        solution.stats.accepted_steps += 1;
 
-        scatter_plot.write_html("solver_results/images/implicit_euler.html");
+        events.advance(solution, t_next, newton_sol)?;
+        // After a reset before the grid point, the next step aims at the same point
+        if solution.last().unwrap().0 == t_next {
+            self.index += 1;
+        }
+        Ok(true)
     }
 }
 
-impl<'a> SolverChoice<'a> for ImplicitEulerSolver<'a> {
-    fn choose_solver(&self) -> Box<dyn SolverChoice<'a> + 'a> {
-        Box::new(ImplicitEulerSolver {
-            solver: Box::new(*self.solver),
-        })
+impl<'a> Solve for ImplicitEulerSolver<'a> {
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError> {
+        let _span = info_span!("solve", method = "Implicit Euler").entered();
+        info!(root_finder = "Newton Raphson", "starting integration");
+        self.stepper(initial_state)?.keep_solution().run()
     }
 
-    fn name_solver(&self) -> &'a str {
-        self.solver.name
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError> {
+        self.solver.validate_initial_state(initial_state)?;
+        let grid: Vec<f64> = self.solver.params.time_grid();
+        let solution = OdeSolution::new(grid[0], initial_state);
+        let method = ImplicitEulerMethod {
+            solver: &self.solver,
+            grid,
+            index: 1,
+        };
+        Ok(OdeStepper::new(&self.solver, solution, Box::new(method)))
     }
 }
 
-impl<'a> WriteSolution<'a> for ImplicitEulerSolver<'a> {
-    fn write_solution(
-        &self,
-        file_path: &'a str,
-        solution: &Vec<f64>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut file = File::create(&file_path)?;
-
-        for val in solution {
-            writeln!(file, "{}", val)?;
-        }
-
-        Ok(())
-    }
-}
+impl<'a> Printable for ImplicitEulerSolver<'a> {}
@@ -0,0 +1,196 @@
+use crate::ode_solvers::{
+    adams_bashforth::AdamsBashforthSolver,
+    adams_moulton::AdamsMoultonSolver,
+    bdf_method::BdfSolver,
+    butcher_tableau::ButcherTableau,
+    dormand_prince::DormandPrinceSolver,
+    explicit_euler_method::ExplicitEulerSolver,
+    explicit_runge_kutta::ExplicitRkSolver,
+    heun_method::HeunSolver,
+    implicit_euler_method::ImplicitEulerSolver,
+    ode_error::OdeError,
+    ode_solver::{OdeSolver, Solve},
+    runge_kutta4::RungeKuttaSolver,
+};
+
+// Order of the Adams methods selected by name
+const ADAMS_ORDER: usize = 4;
+
+/// Description of a solution method that can be selected by name.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MethodInfo {
+    /// Key of the method, e.g. "dormand-prince"
+    pub id: &'static str,
+    /// Name shown in plots and written in the run metadata, e.g. "Dormand-Prince"
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Order of accuracy; the highest order of variable order methods
+    pub order: usize,
+    /// Controls the step size with an error estimate (uses the tolerances)
+    pub adaptive: bool,
+    /// Solves a nonlinear system per step (uses the Jacobian and `max_iters`)
+    pub implicit: bool,
+}
+
+/// Every method known to [`build_solver`].
+pub const METHODS: [MethodInfo; 14] = [
+    MethodInfo {
+        id: "explicit-euler",
+        name: "Explicit Euler",
+        description: "forward Euler method",
+        order: 1,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "heun",
+        name: "Heun",
+        description: "Heun's method (explicit trapezoidal rule)",
+        order: 2,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "midpoint",
+        name: "Midpoint",
+        description: "explicit midpoint Runge Kutta method",
+        order: 2,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "ralston",
+        name: "Ralston",
+        description: "Ralston's second order Runge Kutta method",
+        order: 2,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "rk3",
+        name: "RK3",
+        description: "Kutta's third order method",
+        order: 3,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "ssp-rk3",
+        name: "SSP-RK3",
+        description: "strong stability preserving third order method",
+        order: 3,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "rk4",
+        name: "RK4",
+        description: "classical fourth order Runge Kutta method",
+        order: 4,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "rk4-three-eighths",
+        name: "RK4 3/8",
+        description: "Kutta's 3/8-rule fourth order method",
+        order: 4,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "rk5-butcher",
+        name: "RK5 Butcher",
+        description: "Butcher's six stage fifth order method",
+        order: 5,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "adams-bashforth",
+        name: "Adams-Bashforth",
+        description: "fourth order Adams-Bashforth multistep method",
+        order: ADAMS_ORDER,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "adams-moulton",
+        name: "Adams-Moulton",
+        description: "fourth order Adams-Bashforth-Moulton predictor-corrector",
+        order: ADAMS_ORDER,
+        adaptive: false,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "dormand-prince",
+        name: "Dormand-Prince",
+        description: "adaptive Dormand-Prince 5(4) method",
+        order: 5,
+        adaptive: true,
+        implicit: false,
+    },
+    MethodInfo {
+        id: "implicit-euler",
+        name: "Implicit Euler",
+        description: "backward Euler method, for stiff problems",
+        order: 1,
+        adaptive: false,
+        implicit: true,
+    },
+    MethodInfo {
+        id: "bdf",
+        name: "BDF",
+        description: "variable order (1-5), variable step BDF method, for stiff problems",
+        order: 5,
+        adaptive: true,
+        implicit: true,
+    },
+];
+
+/// Looks up a method by its id, ignoring case and treating spaces and underscores as
+/// dashes, so "Dormand Prince" finds "dormand-prince".
+pub fn find_method(name: &str) -> Option<&'static MethodInfo> {
+    let key: String = name.trim().to_ascii_lowercase().replace([' ', '_'], "-");
+    METHODS.iter().find(|method| method.id == key)
+}
+
+/// Builds the solver of the method named `name` (see [`find_method`]) around `solver`.
+pub fn build_solver<'a>(
+    name: &str,
+    solver: OdeSolver<'a>,
+) -> Result<Box<dyn Solve + 'a>, OdeError> {
+    let method: &MethodInfo = find_method(name).ok_or_else(|| OdeError::InvalidParameter {
+        name: "method",
+        reason: format!("`{}` is not a known method", name),
+    })?;
+    let solver: Box<OdeSolver<'a>> = Box::new(solver);
+    let explicit_rk =
+        |solver: Box<OdeSolver<'a>>, tableau: ButcherTableau| -> Box<dyn Solve + 'a> {
+            Box::new(ExplicitRkSolver { solver, tableau })
+        };
+
+    Ok(match method.id {
+        "explicit-euler" => Box::new(ExplicitEulerSolver { solver }),
+        "heun" => Box::new(HeunSolver { solver }),
+        "midpoint" => explicit_rk(solver, ButcherTableau::midpoint()),
+        "ralston" => explicit_rk(solver, ButcherTableau::ralston()),
+        "rk3" => explicit_rk(solver, ButcherTableau::rk3()),
+        "ssp-rk3" => explicit_rk(solver, ButcherTableau::ssp_rk3()),
+        "rk4" => Box::new(RungeKuttaSolver { solver }),
+        "rk4-three-eighths" => explicit_rk(solver, ButcherTableau::rk4_three_eighths()),
+        "rk5-butcher" => explicit_rk(solver, ButcherTableau::rk5_butcher()),
+        "adams-bashforth" => Box::new(AdamsBashforthSolver {
+            solver,
+            order: ADAMS_ORDER,
+        }),
+        "adams-moulton" => Box::new(AdamsMoultonSolver {
+            solver,
+            order: ADAMS_ORDER,
+        }),
+        "dormand-prince" => Box::new(DormandPrinceSolver { solver }),
+        "implicit-euler" => Box::new(ImplicitEulerSolver { solver }),
+        "bdf" => Box::new(BdfSolver { solver }),
+        id => unreachable!("method `{}` is listed but not built", id),
+    })
+}
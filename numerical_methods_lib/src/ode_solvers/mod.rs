@@ -1,5 +1,19 @@
+pub mod adams_bashforth;
+pub mod adams_moulton;
+pub mod bdf_method;
+pub mod butcher_tableau;
+pub mod convergence;
+pub mod dense_output;
+pub mod dormand_prince;
+pub mod events;
 pub mod explicit_euler_method;
+pub mod explicit_runge_kutta;
 pub mod heun_method;
 pub mod implicit_euler_method;
+pub mod method_registry;
+pub mod ode_error;
+pub mod ode_solution;
 pub mod ode_solver;
+pub mod ode_system;
 pub mod runge_kutta4;
+pub mod stepper;
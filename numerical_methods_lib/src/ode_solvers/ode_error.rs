@@ -0,0 +1,53 @@
+use crate::root_finders::newton_raphson_method::TerminationReason;
+use std::fmt;
+
+/// Errors reported by the ODE solvers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OdeError {
+    /// Newton iteration of an implicit step did not converge.
+    NewtonNonConvergence {
+        t: f64,
+        iterations: i32,
+        reason: TerminationReason,
+    },
+    /// The adaptive step size became too small to make progress.
+    StepSizeUnderflow { t: f64, step: f64 },
+    /// The state contains NaN or infinite values.
+    NonFiniteState { t: f64 },
+    /// A solver parameter or the initial state is invalid.
+    InvalidParameter { name: &'static str, reason: String },
+}
+
+impl fmt::Display for OdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OdeError::NewtonNonConvergence {
+                t,
+                iterations,
+                reason,
+            } => write!(
+                f,
+                "Newton iteration did not converge at t = {} after {} iterations ({:?})",
+                t, iterations, reason
+            ),
+            OdeError::StepSizeUnderflow { t, step } => {
+                write!(f, "step size underflow at t = {} (step = {:e})", t, step)
+            }
+            OdeError::NonFiniteState { t } => write!(f, "non-finite state at t = {}", t),
+            OdeError::InvalidParameter { name, reason } => {
+                write!(f, "invalid parameter `{}`: {}", name, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OdeError {}
+
+/// Returns an error if any component of `state` is NaN or infinite.
+pub(crate) fn check_finite(t: f64, state: &[f64]) -> Result<(), OdeError> {
+    if state.iter().all(|value| value.is_finite()) {
+        Ok(())
+    } else {
+        Err(OdeError::NonFiniteState { t })
+    }
+}
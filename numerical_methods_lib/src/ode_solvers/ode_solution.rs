@@ -0,0 +1,114 @@
+use crate::{
+    ode_solvers::dense_output::DenseOutput, root_finders::newton_raphson_method::TerminationReason,
+};
+
+/// Termination status of a solver run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SolverStatus {
+    /// The integration reached the final time.
+    Success,
+    /// The adaptive step limit was hit before the final time.
+    MaxStepsReached,
+    /// A terminal event stopped the integration before the final time.
+    EventTerminated,
+    /// An observer stopped the integration before the final time.
+    Stopped,
+}
+
+/// Cost counters collected while solving.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolverStats {
+    pub num_rhs_evals: usize,
+    pub num_jacobian_evals: usize,
+    pub num_newton_iters: usize,
+    pub accepted_steps: usize,
+    pub rejected_steps: usize,
+}
+
+/// Newton diagnostics of one implicit step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NewtonStepInfo {
+    /// Time the implicit step solved for
+    pub t: f64,
+    pub iterations: i32,
+    /// Max norm of the Newton residual at the accepted iterate
+    pub residual: f64,
+    pub reason: TerminationReason,
+}
+
+/// A located event: which event fired, when, and the state at that time (before any reset).
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventRecord {
+    /// Index of the event in the solver's event list
+    pub event: usize,
+    pub t: f64,
+    pub state: Vec<f64>,
+}
+
+/// OdeSolution is returned by every solver: the time grid, the state at each time
+/// point, the cost statistics and how the integration terminated.
+#[derive(Clone, Debug)]
+pub struct OdeSolution {
+    pub times: Vec<f64>,
+    /// `states[i]` is the state at `times[i]`
+    pub states: Vec<Vec<f64>>,
+    pub stats: SolverStats,
+    pub status: SolverStatus,
+    /// One entry per implicit step, empty for explicit solvers
+    pub newton_steps: Vec<NewtonStepInfo>,
+    /// Located events in the order they occurred
+    pub events: Vec<EventRecord>,
+    /// Interpolant of every step, kept when `dense_output` is requested
+    pub dense_output: Option<DenseOutput>,
+}
+
+impl OdeSolution {
+    /// Starts a solution at the initial time and state.
+    pub fn new(t_initial: f64, initial_state: &[f64]) -> Self {
+        OdeSolution {
+            times: vec![t_initial],
+            states: vec![initial_state.to_vec()],
+            stats: SolverStats::default(),
+            status: SolverStatus::Success,
+            newton_steps: Vec::new(),
+            events: Vec::new(),
+            dense_output: None,
+        }
+    }
+
+    /// Appends the state reached at time `t`.
+    pub fn push(&mut self, t: f64, state: Vec<f64>) {
+        self.times.push(t);
+        self.states.push(state);
+    }
+
+    /// Number of time points.
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Dimension of the state.
+    pub fn dimension(&self) -> usize {
+        self.states.first().map_or(0, |y| y.len())
+    }
+
+    /// Time and state of the last time point.
+    pub fn last(&self) -> Option<(f64, &Vec<f64>)> {
+        Some((*self.times.last()?, self.states.last()?))
+    }
+
+    /// State at an arbitrary time `t` of the integrated interval, from the dense output.
+    /// `None` without dense output or outside the interval.
+    pub fn interpolate(&self, t: f64) -> Option<Vec<f64>> {
+        self.dense_output.as_ref()?.evaluate(t)
+    }
+
+    /// Time series of a single state component.
+    pub fn component(&self, index: usize) -> Vec<f64> {
+        self.states.iter().map(|y| y[index]).collect()
+    }
+}
@@ -1,85 +1,234 @@
-const T_INITIAL: i32 = 0; // t0
-const T_FINAL: i32 = 1; // tf
+use crate::ode_solvers::{
+    events::Event,
+    ode_error::{check_finite, OdeError},
+    ode_solution::OdeSolution,
+    ode_system::OdeSystem,
+    stepper::OdeStepper,
+};
+
+const T_INITIAL: f64 = 0.0; // t0
+const T_FINAL: f64 = 1.0; // tf
 const TIME_STEP: f64 = 0.01; // h
 const TOLERANCE: f64 = 1e-8; // tol
+const REL_TOLERANCE: f64 = 1e-6; // rtol
 const MAX_ITERATIONS: i32 = 100;
 
+/// Time interval of the integration. `tf < t0` integrates backwards in time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimeSpan {
+    pub t0: f64,
+    pub tf: f64,
+}
+
+impl TimeSpan {
+    pub fn new(t0: f64, tf: f64) -> Self {
+        TimeSpan { t0, tf }
+    }
+
+    /// Length of the interval, always non-negative.
+    pub fn length(&self) -> f64 {
+        (self.tf - self.t0).abs()
+    }
+
+    /// +1.0 for forward and -1.0 for backward integration.
+    pub fn direction(&self) -> f64 {
+        if self.tf < self.t0 {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Step size that covers the span in exactly `num_steps` equal steps.
+    pub fn step_for(&self, num_steps: usize) -> f64 {
+        self.length() / num_steps as f64
+    }
+
+    /// Number of steps of size `time_step` needed to reach `tf`, the last one possibly shorter.
+    /// A non-empty span always takes at least one step.
+    pub fn num_steps(&self, time_step: f64) -> usize {
+        let ratio: f64 = self.length() / time_step;
+        // Spans that are a multiple of the step up to round-off should not get a sliver step
+        let num_steps: usize = if (ratio - ratio.round()).abs() <= 1e-9 * ratio.max(1.0) {
+            ratio.round() as usize
+        } else {
+            ratio.ceil() as usize
+        };
+        if self.length() > 0.0 {
+            num_steps.max(1)
+        } else {
+            num_steps
+        }
+    }
+
+    /// Time points from `t0` to `tf` spaced by `time_step`, landing exactly on `tf`.
+    pub fn grid(&self, time_step: f64) -> Vec<f64> {
+        let num_steps: usize = self.num_steps(time_step);
+        let mut grid: Vec<f64> = (0..num_steps)
+            .map(|index| self.t0 + self.direction() * index as f64 * time_step)
+            .collect();
+        grid.push(self.tf);
+        grid
+    }
+}
+
 /// OdeSolverParams contains all the necessary parameters for solving the ODE numerically
+///
+/// The ODE itself is given separately as an [`OdeSystem`] when building the [`OdeSolver`].
+#[derive(Clone, Debug)]
 pub struct OdeSolverParams {
-    pub f: fn(f64, f64) -> f64,
-    pub f_dash: fn(f64, f64) -> f64,
-    pub num_steps: i32,
-    pub t_initial: i32,
+    pub time_span: TimeSpan,
+    /// Step size (a magnitude, the sign follows the time span); the initial step of adaptive solvers
     pub time_step: f64,
+    /// Newton tolerance, also used as the absolute tolerance of adaptive solvers
     pub tolerance: f64,
+    /// Relative tolerance of adaptive solvers
+    pub rel_tolerance: f64,
     pub max_iters: i32,
+    /// Keep the interpolant of every step in the solution, see [`OdeSolution::interpolate`]
+    pub dense_output: bool,
 }
 
 impl Default for OdeSolverParams {
     fn default() -> Self {
         OdeSolverParams {
-            f: |x: f64, _y: f64| x,
-            f_dash: |_x: f64, _y: f64| 1.0,
-            num_steps: ((T_FINAL - T_INITIAL) as f64 / TIME_STEP) as i32,
-            t_initial: T_INITIAL,
+            time_span: TimeSpan::new(T_INITIAL, T_FINAL),
             time_step: TIME_STEP,
             tolerance: TOLERANCE,
+            rel_tolerance: REL_TOLERANCE,
             max_iters: MAX_ITERATIONS,
+            dense_output: false,
         }
     }
 }
 
+impl OdeSolverParams {
+    /// Checks that the parameters describe a solvable problem.
+    pub fn validate(&self) -> Result<(), OdeError> {
+        let invalid = |name: &'static str, reason: &str| {
+            Err(OdeError::InvalidParameter {
+                name,
+                reason: reason.to_string(),
+            })
+        };
+
+        if !(self.time_step.is_finite() && self.time_step > 0.0) {
+            return invalid("time_step", "must be positive and finite");
+        }
+        if !(self.time_span.t0.is_finite() && self.time_span.tf.is_finite()) {
+            return invalid("time_span", "must have finite end points");
+        }
+        if self.time_span.t0 == self.time_span.tf {
+            return invalid("time_span", "must not be empty");
+        }
+        if !(self.tolerance.is_finite() && self.tolerance > 0.0) {
+            return invalid("tolerance", "must be positive and finite");
+        }
+        if !(self.rel_tolerance.is_finite() && self.rel_tolerance >= 0.0) {
+            return invalid("rel_tolerance", "must be non-negative and finite");
+        }
+        if self.max_iters < 0 {
+            return invalid("max_iters", "must be non-negative");
+        }
+
+        Ok(())
+    }
+
+    /// Time grid of the fixed step solvers.
+    pub fn time_grid(&self) -> Vec<f64> {
+        self.time_span.grid(self.time_step)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct OdeSolver<'a> {
     pub name: &'a str,
+    pub system: &'a dyn OdeSystem,
     pub params: &'a OdeSolverParams,
+    /// Event functions checked after every accepted step
+    pub events: &'a [Event<'a>],
+    /// Output times; when not empty the solution holds the interpolated states at these
+    /// times instead of the steps
+    pub t_eval: &'a [f64],
 }
 
 impl<'a> OdeSolver<'a> {
-    pub fn new(name: &'a str, params: &'a OdeSolverParams) -> Self {
-        OdeSolver { name, params }
+    pub fn new(name: &'a str, system: &'a dyn OdeSystem, params: &'a OdeSolverParams) -> Self {
+        OdeSolver {
+            name,
+            system,
+            params,
+            events: &[],
+            t_eval: &[],
+        }
     }
-}
 
-pub trait Solve {
-    /// Solves the ODE with the intended solver.
-    fn solve(&self, solution: &mut Vec<f64>);
-}
+    /// Locates the zero crossings of `events` while solving.
+    pub fn with_events(self, events: &'a [Event<'a>]) -> Self {
+        OdeSolver { events, ..self }
+    }
 
-pub trait Printable {
-    fn print_val(&self, solution: &Vec<f64>);
-}
+    /// Reports the solution at the times `t_eval` (ordered in the direction of integration,
+    /// inside the time span) instead of at the steps.
+    pub fn with_t_eval(self, t_eval: &'a [f64]) -> Self {
+        OdeSolver { t_eval, ..self }
+    }
 
-pub trait PlotSolution {
-    fn plot_solution(&self, solution: &Vec<f64>);
+    /// Validates the parameters together with the initial state of a solve.
+    pub(crate) fn validate_initial_state(&self, initial_state: &[f64]) -> Result<(), OdeError> {
+        self.params.validate()?;
+        let span: TimeSpan = self.params.time_span;
+        let inside = |t: f64| {
+            span.direction() * (t - span.t0) >= 0.0 && span.direction() * (span.tf - t) >= 0.0
+        };
+        if !self.t_eval.iter().all(|&t| t.is_finite() && inside(t)) {
+            return Err(OdeError::InvalidParameter {
+                name: "t_eval",
+                reason: "must lie inside the time span".to_string(),
+            });
+        }
+        if self
+            .t_eval
+            .windows(2)
+            .any(|pair| span.direction() * (pair[1] - pair[0]) < 0.0)
+        {
+            return Err(OdeError::InvalidParameter {
+                name: "t_eval",
+                reason: "must be ordered in the direction of integration".to_string(),
+            });
+        }
+        if initial_state.is_empty() {
+            return Err(OdeError::InvalidParameter {
+                name: "initial_state",
+                reason: "must have at least one component".to_string(),
+            });
+        }
+        if initial_state.len() != self.system.dimension() {
+            return Err(OdeError::InvalidParameter {
+                name: "initial_state",
+                reason: format!(
+                    "has {} components but the system dimension is {}",
+                    initial_state.len(),
+                    self.system.dimension()
+                ),
+            });
+        }
+        check_finite(self.params.time_span.t0, initial_state)
+    }
 }
 
-pub trait SolverChoice<'a> {
-    fn choose_solver(&self) -> Box<dyn SolverChoice<'a> + 'a>;
-    // By adding + 'a to the return type, you're specifying that
-    // the returned Box<dyn SolverChoice<'a>> must live at least as long as 'a.
-    // This ensures that the returned value doesn't outlive the lifetime of self
+pub trait Solve {
+    /// Solves the ODE with the intended solver, starting from `initial_state` at `time_span.t0`.
+    fn solve(&self, initial_state: &[f64]) -> Result<OdeSolution, OdeError>;
 
-    fn name_solver(&self) -> &'a str;
+    /// Same integration as [`Solve::solve`], one accepted step at a time.
+    fn stepper(&self, initial_state: &[f64]) -> Result<OdeStepper<'_>, OdeError>;
 }
 
-impl<'a> SolverChoice<'a> for OdeSolver<'a> {
-    fn choose_solver(&self) -> Box<dyn SolverChoice<'a> + 'a> {
-        Box::new(OdeSolver {
-            name: "Ode Solver",
-            params: &self.params,
-        })
-    }
-
-    fn name_solver(&self) -> &'a str {
-        self.name
+pub trait Printable {
+    fn print_val(&self, solution: &OdeSolution) {
+        for (time, value) in solution.times.iter().zip(&solution.states) {
+            println!("time: {:.3} \t value: {:.3?}", time, value)
+        }
     }
 }
-
-pub trait WriteSolution<'a> {
-    fn write_solution(
-        &self,
-        file_path: &'a str,
-        solution: &Vec<f64>,
-    ) -> Result<(), Box<dyn std::error::Error>>;
-}
@@ -0,0 +1,97 @@
+use crate::differentiation::finite_difference::FiniteDifferenceJacobian;
+
+/// Right hand side of a system of ODEs `dy/dt = f(t, y)`.
+///
+/// Implement it on a struct to carry model parameters (rate constants, forcing
+/// amplitudes, ...), or wrap a closure in a [`ClosureSystem`].
+pub trait OdeSystem {
+    /// Number of state components.
+    fn dimension(&self) -> usize;
+
+    /// Writes `f(t, y)` into `dydt`.
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]);
+
+    /// Writes the Jacobian `df/dy` (row-major, N x N) into `jac`.
+    ///
+    /// Optional: the default approximates it with forward differences of `rhs`, see
+    /// `differentiation::finite_difference` for other schemes and sparse Jacobians. For
+    /// an exact Jacobian without deriving it by hand, see `differentiation::auto_diff`.
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        FiniteDifferenceJacobian::default().ode_jacobian(self, t, y, jac);
+    }
+
+    /// Number of evaluations of `rhs` made by one call of `jacobian`, added to the
+    /// right hand side evaluations of the solver statistics.
+    ///
+    /// The default is the cost of the default `jacobian`; override both together, with
+    /// 0 for an exact Jacobian.
+    fn jacobian_rhs_evals(&self) -> usize {
+        FiniteDifferenceJacobian::default().evaluations_per_jacobian(self.dimension())
+    }
+}
+
+/// OdeSystem built from closures, so the right hand side can capture parameters.
+pub struct ClosureSystem<F, J = fn(f64, &[f64], &mut [f64])> {
+    dimension: usize,
+    rhs: F,
+    jacobian: Option<J>,
+}
+
+impl<F> ClosureSystem<F>
+where
+    F: Fn(f64, &[f64], &mut [f64]),
+{
+    /// System without a Jacobian, implicit solvers fall back to finite differences.
+    pub fn new(dimension: usize, rhs: F) -> Self {
+        ClosureSystem {
+            dimension,
+            rhs,
+            jacobian: None,
+        }
+    }
+}
+
+impl<F, J> ClosureSystem<F, J>
+where
+    F: Fn(f64, &[f64], &mut [f64]),
+    J: Fn(f64, &[f64], &mut [f64]),
+{
+    /// System with an exact Jacobian written row-major by `jacobian`.
+    pub fn with_jacobian(dimension: usize, rhs: F, jacobian: J) -> Self {
+        ClosureSystem {
+            dimension,
+            rhs,
+            jacobian: Some(jacobian),
+        }
+    }
+}
+
+impl<F, J> OdeSystem for ClosureSystem<F, J>
+where
+    F: Fn(f64, &[f64], &mut [f64]),
+    J: Fn(f64, &[f64], &mut [f64]),
+{
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        (self.rhs)(t, y, dydt)
+    }
+
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        match &self.jacobian {
+            Some(jacobian) => jacobian(t, y, jac),
+            None => {
+                FiniteDifferenceJacobian::default().ode_jacobian(self, t, y, jac);
+            }
+        }
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        match &self.jacobian {
+            Some(_) => 0,
+            None => FiniteDifferenceJacobian::default().evaluations_per_jacobian(self.dimension),
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use crate::ode_solvers::{
+    events::EventLocator,
+    ode_error::OdeError,
+    ode_solution::{OdeSolution, SolverStats, SolverStatus},
+    ode_solver::OdeSolver,
+};
+use std::ops::ControlFlow;
+use tracing::{debug, trace};
+
+/// Callback invoked with `(t, y)` after every accepted step. Returning
+/// `ControlFlow::Break` stops the integration.
+pub type Observer<'s> = Box<dyn FnMut(f64, &[f64]) -> ControlFlow<()> + 's>;
+
+/// The body of a solver loop, driven one step at a time by [`OdeStepper`].
+pub(crate) trait StepMethod {
+    /// Takes steps until one is accepted and handed to `events`, which appends it to
+    /// `solution`. Returns `Ok(false)` once the integration is over.
+    fn advance(
+        &mut self,
+        solution: &mut OdeSolution,
+        events: &mut EventLocator,
+    ) -> Result<bool, OdeError>;
+
+    /// Number of trailing points of the solution the method reads.
+    fn history(&self) -> usize {
+        1
+    }
+
+    /// Tells the method that `count` points were removed from the front of the solution.
+    fn dropped(&mut self, _count: usize) {}
+}
+
+/// Iterator over the accepted steps of a solver, see [`Solve::stepper`].
+///
+/// Each call to [`OdeStepper::step`] advances the integration by one accepted step and
+/// returns its `(t, y)`, so results can be streamed, interleaved with other computations
+/// or abandoned at any time. Unless [`OdeStepper::keep_solution`] is set, only the last
+/// points the solver itself needs are kept; the located events, the dense output and the
+/// statistics are always kept.
+///
+/// [`Solve::stepper`]: crate::ode_solvers::ode_solver::Solve::stepper
+pub struct OdeStepper<'s> {
+    method: Box<dyn StepMethod + 's>,
+    events: EventLocator<'s>,
+    solution: OdeSolution,
+    observers: Vec<Observer<'s>>,
+    keep_solution: bool,
+    /// Index in the solution of the next point to hand out
+    next_point: usize,
+    /// Number of points handed out so far, and the time of the last one
+    emitted: usize,
+    last_time: f64,
+    /// +1.0 for forward and -1.0 for backward integration
+    direction: f64,
+    finished: bool,
+}
+
+impl<'s> OdeStepper<'s> {
+    pub(crate) fn new(
+        solver: &'s OdeSolver,
+        mut solution: OdeSolution,
+        method: Box<dyn StepMethod + 's>,
+    ) -> Self {
+        let events = EventLocator::new(solver, &mut solution);
+        OdeStepper {
+            method,
+            events,
+            next_point: solution.len(),
+            emitted: 0,
+            last_time: *solution.times.last().unwrap(),
+            direction: solver.params.time_span.direction(),
+            solution,
+            observers: Vec::new(),
+            keep_solution: false,
+            finished: false,
+        }
+    }
+
+    /// Keeps every step in the solution, as [`OdeStepper::run`] returns it.
+    pub fn keep_solution(self) -> Self {
+        OdeStepper {
+            keep_solution: true,
+            ..self
+        }
+    }
+
+    /// Adds a callback invoked after every accepted step.
+    pub fn with_observer(
+        mut self,
+        observer: impl FnMut(f64, &[f64]) -> ControlFlow<()> + 's,
+    ) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Time of the last accepted step.
+    pub fn time(&self) -> f64 {
+        *self.solution.times.last().unwrap()
+    }
+
+    /// State at the last accepted step.
+    pub fn state(&self) -> &[f64] {
+        self.solution.states.last().unwrap()
+    }
+
+    pub fn stats(&self) -> SolverStats {
+        self.solution.stats
+    }
+
+    pub fn status(&self) -> SolverStatus {
+        self.solution.status
+    }
+
+    /// The steps kept so far.
+    pub fn solution(&self) -> &OdeSolution {
+        &self.solution
+    }
+
+    /// Advances by one accepted step, `None` once the integration is over.
+    pub fn step(&mut self) -> Option<Result<(f64, Vec<f64>), OdeError>> {
+        // A solver step can append several points, e.g. both sides of a state reset
+        while self.next_point >= self.solution.len() {
+            if self.finished {
+                return None;
+            }
+            match self.method.advance(&mut self.solution, &mut self.events) {
+                Ok(accepted) => {
+                    self.finished = !accepted || self.solution.status != SolverStatus::Success;
+                }
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        let t: f64 = self.solution.times[self.next_point];
+        let y: Vec<f64> = self.solution.states[self.next_point].clone();
+        self.next_point += 1;
+        self.emitted += 1;
+        trace!(
+            step = self.emitted,
+            t,
+            h = t - self.last_time,
+            "accepted step"
+        );
+        self.last_time = t;
+
+        let mut stop: bool = false;
+        for observer in &mut self.observers {
+            stop |= observer(t, &y).is_break();
+        }
+        if stop {
+            debug!(t, "integration stopped by an observer");
+            self.solution.times.truncate(self.next_point);
+            self.solution.states.truncate(self.next_point);
+            // The step that produced the stop time may have gone further
+            let direction: f64 = self.direction;
+            let after = |t_record: f64| direction * (t_record - t) > 0.0;
+            self.solution.events.retain(|record| !after(record.t));
+            self.solution.newton_steps.retain(|info| !after(info.t));
+            if let Some(dense) = &mut self.solution.dense_output {
+                dense.truncate(t);
+            }
+            self.solution.status = SolverStatus::Stopped;
+            self.finished = true;
+        }
+
+        if !self.keep_solution {
+            let count: usize = self
+                .next_point
+                .min(self.solution.len().saturating_sub(self.method.history()));
+            if count > 0 {
+                self.solution.times.drain(..count);
+                self.solution.states.drain(..count);
+                self.solution.newton_steps.clear();
+                self.method.dropped(count);
+                self.next_point -= count;
+            }
+        }
+        Some(Ok((t, y)))
+    }
+
+    /// Runs the integration to the end and returns the solution, resampled at `t_eval`
+    /// if requested.
+    pub fn run(mut self) -> Result<OdeSolution, OdeError> {
+        while let Some(step) = self.step() {
+            step?;
+        }
+        Ok(self.into_solution())
+    }
+
+    /// Stops stepping and returns the solution so far.
+    pub fn into_solution(self) -> OdeSolution {
+        let stats: SolverStats = self.solution.stats;
+        debug!(
+            status = ?self.solution.status,
+            t = self.time(),
+            accepted_steps = stats.accepted_steps,
+            rejected_steps = stats.rejected_steps,
+            rhs_evals = stats.num_rhs_evals,
+            jacobian_evals = stats.num_jacobian_evals,
+            newton_iterations = stats.num_newton_iters,
+            events = self.solution.events.len(),
+            "integration finished"
+        );
+        self.events.finish(self.solution)
+    }
+}
+
+impl<'s> Iterator for OdeStepper<'s> {
+    type Item = Result<(f64, Vec<f64>), OdeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step()
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    ode_solvers::ode_solution::OdeSolution,
+    output::{
+        output_error::OutputError,
+        solution_file::{solution_from_points, RunMetadata, SolutionRecord},
+    },
+};
+use std::io::{ErrorKind, Read, Write};
+
+/// First bytes of every binary solution file
+pub const MAGIC: [u8; 4] = *b"ODES";
+pub const VERSION: u32 = 1;
+
+/// Writes `solution` in the compact binary format, all numbers little-endian:
+///
+/// | bytes | content |
+/// |---|---|
+/// | 4 | [`MAGIC`] |
+/// | 4 | [`VERSION`] as `u32` |
+/// | 4 + m | length `m` as `u32`, then the metadata as UTF-8 JSON |
+/// | 4 | state dimension `n` as `u32` |
+/// | 8 | number of time points `k` as `u64` |
+/// | 8 k (n + 1) | per time point `t, y0, ..., yn` as `f64` |
+pub fn write_binary(
+    writer: &mut impl Write,
+    solution: &OdeSolution,
+    metadata: &RunMetadata,
+) -> Result<(), OutputError> {
+    let metadata: Vec<u8> =
+        serde_json::to_vec(metadata).map_err(|err| OutputError::Io(err.into()))?;
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(metadata.len() as u32).to_le_bytes())?;
+    writer.write_all(&metadata)?;
+    writer.write_all(&(solution.dimension() as u32).to_le_bytes())?;
+    writer.write_all(&(solution.len() as u64).to_le_bytes())?;
+    for (t, state) in solution.times.iter().zip(&solution.states) {
+        for value in std::iter::once(t).chain(state) {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Reads `N` bytes, a truncated file is an [`OutputError::InvalidBinary`].
+fn read_bytes<const N: usize>(reader: &mut impl Read, what: &str) -> Result<[u8; N], OutputError> {
+    let mut bytes = [0u8; N];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => OutputError::InvalidBinary(format!("truncated {}", what)),
+            _ => OutputError::Io(err),
+        })?;
+    Ok(bytes)
+}
+
+/// Reads a solution written by [`write_binary`].
+pub fn read_binary(mut reader: impl Read) -> Result<SolutionRecord, OutputError> {
+    if read_bytes::<4>(&mut reader, "header")? != MAGIC {
+        return Err(OutputError::InvalidBinary(
+            "not a binary solution file".to_string(),
+        ));
+    }
+    let version: u32 = u32::from_le_bytes(read_bytes(&mut reader, "header")?);
+    if version != VERSION {
+        return Err(OutputError::InvalidBinary(format!(
+            "unsupported version {}",
+            version
+        )));
+    }
+
+    let metadata_len: u32 = u32::from_le_bytes(read_bytes(&mut reader, "header")?);
+    let mut metadata: Vec<u8> = Vec::new();
+    reader
+        .by_ref()
+        .take(metadata_len as u64)
+        .read_to_end(&mut metadata)?;
+    if metadata.len() != metadata_len as usize {
+        return Err(OutputError::InvalidBinary("truncated metadata".to_string()));
+    }
+    let metadata: RunMetadata = serde_json::from_slice(&metadata)
+        .map_err(|err| OutputError::InvalidBinary(format!("metadata: {}", err)))?;
+
+    let dimension: usize = u32::from_le_bytes(read_bytes(&mut reader, "header")?) as usize;
+    let count: u64 = u64::from_le_bytes(read_bytes(&mut reader, "header")?);
+    let mut times: Vec<f64> = Vec::new();
+    let mut states: Vec<Vec<f64>> = Vec::new();
+    for _ in 0..count {
+        times.push(f64::from_le_bytes(read_bytes(&mut reader, "data")?));
+        let state: Vec<f64> = (0..dimension)
+            .map(|_| read_bytes(&mut reader, "data").map(f64::from_le_bytes))
+            .collect::<Result<_, _>>()?;
+        states.push(state);
+    }
+
+    let solution: OdeSolution =
+        solution_from_points(times, states).map_err(OutputError::InvalidBinary)?;
+    Ok(SolutionRecord {
+        metadata: Some(metadata),
+        solution,
+    })
+}
@@ -0,0 +1,126 @@
+use crate::{
+    ode_solvers::ode_solution::OdeSolution,
+    output::{
+        output_error::OutputError,
+        solution_file::{solution_from_points, RunMetadata, SolutionRecord},
+    },
+};
+use std::io::{BufRead, Write};
+
+/// Writes `solution` as CSV: the metadata as `# key: value` comment lines, a
+/// `t,y0,...,yn` header, then one row per time point. Values are written with the
+/// shortest representation that parses back to the same `f64`.
+pub fn write_csv(
+    writer: &mut impl Write,
+    solution: &OdeSolution,
+    metadata: &RunMetadata,
+) -> Result<(), OutputError> {
+    writeln!(writer, "# method: {}", metadata.method)?;
+    writeln!(writer, "# t0: {}", metadata.t0)?;
+    writeln!(writer, "# tf: {}", metadata.tf)?;
+    writeln!(writer, "# time_step: {}", metadata.time_step)?;
+    writeln!(writer, "# tolerance: {}", metadata.tolerance)?;
+    writeln!(writer, "# rel_tolerance: {}", metadata.rel_tolerance)?;
+
+    let header: Vec<String> = std::iter::once("t".to_string())
+        .chain((0..solution.dimension()).map(|component| format!("y{}", component)))
+        .collect();
+    writeln!(writer, "{}", header.join(","))?;
+    for (t, state) in solution.times.iter().zip(&solution.states) {
+        let row: Vec<String> = std::iter::once(t)
+            .chain(state)
+            .map(|value| value.to_string())
+            .collect();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+/// Metadata from the `# key: value` comments, `None` if there are none.
+fn parse_metadata(
+    comments: &[(usize, String, String)],
+) -> Result<Option<RunMetadata>, OutputError> {
+    if comments.is_empty() {
+        return Ok(None);
+    }
+    let text = |key: &str| {
+        comments
+            .iter()
+            .find(|(_, name, _)| name == key)
+            .map(|(line, _, value)| (*line, value.as_str()))
+            .ok_or_else(|| OutputError::Parse {
+                line: comments[0].0,
+                reason: format!("metadata `{}` is missing", key),
+            })
+    };
+    let number = |key: &str| -> Result<f64, OutputError> {
+        let (line, value) = text(key)?;
+        value.parse::<f64>().map_err(|err| OutputError::Parse {
+            line,
+            reason: format!("metadata `{}`: {}", key, err),
+        })
+    };
+    Ok(Some(RunMetadata {
+        method: text("method")?.1.to_string(),
+        t0: number("t0")?,
+        tf: number("tf")?,
+        time_step: number("time_step")?,
+        tolerance: number("tolerance")?,
+        rel_tolerance: number("rel_tolerance")?,
+    }))
+}
+
+/// Reads a solution written by [`write_csv`]. The metadata comments are optional, so
+/// any CSV file with a header and one numeric row per time point can be read.
+pub fn read_csv(reader: impl BufRead) -> Result<SolutionRecord, OutputError> {
+    // (line number, key, value) of every metadata comment
+    let mut comments: Vec<(usize, String, String)> = Vec::new();
+    let mut columns: Option<usize> = None;
+    let mut times: Vec<f64> = Vec::new();
+    let mut states: Vec<Vec<f64>> = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line: String = line?;
+        let number: usize = index + 1;
+        let line: &str = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            if let Some((key, value)) = comment.split_once(':') {
+                comments.push((number, key.trim().to_string(), value.trim().to_string()));
+            }
+            continue;
+        }
+        let Some(columns) = columns else {
+            // The header
+            columns = Some(line.split(',').count());
+            continue;
+        };
+
+        let row: Vec<f64> = line
+            .split(',')
+            .map(|value| value.trim().parse::<f64>())
+            .collect::<Result<_, _>>()
+            .map_err(|err| OutputError::Parse {
+                line: number,
+                reason: err.to_string(),
+            })?;
+        if row.len() != columns {
+            return Err(OutputError::Parse {
+                line: number,
+                reason: format!("expected {} values, found {}", columns, row.len()),
+            });
+        }
+        times.push(row[0]);
+        states.push(row[1..].to_vec());
+    }
+
+    let metadata: Option<RunMetadata> = parse_metadata(&comments)?;
+    let solution: OdeSolution =
+        solution_from_points(times, states).map_err(|reason| OutputError::Parse {
+            line: comments.len() + 1,
+            reason,
+        })?;
+    Ok(SolutionRecord { metadata, solution })
+}
@@ -0,0 +1,92 @@
+use crate::{
+    ode_solvers::ode_solution::OdeSolution,
+    output::{
+        output_error::OutputError,
+        solution_file::{solution_from_points, RunMetadata, SolutionRecord},
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// First line of the file
+#[derive(Serialize, Deserialize)]
+struct MetadataLine {
+    metadata: RunMetadata,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PointLine {
+    t: f64,
+    y: Vec<f64>,
+}
+
+fn json_error(err: serde_json::Error) -> OutputError {
+    OutputError::Io(err.into())
+}
+
+/// Writes `solution` as JSON lines: `{"metadata": {...}}` on the first line, then one
+/// `{"t": .., "y": [..]}` object per time point, so the file can be streamed or
+/// appended to line by line.
+pub fn write_jsonl(
+    writer: &mut impl Write,
+    solution: &OdeSolution,
+    metadata: &RunMetadata,
+) -> Result<(), OutputError> {
+    let metadata_line = MetadataLine {
+        metadata: metadata.clone(),
+    };
+    serde_json::to_writer(&mut *writer, &metadata_line).map_err(json_error)?;
+    writeln!(writer)?;
+    for (&t, state) in solution.times.iter().zip(&solution.states) {
+        let point = PointLine {
+            t,
+            y: state.clone(),
+        };
+        serde_json::to_writer(&mut *writer, &point).map_err(json_error)?;
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Reads a solution written by [`write_jsonl`].
+pub fn read_jsonl(reader: impl BufRead) -> Result<SolutionRecord, OutputError> {
+    let mut metadata: Option<RunMetadata> = None;
+    let mut times: Vec<f64> = Vec::new();
+    let mut states: Vec<Vec<f64>> = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line: String = line?;
+        let number: usize = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parse_error = |err: serde_json::Error| OutputError::Parse {
+            line: number,
+            reason: err.to_string(),
+        };
+        if metadata.is_none() {
+            let metadata_line: MetadataLine = serde_json::from_str(&line).map_err(parse_error)?;
+            metadata = Some(metadata_line.metadata);
+            continue;
+        }
+        let point: PointLine = serde_json::from_str(&line).map_err(parse_error)?;
+        if let Some(first) = states.first() {
+            if point.y.len() != first.len() {
+                return Err(OutputError::Parse {
+                    line: number,
+                    reason: format!(
+                        "expected {} state components, found {}",
+                        first.len(),
+                        point.y.len()
+                    ),
+                });
+            }
+        }
+        times.push(point.t);
+        states.push(point.y);
+    }
+
+    let solution: OdeSolution = solution_from_points(times, states)
+        .map_err(|reason| OutputError::Parse { line: 1, reason })?;
+    Ok(SolutionRecord { metadata, solution })
+}
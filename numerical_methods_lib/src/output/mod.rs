@@ -0,0 +1,5 @@
+pub mod binary_format;
+pub mod csv_format;
+pub mod jsonl_format;
+pub mod output_error;
+pub mod solution_file;
@@ -0,0 +1,41 @@
+use std::fmt;
+
+/// Errors reported when writing or reading a solution file.
+#[derive(Debug)]
+pub enum OutputError {
+    /// The file could not be read or written.
+    Io(std::io::Error),
+    /// The file extension does not name a supported format.
+    UnknownFormat(String),
+    /// A line of a text file could not be parsed (lines count from 1).
+    Parse { line: usize, reason: String },
+    /// A binary file is truncated or was not written by [`write_binary`].
+    ///
+    /// [`write_binary`]: crate::output::binary_format::write_binary
+    InvalidBinary(String),
+}
+
+impl fmt::Display for OutputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputError::Io(err) => write!(f, "could not access the solution file: {}", err),
+            OutputError::UnknownFormat(path) => write!(
+                f,
+                "unknown solution format for `{}` (expected .csv, .jsonl or .bin)",
+                path
+            ),
+            OutputError::Parse { line, reason } => write!(f, "line {}: {}", line, reason),
+            OutputError::InvalidBinary(reason) => {
+                write!(f, "invalid binary solution file: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for OutputError {}
+
+impl From<std::io::Error> for OutputError {
+    fn from(err: std::io::Error) -> Self {
+        OutputError::Io(err)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    ode_solvers::{ode_solution::OdeSolution, ode_solver::OdeSolverParams},
+    output::{
+        binary_format::{read_binary, write_binary},
+        csv_format::{read_csv, write_csv},
+        jsonl_format::{read_jsonl, write_jsonl},
+        output_error::OutputError,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{create_dir_all, File},
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// File format of a written solution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    /// Comma separated values with a `t,y0,...,yn` header, metadata in `#` comment lines.
+    Csv,
+    /// One JSON object per line: the metadata, then one `{"t": .., "y": [..]}` per point.
+    JsonLines,
+    /// Little-endian `f64` rows after a short header, see [`write_binary`].
+    Binary,
+}
+
+impl OutputFormat {
+    /// Format given by the extension of `file_path`.
+    pub fn from_path(file_path: &Path) -> Option<OutputFormat> {
+        let extension: String = file_path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(OutputFormat::Csv),
+            "jsonl" => Some(OutputFormat::JsonLines),
+            "bin" => Some(OutputFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// Describes the run that produced a solution, written along with it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunMetadata {
+    /// Name of the solution method, e.g. "Dormand-Prince"
+    pub method: String,
+    pub t0: f64,
+    pub tf: f64,
+    /// Step size, the initial step of adaptive solvers
+    pub time_step: f64,
+    pub tolerance: f64,
+    pub rel_tolerance: f64,
+}
+
+impl RunMetadata {
+    pub fn new(method: &str, params: &OdeSolverParams) -> Self {
+        RunMetadata {
+            method: method.to_string(),
+            t0: params.time_span.t0,
+            tf: params.time_span.tf,
+            time_step: params.time_step,
+            tolerance: params.tolerance,
+            rel_tolerance: params.rel_tolerance,
+        }
+    }
+}
+
+/// A solution read back from a file. The times and states are exactly the ones written.
+#[derive(Clone, Debug)]
+pub struct SolutionRecord {
+    /// `None` for a CSV file without metadata comments
+    pub metadata: Option<RunMetadata>,
+    pub solution: OdeSolution,
+}
+
+/// Builds the solution of the points read from a file, which must not be empty.
+pub(crate) fn solution_from_points(
+    times: Vec<f64>,
+    states: Vec<Vec<f64>>,
+) -> Result<OdeSolution, String> {
+    let (&t_initial, initial_state) = match (times.first(), states.first()) {
+        (Some(t), Some(y)) => (t, y),
+        _ => return Err("the file contains no time points".to_string()),
+    };
+    let mut solution = OdeSolution::new(t_initial, initial_state);
+    solution.times = times;
+    solution.states = states;
+    Ok(solution)
+}
+
+/// Writes the times and states of `solution` to `file_path`, in the format given by its
+/// extension (`.csv`, `.jsonl` or `.bin`), creating the parent directories.
+pub fn write_solution_file(
+    file_path: impl AsRef<Path>,
+    solution: &OdeSolution,
+    metadata: &RunMetadata,
+) -> Result<(), OutputError> {
+    let file_path: &Path = file_path.as_ref();
+    let format: OutputFormat = OutputFormat::from_path(file_path)
+        .ok_or_else(|| OutputError::UnknownFormat(file_path.display().to_string()))?;
+    if let Some(parent) = file_path.parent() {
+        create_dir_all(parent)?;
+    }
+    let mut writer = BufWriter::new(File::create(file_path)?);
+    match format {
+        OutputFormat::Csv => write_csv(&mut writer, solution, metadata)?,
+        OutputFormat::JsonLines => write_jsonl(&mut writer, solution, metadata)?,
+        OutputFormat::Binary => write_binary(&mut writer, solution, metadata)?,
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a solution written by [`write_solution_file`], in the format given by the
+/// extension of `file_path`.
+pub fn read_solution_file(file_path: impl AsRef<Path>) -> Result<SolutionRecord, OutputError> {
+    let file_path: &Path = file_path.as_ref();
+    let format: OutputFormat = OutputFormat::from_path(file_path)
+        .ok_or_else(|| OutputError::UnknownFormat(file_path.display().to_string()))?;
+    let reader = BufReader::new(File::open(file_path)?);
+    match format {
+        OutputFormat::Csv => read_csv(reader),
+        OutputFormat::JsonLines => read_jsonl(reader),
+        OutputFormat::Binary => read_binary(reader),
+    }
+}
@@ -0,0 +1,101 @@
+use crate::plotting::{
+    figure::{AxisScale, Figure, PlotStyle, Trace},
+    plot_error::PlotError,
+};
+use std::path::Path;
+
+/// Log-log figure of the error of one or more methods against the step size (or the
+/// tolerance, or the work), where a method of order `p` shows as a line of slope `p`.
+///
+/// ```ignore
+/// ConvergencePlot::new("y' = -20 t y^2")
+///     .with_series("Heun", &step_sizes, &heun_errors)
+///     .with_series("RK4", &step_sizes, &rk4_errors)
+///     .with_reference_slope(4)
+///     .save("solver_results/images/convergence.svg")?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvergencePlot {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub series: Vec<Trace>,
+}
+
+impl ConvergencePlot {
+    pub fn new(title: &str) -> Self {
+        ConvergencePlot {
+            title: title.to_string(),
+            x_label: "step size".to_string(),
+            y_label: "error".to_string(),
+            series: Vec::new(),
+        }
+    }
+
+    pub fn with_x_label(self, x_label: &str) -> Self {
+        ConvergencePlot {
+            x_label: x_label.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_y_label(self, y_label: &str) -> Self {
+        ConvergencePlot {
+            y_label: y_label.to_string(),
+            ..self
+        }
+    }
+
+    /// Adds the errors measured at the given step sizes.
+    pub fn with_series(mut self, name: &str, step_sizes: &[f64], errors: &[f64]) -> Self {
+        self.series.push(Trace {
+            name: name.to_string(),
+            x: step_sizes.to_vec(),
+            y: errors.to_vec(),
+            z: None,
+        });
+        self
+    }
+
+    /// Adds a line `C h^order` through the last point of the first series, to compare
+    /// the observed slopes with. Does nothing before a series was added.
+    pub fn with_reference_slope(mut self, order: u32) -> Self {
+        let (h_ref, error_ref) = match self.series.first() {
+            Some(first) if !first.x.is_empty() => {
+                (*first.x.last().unwrap(), *first.y.last().unwrap())
+            }
+            _ => return self,
+        };
+        let step_sizes: Vec<f64> = self.series[0].x.clone();
+        let errors: Vec<f64> = step_sizes
+            .iter()
+            .map(|h| error_ref * (h / h_ref).powi(order as i32))
+            .collect();
+        self.series.push(Trace {
+            name: format!("O(h^{})", order),
+            x: step_sizes,
+            y: errors,
+            z: None,
+        });
+        self
+    }
+
+    pub fn to_figure(&self) -> Figure {
+        Figure {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            z_label: String::new(),
+            x_scale: AxisScale::Log,
+            y_scale: AxisScale::Log,
+            style: PlotStyle::LinesMarkers,
+            traces: self.series.clone(),
+        }
+    }
+
+    /// Writes the figure in the format given by the extension of `file_path`, see
+    /// [`Figure::save`].
+    pub fn save(&self, file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+        self.to_figure().save(file_path)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::plotting::plot_error::PlotError;
+use std::path::Path;
+
+/// How the points of a trace are drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlotStyle {
+    Markers,
+    Lines,
+    LinesMarkers,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisScale {
+    Linear,
+    Log,
+}
+
+/// Output file format of a figure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlotFormat {
+    /// Interactive plotly page, needs the `plot` feature.
+    Html,
+    /// Needs the `static-plot` feature.
+    Svg,
+    /// Needs the `static-plot` feature.
+    Png,
+}
+
+impl PlotFormat {
+    /// Format given by the extension of `file_path`.
+    pub fn from_path(file_path: &Path) -> Option<PlotFormat> {
+        let extension: String = file_path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "html" | "htm" => Some(PlotFormat::Html),
+            "svg" => Some(PlotFormat::Svg),
+            "png" => Some(PlotFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+/// One curve of a figure. `z` is set for the traces of a 3-D figure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    pub name: String,
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub z: Option<Vec<f64>>,
+}
+
+/// Backend independent description of a figure, drawn by the HTML (plotly) and the
+/// static (plotters) backends alike.
+///
+/// Built by [`SolutionPlot`], [`PhasePortrait`] and [`ConvergencePlot`].
+///
+/// [`SolutionPlot`]: crate::plotting::solution_plot::SolutionPlot
+/// [`PhasePortrait`]: crate::plotting::phase_portrait::PhasePortrait
+/// [`ConvergencePlot`]: crate::plotting::convergence_plot::ConvergencePlot
+#[derive(Clone, Debug, PartialEq)]
+pub struct Figure {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    /// Only used by 3-D figures
+    pub z_label: String,
+    pub x_scale: AxisScale,
+    pub y_scale: AxisScale,
+    pub style: PlotStyle,
+    pub traces: Vec<Trace>,
+}
+
+impl Figure {
+    /// Whether the traces are curves in space.
+    pub fn is_3d(&self) -> bool {
+        self.traces.iter().any(|trace| trace.z.is_some())
+    }
+
+    /// Writes the figure in the format given by the extension of `file_path`
+    /// (`.html`, `.svg` or `.png`), creating the parent directories.
+    pub fn save(&self, file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+        let file_path: &Path = file_path.as_ref();
+        let format: PlotFormat = PlotFormat::from_path(file_path)
+            .ok_or_else(|| PlotError::UnknownFormat(file_path.display().to_string()))?;
+        self.write(file_path, format)
+    }
+
+    /// Writes the figure to `file_path` in the given format, creating the parent
+    /// directories.
+    pub fn write(&self, file_path: impl AsRef<Path>, format: PlotFormat) -> Result<(), PlotError> {
+        let file_path: &Path = file_path.as_ref();
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        match format {
+            #[cfg(feature = "plot")]
+            PlotFormat::Html => crate::plotting::html::write_html(self, file_path),
+            #[cfg(feature = "static-plot")]
+            PlotFormat::Svg => crate::plotting::static_export::write_svg(self, file_path),
+            #[cfg(feature = "static-plot")]
+            PlotFormat::Png => crate::plotting::static_export::write_png(self, file_path),
+            #[allow(unreachable_patterns)]
+            _ => Err(PlotError::FormatUnavailable(format)),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::plotting::{
+    figure::{AxisScale, Figure, PlotStyle},
+    plot_error::PlotError,
+};
+use plotly::{
+    common::{Marker, Mode, Title},
+    layout::{Axis, AxisType, LayoutScene},
+    Layout, Plot, Scatter, Scatter3D,
+};
+use std::{fs::File, io::Write, path::Path};
+
+fn axis(label: &str, scale: AxisScale) -> Axis {
+    let axis = Axis::new().title(Title::with_text(label));
+    match scale {
+        AxisScale::Linear => axis,
+        AxisScale::Log => axis.type_(AxisType::Log),
+    }
+}
+
+/// The figure as a plotly plot, e.g. to show it or customise it further.
+pub fn to_plotly(figure: &Figure) -> Plot {
+    let mode: Mode = match figure.style {
+        PlotStyle::Markers => Mode::Markers,
+        PlotStyle::Lines => Mode::Lines,
+        PlotStyle::LinesMarkers => Mode::LinesMarkers,
+    };
+    let mut plot = Plot::new();
+    for trace in &figure.traces {
+        match &trace.z {
+            Some(z) => plot.add_trace(
+                Scatter3D::new(trace.x.clone(), trace.y.clone(), z.clone())
+                    .name(&trace.name)
+                    .mode(mode.clone())
+                    .marker(Marker::new().size(2)),
+            ),
+            None => plot.add_trace(
+                Scatter::new(trace.x.clone(), trace.y.clone())
+                    .name(&trace.name)
+                    .mode(mode.clone())
+                    .marker(Marker::new().size(2)),
+            ),
+        }
+    }
+
+    let x_axis: Axis = axis(&figure.x_label, figure.x_scale);
+    let y_axis: Axis = axis(&figure.y_label, figure.y_scale);
+    let layout = Layout::new().title(Title::with_text(&figure.title));
+    let layout = if figure.is_3d() {
+        layout.scene(
+            LayoutScene::new()
+                .x_axis(x_axis)
+                .y_axis(y_axis)
+                .z_axis(axis(&figure.z_label, AxisScale::Linear)),
+        )
+    } else {
+        layout.x_axis(x_axis).y_axis(y_axis)
+    };
+    plot.set_layout(layout);
+    plot
+}
+
+/// Writes the figure as a standalone HTML page.
+pub fn write_html(figure: &Figure, file_path: &Path) -> Result<(), PlotError> {
+    let mut file = File::create(file_path)?;
+    file.write_all(to_plotly(figure).to_html().as_bytes())?;
+    Ok(())
+}
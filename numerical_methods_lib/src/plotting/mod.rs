@@ -0,0 +1,9 @@
+pub mod convergence_plot;
+pub mod figure;
+#[cfg(feature = "plot")]
+pub mod html;
+pub mod phase_portrait;
+pub mod plot_error;
+pub mod solution_plot;
+#[cfg(feature = "static-plot")]
+pub mod static_export;
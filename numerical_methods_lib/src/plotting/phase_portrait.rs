@@ -0,0 +1,100 @@
+use crate::{
+    ode_solvers::ode_solution::OdeSolution,
+    plotting::{
+        figure::{AxisScale, Figure, PlotStyle, Trace},
+        plot_error::PlotError,
+    },
+};
+use std::path::Path;
+
+/// Figure of trajectories in the state space of a 2-D or 3-D system: the chosen state
+/// components plotted against each other, time is implicit.
+///
+/// ```ignore
+/// PhasePortrait::planar("Van der Pol", 0, 1)
+///     .with_solution("mu = 1", &solution)
+///     .save("solver_results/images/van_der_pol.png")?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhasePortrait {
+    pub title: String,
+    /// Indices of the state components on the x, y and (for a 3-D portrait) z axes
+    pub components: Vec<usize>,
+    /// Axis labels, `y0`, `y1`, ... by default
+    pub labels: Vec<String>,
+    pub style: PlotStyle,
+    pub trajectories: Vec<Trace>,
+}
+
+impl PhasePortrait {
+    /// Portrait of component `y` against component `x`.
+    pub fn planar(title: &str, x: usize, y: usize) -> Self {
+        PhasePortrait::with_components(title, vec![x, y])
+    }
+
+    /// Portrait of three components, e.g. the Lorenz attractor.
+    pub fn spatial(title: &str, x: usize, y: usize, z: usize) -> Self {
+        PhasePortrait::with_components(title, vec![x, y, z])
+    }
+
+    fn with_components(title: &str, components: Vec<usize>) -> Self {
+        PhasePortrait {
+            title: title.to_string(),
+            labels: components.iter().map(|c| format!("y{}", c)).collect(),
+            components,
+            style: PlotStyle::Lines,
+            trajectories: Vec::new(),
+        }
+    }
+
+    /// Sets the axis labels, in the order of the components.
+    pub fn with_labels(mut self, labels: &[&str]) -> Self {
+        for (label, new_label) in self.labels.iter_mut().zip(labels) {
+            *label = new_label.to_string();
+        }
+        self
+    }
+
+    pub fn with_style(self, style: PlotStyle) -> Self {
+        PhasePortrait { style, ..self }
+    }
+
+    /// Adds the trajectory of `solution`.
+    ///
+    /// # Panics
+    /// If the solution has fewer components than the portrait plots.
+    pub fn with_solution(mut self, label: &str, solution: &OdeSolution) -> Self {
+        self.add_solution(label, solution);
+        self
+    }
+
+    /// Same as [`PhasePortrait::with_solution`], for adding solutions in a loop.
+    pub fn add_solution(&mut self, label: &str, solution: &OdeSolution) {
+        let mut axes = self.components.iter().map(|&c| solution.component(c));
+        self.trajectories.push(Trace {
+            name: label.to_string(),
+            x: axes.next().unwrap(),
+            y: axes.next().unwrap(),
+            z: axes.next(),
+        });
+    }
+
+    pub fn to_figure(&self) -> Figure {
+        Figure {
+            title: self.title.clone(),
+            x_label: self.labels[0].clone(),
+            y_label: self.labels[1].clone(),
+            z_label: self.labels.get(2).cloned().unwrap_or_default(),
+            x_scale: AxisScale::Linear,
+            y_scale: AxisScale::Linear,
+            style: self.style,
+            traces: self.trajectories.clone(),
+        }
+    }
+
+    /// Writes the figure in the format given by the extension of `file_path`, see
+    /// [`Figure::save`].
+    pub fn save(&self, file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+        self.to_figure().save(file_path)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::plotting::figure::PlotFormat;
+use std::fmt;
+
+/// Errors reported when writing a figure.
+#[derive(Debug)]
+pub enum PlotError {
+    /// The output file could not be written.
+    Io(std::io::Error),
+    /// The file extension does not name a supported format.
+    UnknownFormat(String),
+    /// The backend of the format was not compiled in, see the cargo features.
+    FormatUnavailable(PlotFormat),
+    /// No font could be loaded to draw the text of a static image.
+    FontUnavailable(String),
+    /// The static backend failed to draw the figure.
+    Drawing(String),
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlotError::Io(err) => write!(f, "could not write the plot: {}", err),
+            PlotError::UnknownFormat(path) => {
+                write!(
+                    f,
+                    "unknown plot format for `{}` (expected .html, .svg or .png)",
+                    path
+                )
+            }
+            PlotError::FormatUnavailable(format) => {
+                let feature: &str = match format {
+                    PlotFormat::Html => "plot",
+                    PlotFormat::Svg | PlotFormat::Png => "static-plot",
+                };
+                write!(
+                    f,
+                    "{:?} output needs the `{}` feature of numerical_methods_lib",
+                    format, feature
+                )
+            }
+            PlotError::FontUnavailable(reason) => write!(f, "no font available: {}", reason),
+            PlotError::Drawing(reason) => write!(f, "could not draw the plot: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+impl From<std::io::Error> for PlotError {
+    fn from(err: std::io::Error) -> Self {
+        PlotError::Io(err)
+    }
+}
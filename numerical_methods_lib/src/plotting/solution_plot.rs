@@ -0,0 +1,131 @@
+use crate::{
+    ode_solvers::ode_solution::OdeSolution,
+    plotting::{
+        figure::{AxisScale, Figure, PlotFormat, PlotStyle, Trace},
+        plot_error::PlotError,
+    },
+};
+use std::path::Path;
+
+/// One curve of a figure: a state component over time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Series {
+    pub name: String,
+    pub times: Vec<f64>,
+    pub values: Vec<f64>,
+}
+
+/// Figure of one or more solutions against time, independent of the solver that
+/// produced them.
+///
+/// Every solution added contributes one series per state component, so several solver
+/// runs can be overlaid for comparison:
+///
+/// ```ignore
+/// SolutionPlot::new("y' = -20 t y^2")
+///     .with_solution("RK4", &rk4_solution)
+///     .with_solution("Dormand-Prince", &dp_solution)
+///     .save("solver_results/images/comparison.svg")?;
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolutionPlot {
+    pub title: String,
+    pub x_label: String,
+    pub y_label: String,
+    pub style: PlotStyle,
+    pub series: Vec<Series>,
+}
+
+impl SolutionPlot {
+    /// Empty figure with time on the x axis and the solution on the y axis.
+    pub fn new(title: &str) -> Self {
+        SolutionPlot {
+            title: title.to_string(),
+            x_label: "time".to_string(),
+            y_label: "solution".to_string(),
+            style: PlotStyle::Markers,
+            series: Vec::new(),
+        }
+    }
+
+    pub fn with_x_label(self, x_label: &str) -> Self {
+        SolutionPlot {
+            x_label: x_label.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_y_label(self, y_label: &str) -> Self {
+        SolutionPlot {
+            y_label: y_label.to_string(),
+            ..self
+        }
+    }
+
+    pub fn with_style(self, style: PlotStyle) -> Self {
+        SolutionPlot { style, ..self }
+    }
+
+    /// Adds every component of `solution`. A scalar solution is named `label`, the
+    /// components of a system `label y0`, `label y1`, ...
+    pub fn with_solution(mut self, label: &str, solution: &OdeSolution) -> Self {
+        self.add_solution(label, solution);
+        self
+    }
+
+    /// Same as [`SolutionPlot::with_solution`], for adding solutions in a loop.
+    pub fn add_solution(&mut self, label: &str, solution: &OdeSolution) {
+        let dimension: usize = solution.dimension();
+        for component in 0..dimension {
+            let name: String = if dimension == 1 {
+                label.to_string()
+            } else {
+                format!("{} y{}", label, component)
+            };
+            self.series.push(Series {
+                name,
+                times: solution.times.clone(),
+                values: solution.component(component),
+            });
+        }
+    }
+
+    pub fn to_figure(&self) -> Figure {
+        Figure {
+            title: self.title.clone(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            z_label: String::new(),
+            x_scale: AxisScale::Linear,
+            y_scale: AxisScale::Linear,
+            style: self.style,
+            traces: self
+                .series
+                .iter()
+                .map(|series| Trace {
+                    name: series.name.clone(),
+                    x: series.times.clone(),
+                    y: series.values.clone(),
+                    z: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// The figure as a plotly plot, e.g. to show it or customise it further.
+    #[cfg(feature = "plot")]
+    pub fn to_plotly(&self) -> plotly::Plot {
+        crate::plotting::html::to_plotly(&self.to_figure())
+    }
+
+    /// Writes the figure as a standalone HTML page, creating the parent directories.
+    pub fn write_html(&self, file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+        self.to_figure().write(file_path, PlotFormat::Html)
+    }
+
+    /// Writes the figure in the format given by the extension of `file_path`, see
+    /// [`Figure::save`].
+    pub fn save(&self, file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+        self.to_figure().save(file_path)
+    }
+}
@@ -0,0 +1,283 @@
+use crate::plotting::{
+    figure::{AxisScale, Figure, PlotStyle},
+    plot_error::PlotError,
+};
+use plotters::{
+    coord::{
+        ranged1d::{AsRangedCoord, ValueFormatter},
+        Shift,
+    },
+    prelude::*,
+    style::register_font,
+};
+use std::{
+    ops::Range,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+/// Size in pixels of the exported images.
+pub const IMAGE_SIZE: (u32, u32) = (1024, 768);
+
+/// Environment variable naming the TrueType font used for the text of static images.
+pub const FONT_VARIABLE: &str = "ODE_PLOT_FONT";
+
+// DejaVu Sans, used when no font was registered or named by FONT_VARIABLE, see
+// fonts/LICENSE
+const EMBEDDED_FONT: &[u8] = include_bytes!("../../fonts/DejaVuSans.ttf");
+
+const FONT_FAMILY: &str = "sans-serif";
+
+static FONT_REGISTERED: AtomicBool = AtomicBool::new(false);
+// Outcome of the registration of the default font, done once per program
+static DEFAULT_FONT: OnceLock<Result<(), String>> = OnceLock::new();
+
+/// Uses the TrueType font at `file_path` for the text of static images.
+///
+/// The images are rasterised in pure Rust, so the font is read from a file instead of
+/// being looked up through the system. Without a call to this function the font named by
+/// the `ODE_PLOT_FONT` environment variable is used, or else the embedded DejaVu Sans.
+///
+/// The font registry of plotters keeps the font data for the rest of the program, so
+/// every call leaks a copy of the file: register a font once, not once per figure.
+pub fn register_font_file(file_path: impl AsRef<Path>) -> Result<(), PlotError> {
+    let file_path: &Path = file_path.as_ref();
+    let bytes: Vec<u8> = std::fs::read(file_path).map_err(|err| {
+        PlotError::FontUnavailable(format!("cannot read `{}`: {}", file_path.display(), err))
+    })?;
+    register_font(FONT_FAMILY, FontStyle::Normal, bytes.leak()).map_err(|_| {
+        PlotError::FontUnavailable(format!("`{}` is not a TrueType font", file_path.display()))
+    })?;
+    FONT_REGISTERED.store(true, Ordering::Release);
+    Ok(())
+}
+
+fn ensure_font() -> Result<(), PlotError> {
+    if FONT_REGISTERED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+    DEFAULT_FONT
+        .get_or_init(|| match std::env::var(FONT_VARIABLE) {
+            Ok(file_path) => register_font_file(file_path).map_err(|err| match err {
+                PlotError::FontUnavailable(reason) => reason,
+                err => err.to_string(),
+            }),
+            Err(_) => register_font(FONT_FAMILY, FontStyle::Normal, EMBEDDED_FONT)
+                .map_err(|_| "the embedded font could not be loaded".to_string()),
+        })
+        .clone()
+        .map_err(PlotError::FontUnavailable)
+}
+
+/// Writes the figure as an SVG image.
+pub fn write_svg(figure: &Figure, file_path: &Path) -> Result<(), PlotError> {
+    ensure_font()?;
+    draw(
+        figure,
+        SVGBackend::new(file_path, IMAGE_SIZE).into_drawing_area(),
+    )
+}
+
+/// The figure as an SVG image, e.g. to be inlined in an HTML page.
+pub fn to_svg(figure: &Figure) -> Result<String, PlotError> {
+    ensure_font()?;
+    let mut svg = String::new();
+    draw(
+        figure,
+        SVGBackend::with_string(&mut svg, IMAGE_SIZE).into_drawing_area(),
+    )?;
+    Ok(svg)
+}
+
+/// Writes the figure as a PNG image.
+pub fn write_png(figure: &Figure, file_path: &Path) -> Result<(), PlotError> {
+    ensure_font()?;
+    draw(
+        figure,
+        BitMapBackend::new(file_path, IMAGE_SIZE).into_drawing_area(),
+    )
+}
+
+fn drawing_error(err: impl std::error::Error) -> PlotError {
+    PlotError::Drawing(err.to_string())
+}
+
+/// Range covering `values`, widened if all values are equal. Only positive values count
+/// on a log axis.
+fn value_range<'v>(values: impl Iterator<Item = &'v f64>, scale: AxisScale) -> Range<f64> {
+    let (min, max) = values
+        .filter(|value| value.is_finite() && (scale == AxisScale::Linear || **value > 0.0))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    match scale {
+        _ if min > max => 0.0..1.0,
+        AxisScale::Linear if min == max => min - 0.5..max + 0.5,
+        AxisScale::Linear => min..max,
+        AxisScale::Log if min == max => min / 2.0..max * 2.0,
+        AxisScale::Log => min..max,
+    }
+}
+
+fn draw<DB: DrawingBackend>(
+    figure: &Figure,
+    root: DrawingArea<DB, Shift>,
+) -> Result<(), PlotError> {
+    root.fill(&WHITE).map_err(drawing_error)?;
+    let x_range: Range<f64> = value_range(
+        figure.traces.iter().flat_map(|trace| &trace.x),
+        figure.x_scale,
+    );
+    let y_range: Range<f64> = value_range(
+        figure.traces.iter().flat_map(|trace| &trace.y),
+        figure.y_scale,
+    );
+
+    if figure.is_3d() {
+        let z_range: Range<f64> = value_range(
+            figure
+                .traces
+                .iter()
+                .flat_map(|trace| trace.z.iter().flatten()),
+            AxisScale::Linear,
+        );
+        draw_3d(figure, &root, x_range, y_range, z_range)?;
+    } else {
+        match (figure.x_scale, figure.y_scale) {
+            (AxisScale::Linear, AxisScale::Linear) => draw_2d(figure, &root, x_range, y_range)?,
+            (AxisScale::Log, AxisScale::Linear) => {
+                draw_2d(figure, &root, x_range.log_scale(), y_range)?
+            }
+            (AxisScale::Linear, AxisScale::Log) => {
+                draw_2d(figure, &root, x_range, y_range.log_scale())?
+            }
+            (AxisScale::Log, AxisScale::Log) => {
+                draw_2d(figure, &root, x_range.log_scale(), y_range.log_scale())?
+            }
+        }
+    }
+    root.present().map_err(drawing_error)
+}
+
+fn draw_2d<DB, X, Y>(
+    figure: &Figure,
+    root: &DrawingArea<DB, Shift>,
+    x_range: X,
+    y_range: Y,
+) -> Result<(), PlotError>
+where
+    DB: DrawingBackend,
+    X: AsRangedCoord<Value = f64>,
+    Y: AsRangedCoord<Value = f64>,
+    X::CoordDescType: ValueFormatter<f64>,
+    Y::CoordDescType: ValueFormatter<f64>,
+{
+    let mut chart = ChartBuilder::on(root)
+        .caption(&figure.title, (FONT_FAMILY, 28))
+        .margin(20)
+        .x_label_area_size(50)
+        .y_label_area_size(70)
+        .build_cartesian_2d(x_range, y_range)
+        .map_err(drawing_error)?;
+    chart
+        .configure_mesh()
+        .x_desc(figure.x_label.as_str())
+        .y_desc(figure.y_label.as_str())
+        .label_style((FONT_FAMILY, 16))
+        .draw()
+        .map_err(drawing_error)?;
+
+    let drawable = |value: f64, scale: AxisScale| {
+        value.is_finite() && (scale == AxisScale::Linear || value > 0.0)
+    };
+    for (index, trace) in figure.traces.iter().enumerate() {
+        let color: RGBAColor = Palette99::pick(index).to_rgba();
+        let points: Vec<(f64, f64)> = trace
+            .x
+            .iter()
+            .zip(&trace.y)
+            .map(|(&x, &y)| (x, y))
+            .filter(|&(x, y)| drawable(x, figure.x_scale) && drawable(y, figure.y_scale))
+            .collect();
+        if figure.style != PlotStyle::Markers {
+            chart
+                .draw_series(LineSeries::new(points.clone(), color.stroke_width(2)))
+                .map_err(drawing_error)?;
+        }
+        if figure.style != PlotStyle::Lines {
+            chart
+                .draw_series(
+                    points
+                        .iter()
+                        .map(|&point| Circle::new(point, 2, color.filled())),
+                )
+                .map_err(drawing_error)?;
+        }
+        // An empty series carries the legend entry
+        chart
+            .draw_series(std::iter::empty::<Circle<(f64, f64), i32>>())
+            .map_err(drawing_error)?
+            .label(trace.name.as_str())
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+    }
+    chart
+        .configure_series_labels()
+        .label_font((FONT_FAMILY, 16))
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(drawing_error)
+}
+
+/// Curves in space. The axis titles are not drawn, plotters has no 3-D axis descriptions.
+fn draw_3d<DB: DrawingBackend>(
+    figure: &Figure,
+    root: &DrawingArea<DB, Shift>,
+    x_range: Range<f64>,
+    y_range: Range<f64>,
+    z_range: Range<f64>,
+) -> Result<(), PlotError> {
+    // plotters draws the second coordinate upwards, the z component of the figure
+    let mut chart = ChartBuilder::on(root)
+        .caption(&figure.title, (FONT_FAMILY, 28))
+        .margin(20)
+        .build_cartesian_3d(x_range, z_range, y_range)
+        .map_err(drawing_error)?;
+    chart
+        .configure_axes()
+        .label_style((FONT_FAMILY, 14))
+        .draw()
+        .map_err(drawing_error)?;
+
+    for (index, trace) in figure.traces.iter().enumerate() {
+        let color: RGBAColor = Palette99::pick(index).to_rgba();
+        let z: &[f64] = trace.z.as_deref().unwrap_or(&[]);
+        let points: Vec<(f64, f64, f64)> = trace
+            .x
+            .iter()
+            .zip(&trace.y)
+            .zip(z)
+            .map(|((&x, &y), &z)| (x, z, y))
+            .filter(|&(x, z, y)| x.is_finite() && y.is_finite() && z.is_finite())
+            .collect();
+        chart
+            .draw_series(LineSeries::new(points, color.stroke_width(2)))
+            .map_err(drawing_error)?
+            .label(trace.name.as_str())
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+    }
+    chart
+        .configure_series_labels()
+        .label_font((FONT_FAMILY, 16))
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .map_err(drawing_error)
+}
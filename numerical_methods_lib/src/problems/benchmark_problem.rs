@@ -0,0 +1,105 @@
+use crate::{
+    ode_solvers::{ode_solver::TimeSpan, ode_system::OdeSystem},
+    problems::{chemical_kinetics, mechanics, oscillators, scalar_problems},
+};
+
+/// Exact solution `y(t)` for the initial state `y0` at `t0`.
+pub type ExactSolution = fn(t0: f64, y0: &[f64], t: f64) -> Vec<f64>;
+
+/// What the solutions of a problem are measured against.
+#[derive(Clone, Debug)]
+pub enum Reference {
+    /// Analytic solution, valid from any initial time and state.
+    Exact(ExactSolution),
+    /// State at the end of the default time span from the default initial state, from
+    /// an analytic argument (e.g. a whole number of periods) or a high-accuracy solve.
+    FinalState(Vec<f64>),
+}
+
+/// A standard test problem: the system with its default parameters, time span and
+/// initial state, and a reference solution, so every method can be evaluated on the
+/// same suite.
+///
+/// ```ignore
+/// let robertson = problem("robertson").unwrap();
+/// let params = OdeSolverParams {
+///     time_span: robertson.time_span,
+///     ..Default::default()
+/// };
+/// let solver = OdeSolver::new("BDF", robertson.system.as_ref(), &params);
+/// let solution = build_solver("bdf", solver)?.solve(&robertson.initial_state)?;
+/// ```
+pub struct BenchmarkProblem {
+    /// Key of the problem, e.g. "van-der-pol-stiff"
+    pub id: &'static str,
+    pub description: String,
+    /// Explicit methods need tiny steps on it
+    pub stiff: bool,
+    pub time_span: TimeSpan,
+    pub initial_state: Vec<f64>,
+    pub system: Box<dyn OdeSystem>,
+    /// `None` for problems without a known solution, such as user-defined ones
+    pub reference: Option<Reference>,
+}
+
+impl BenchmarkProblem {
+    /// Exact solution, for the problems that have one.
+    pub fn exact(&self) -> Option<ExactSolution> {
+        match self.reference {
+            Some(Reference::Exact(exact)) => Some(exact),
+            _ => None,
+        }
+    }
+
+    /// Reference state at the end of the default time span.
+    pub fn reference_final_state(&self) -> Option<Vec<f64>> {
+        match &self.reference {
+            Some(Reference::Exact(exact)) => Some(exact(
+                self.time_span.t0,
+                &self.initial_state,
+                self.time_span.tf,
+            )),
+            Some(Reference::FinalState(state)) => Some(state.clone()),
+            None => None,
+        }
+    }
+}
+
+/// Names of the problems of the suite, in the order `all_problems` returns them.
+pub const PROBLEM_IDS: [&str; 11] = [
+    "exponential-decay",
+    "logistic",
+    "prothero-robinson",
+    "van-der-pol",
+    "van-der-pol-stiff",
+    "lorenz",
+    "brusselator",
+    "robertson",
+    "hires",
+    "kepler",
+    "pendulum",
+];
+
+/// The problem of the suite named `id`.
+pub fn problem(id: &str) -> Option<BenchmarkProblem> {
+    let problem: BenchmarkProblem = match id {
+        "exponential-decay" => scalar_problems::exponential_decay(),
+        "logistic" => scalar_problems::logistic(),
+        "prothero-robinson" => scalar_problems::prothero_robinson(),
+        "van-der-pol" => oscillators::van_der_pol(),
+        "van-der-pol-stiff" => oscillators::van_der_pol_stiff(),
+        "lorenz" => oscillators::lorenz(),
+        "brusselator" => oscillators::brusselator(),
+        "robertson" => chemical_kinetics::robertson(),
+        "hires" => chemical_kinetics::hires(),
+        "kepler" => mechanics::kepler(),
+        "pendulum" => mechanics::pendulum(),
+        _ => return None,
+    };
+    Some(problem)
+}
+
+/// Every problem of the suite.
+pub fn all_problems() -> Vec<BenchmarkProblem> {
+    PROBLEM_IDS.iter().filter_map(|id| problem(id)).collect()
+}
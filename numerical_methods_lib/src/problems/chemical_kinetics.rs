@@ -0,0 +1,147 @@
+use crate::{
+    ode_solvers::{ode_solver::TimeSpan, ode_system::OdeSystem},
+    problems::benchmark_problem::{BenchmarkProblem, Reference},
+};
+
+/// Robertson's autocatalytic reaction, rate constants spanning eleven orders of
+/// magnitude:
+///
+/// ```text
+/// y1' = -k1 y1 + k3 y2 y3
+/// y2' =  k1 y1 - k3 y2 y3 - k2 y2^2
+/// y3' =  k2 y2^2
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Robertson {
+    pub k1: f64,
+    pub k2: f64,
+    pub k3: f64,
+}
+
+impl Default for Robertson {
+    fn default() -> Self {
+        Robertson {
+            k1: 0.04,
+            k2: 3e7,
+            k3: 1e4,
+        }
+    }
+}
+
+impl OdeSystem for Robertson {
+    fn dimension(&self) -> usize {
+        3
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let forward: f64 = self.k1 * y[0];
+        let backward: f64 = self.k3 * y[1] * y[2];
+        let production: f64 = self.k2 * y[1] * y[1];
+        dydt[0] = -forward + backward;
+        dydt[1] = forward - backward - production;
+        dydt[2] = production;
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        jac.copy_from_slice(&[
+            -self.k1,
+            self.k3 * y[2],
+            self.k3 * y[1],
+            self.k1,
+            -self.k3 * y[2] - 2.0 * self.k2 * y[1],
+            -self.k3 * y[1],
+            0.0,
+            2.0 * self.k2 * y[1],
+            0.0,
+        ]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// High Irradiance RESponse of a plant to light (Schäfer 1975), eight reactions: the
+/// HIRES problem of the test set for IVP solvers of Mazzia and Magherini.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hires;
+
+impl OdeSystem for Hires {
+    fn dimension(&self) -> usize {
+        8
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let reaction: f64 = 280.0 * y[5] * y[7];
+        dydt[0] = -1.71 * y[0] + 0.43 * y[1] + 8.32 * y[2] + 0.0007;
+        dydt[1] = 1.71 * y[0] - 8.75 * y[1];
+        dydt[2] = -10.03 * y[2] + 0.43 * y[3] + 0.035 * y[4];
+        dydt[3] = 8.32 * y[1] + 1.71 * y[2] - 1.12 * y[3];
+        dydt[4] = -1.745 * y[4] + 0.43 * y[5] + 0.43 * y[6];
+        dydt[5] = -reaction + 0.69 * y[3] + 1.71 * y[4] - 0.43 * y[5] + 0.69 * y[6];
+        dydt[6] = reaction - 1.81 * y[6];
+        dydt[7] = -reaction + 1.81 * y[6];
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        #[rustfmt::skip]
+        let linear: [f64; 64] = [
+            -1.71, 0.43, 8.32, 0.0, 0.0, 0.0, 0.0, 0.0,
+            1.71, -8.75, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, -10.03, 0.43, 0.035, 0.0, 0.0, 0.0,
+            0.0, 8.32, 1.71, -1.12, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, -1.745, 0.43, 0.43, 0.0,
+            0.0, 0.0, 0.0, 0.69, 1.71, -0.43, 0.69, 0.0,
+            0.0, 0.0, 0.0, 0.0, 0.0, 0.0, -1.81, 0.0,
+            0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.81, 0.0,
+        ];
+        jac.copy_from_slice(&linear);
+        // d(280 y6 y8) / dy6 and / dy8, in the rows of y6', y7' and y8'
+        for (row, sign) in [(5, -1.0), (6, 1.0), (7, -1.0)] {
+            jac[row * 8 + 5] += sign * 280.0 * y[7];
+            jac[row * 8 + 7] += sign * 280.0 * y[5];
+        }
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+pub fn robertson() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "robertson",
+        description: "Robertson chemical kinetics".to_string(),
+        stiff: true,
+        time_span: TimeSpan::new(0.0, 40.0),
+        initial_state: vec![1.0, 0.0, 0.0],
+        system: Box::new(Robertson::default()),
+        reference: Some(Reference::FinalState(ROBERTSON_FINAL_STATE.to_vec())),
+    }
+}
+
+pub fn hires() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "hires",
+        description: "HIRES plant physiology model".to_string(),
+        stiff: true,
+        time_span: TimeSpan::new(0.0, 321.8122),
+        initial_state: vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0057],
+        system: Box::new(Hires),
+        reference: Some(Reference::FinalState(HIRES_FINAL_STATE.to_vec())),
+    }
+}
+
+// BDF with tolerances of 1e-13, converged to about 1e-11
+const ROBERTSON_FINAL_STATE: [f64; 3] = [0.71582706873, 9.1855347650e-6, 0.28416374574];
+// Reference solution of the IVP test set
+const HIRES_FINAL_STATE: [f64; 8] = [
+    0.7371312573325668e-3,
+    0.1442485726316185e-3,
+    0.5888729740967575e-4,
+    0.1175651343283149e-2,
+    0.2386356198831331e-2,
+    0.6238968252742796e-2,
+    0.2849998395185769e-2,
+    0.2850001604814231e-2,
+];
@@ -0,0 +1,134 @@
+use crate::{
+    ode_solvers::{ode_solver::TimeSpan, ode_system::OdeSystem},
+    problems::benchmark_problem::{BenchmarkProblem, Reference},
+};
+use std::f64::consts::PI;
+
+const KEPLER_ECCENTRICITY: f64 = 0.5;
+const KEPLER_ORBITS: f64 = 10.0;
+const PENDULUM_AMPLITUDE: f64 = 2.0;
+const PENDULUM_PERIODS: f64 = 10.0;
+
+/// Two-body problem in the plane with unit gravitational parameter, state
+/// `[q1, q2, p1, p2]`: `q'' = -q / |q|^3`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Kepler;
+
+impl OdeSystem for Kepler {
+    fn dimension(&self) -> usize {
+        4
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let r3: f64 = (y[0] * y[0] + y[1] * y[1]).powf(1.5);
+        dydt[0] = y[2];
+        dydt[1] = y[3];
+        dydt[2] = -y[0] / r3;
+        dydt[3] = -y[1] / r3;
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        let r2: f64 = y[0] * y[0] + y[1] * y[1];
+        let r3: f64 = r2.powf(1.5);
+        let r5: f64 = r3 * r2;
+        let cross: f64 = 3.0 * y[0] * y[1] / r5;
+        jac.copy_from_slice(&[
+            0.0,
+            0.0,
+            1.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            1.0,
+            3.0 * y[0] * y[0] / r5 - 1.0 / r3,
+            cross,
+            0.0,
+            0.0,
+            cross,
+            3.0 * y[1] * y[1] / r5 - 1.0 / r3,
+            0.0,
+            0.0,
+        ]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Nonlinear pendulum `theta'' = -omega^2 * sin(theta)`, state `[theta, theta']`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pendulum {
+    /// Angular frequency of the small oscillations, `sqrt(g / length)`
+    pub omega: f64,
+}
+
+impl Pendulum {
+    /// Period of the oscillation of amplitude `amplitude` (below pi), `4 K(k) / omega`
+    /// with `k = sin(amplitude / 2)` and the complete elliptic integral `K`.
+    pub fn period(&self, amplitude: f64) -> f64 {
+        let k: f64 = (0.5 * amplitude).sin();
+        4.0 * complete_elliptic_k(k) / self.omega
+    }
+}
+
+impl OdeSystem for Pendulum {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = y[1];
+        dydt[1] = -self.omega * self.omega * y[0].sin();
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        jac.copy_from_slice(&[0.0, 1.0, -self.omega * self.omega * y[0].cos(), 0.0]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// `K(k) = pi / (2 agm(1, sqrt(1 - k^2)))`, by the arithmetic-geometric mean.
+fn complete_elliptic_k(k: f64) -> f64 {
+    let mut a: f64 = 1.0;
+    let mut g: f64 = (1.0 - k * k).sqrt();
+    while (a - g).abs() > f64::EPSILON * a {
+        (a, g) = (0.5 * (a + g), (a * g).sqrt());
+    }
+    PI / (2.0 * a)
+}
+
+pub fn kepler() -> BenchmarkProblem {
+    let e: f64 = KEPLER_ECCENTRICITY;
+    // Starts at the pericentre, the orbit has period 2 pi
+    let initial_state: Vec<f64> = vec![1.0 - e, 0.0, 0.0, ((1.0 + e) / (1.0 - e)).sqrt()];
+    BenchmarkProblem {
+        id: "kepler",
+        description: "Kepler two-body problem, 10 orbits of eccentricity 0.5".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, KEPLER_ORBITS * 2.0 * PI),
+        // Back at the pericentre after every orbit
+        reference: Some(Reference::FinalState(initial_state.clone())),
+        initial_state,
+        system: Box::new(Kepler),
+    }
+}
+
+pub fn pendulum() -> BenchmarkProblem {
+    let pendulum = Pendulum { omega: 1.0 };
+    let initial_state: Vec<f64> = vec![PENDULUM_AMPLITUDE, 0.0];
+    BenchmarkProblem {
+        id: "pendulum",
+        description: "pendulum released at 2 rad, 10 periods".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, PENDULUM_PERIODS * pendulum.period(PENDULUM_AMPLITUDE)),
+        // Back at the release point after every period
+        reference: Some(Reference::FinalState(initial_state.clone())),
+        initial_state,
+        system: Box::new(pendulum),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod benchmark_problem;
+pub mod chemical_kinetics;
+pub mod mechanics;
+pub mod oscillators;
+pub mod scalar_problems;
@@ -0,0 +1,167 @@
+use crate::{
+    ode_solvers::{ode_solver::TimeSpan, ode_system::OdeSystem},
+    problems::benchmark_problem::{BenchmarkProblem, Reference},
+};
+
+/// Van der Pol oscillator in the scaled form of the test set for IVP solvers,
+/// `x'' = ((1 - x^2) * x' - x) / epsilon`, as the system of `x` and `x'`. It is the
+/// classic `x'' = mu * (1 - x^2) * x' - x` with `mu = 1 / sqrt(epsilon)` in the time
+/// `t / mu`, stiff for small `epsilon`, with slow phases separated by fast jumps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VanDerPol {
+    pub epsilon: f64,
+}
+
+impl OdeSystem for VanDerPol {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = y[1];
+        dydt[1] = ((1.0 - y[0] * y[0]) * y[1] - y[0]) / self.epsilon;
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        jac.copy_from_slice(&[
+            0.0,
+            1.0,
+            (-2.0 * y[0] * y[1] - 1.0) / self.epsilon,
+            (1.0 - y[0] * y[0]) / self.epsilon,
+        ]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Lorenz system, chaotic for the classic `sigma = 10`, `rho = 28`, `beta = 8/3`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Lorenz {
+    pub sigma: f64,
+    pub rho: f64,
+    pub beta: f64,
+}
+
+impl OdeSystem for Lorenz {
+    fn dimension(&self) -> usize {
+        3
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = self.sigma * (y[1] - y[0]);
+        dydt[1] = y[0] * (self.rho - y[2]) - y[1];
+        dydt[2] = y[0] * y[1] - self.beta * y[2];
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        jac.copy_from_slice(&[
+            -self.sigma,
+            self.sigma,
+            0.0,
+            self.rho - y[2],
+            -1.0,
+            -y[0],
+            y[1],
+            y[0],
+            -self.beta,
+        ]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Brusselator reaction `x' = a + x^2 y - (b + 1) x`, `y' = b x - x^2 y`, which settles
+/// on a limit cycle for `b > 1 + a^2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Brusselator {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl OdeSystem for Brusselator {
+    fn dimension(&self) -> usize {
+        2
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        let x2y: f64 = y[0] * y[0] * y[1];
+        dydt[0] = self.a + x2y - (self.b + 1.0) * y[0];
+        dydt[1] = self.b * y[0] - x2y;
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        let two_xy: f64 = 2.0 * y[0] * y[1];
+        let x2: f64 = y[0] * y[0];
+        jac.copy_from_slice(&[two_xy - (self.b + 1.0), x2, self.b - two_xy, -x2]);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+pub fn van_der_pol() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "van-der-pol",
+        description: "Van der Pol oscillator with mu = 1".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 20.0),
+        initial_state: vec![2.0, 0.0],
+        system: Box::new(VanDerPol { epsilon: 1.0 }),
+        reference: Some(Reference::FinalState(VAN_DER_POL_FINAL_STATE.to_vec())),
+    }
+}
+
+pub fn van_der_pol_stiff() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "van-der-pol-stiff",
+        description: "Van der Pol oscillator with epsilon = 1e-6 (mu = 1000)".to_string(),
+        stiff: true,
+        time_span: TimeSpan::new(0.0, 2.0),
+        initial_state: vec![2.0, 0.0],
+        system: Box::new(VanDerPol { epsilon: 1e-6 }),
+        reference: Some(Reference::FinalState(
+            VAN_DER_POL_STIFF_FINAL_STATE.to_vec(),
+        )),
+    }
+}
+
+pub fn lorenz() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "lorenz",
+        description: "Lorenz system with sigma = 10, rho = 28, beta = 8/3".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 10.0),
+        initial_state: vec![1.0, 1.0, 1.0],
+        system: Box::new(Lorenz {
+            sigma: 10.0,
+            rho: 28.0,
+            beta: 8.0 / 3.0,
+        }),
+        reference: Some(Reference::FinalState(LORENZ_FINAL_STATE.to_vec())),
+    }
+}
+
+pub fn brusselator() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "brusselator",
+        description: "Brusselator with a = 1, b = 3".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 20.0),
+        initial_state: vec![1.5, 3.0],
+        system: Box::new(Brusselator { a: 1.0, b: 3.0 }),
+        reference: Some(Reference::FinalState(BRUSSELATOR_FINAL_STATE.to_vec())),
+    }
+}
+
+// Dormand-Prince with tolerances of 1e-15, converged to about 1e-12 (1e-11 for the
+// Lorenz system, whose errors grow exponentially)
+const VAN_DER_POL_FINAL_STATE: [f64; 2] = [2.00814976217495, -4.25088752731e-2];
+const LORENZ_FINAL_STATE: [f64; 3] = [-4.9026875411347, -3.7438729218044, 24.690858102788];
+const BRUSSELATOR_FINAL_STATE: [f64; 2] = [0.498637071268348, 4.59678034945201];
+// Reference solution of the test set for IVP solvers
+const VAN_DER_POL_STIFF_FINAL_STATE: [f64; 2] = [1.706167732170483, -0.8928097010247975];
@@ -0,0 +1,137 @@
+use crate::{
+    ode_solvers::{ode_solver::TimeSpan, ode_system::OdeSystem},
+    problems::benchmark_problem::{BenchmarkProblem, Reference},
+};
+
+const DECAY_RATE: f64 = 1.0;
+const LOGISTIC_RATE: f64 = 1.0;
+const LOGISTIC_CAPACITY: f64 = 1.0;
+const PROTHERO_ROBINSON_LAMBDA: f64 = -1e6;
+
+/// `y' = -rate * y`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExponentialDecay {
+    pub rate: f64,
+}
+
+impl OdeSystem for ExponentialDecay {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = -self.rate * y[0];
+    }
+
+    fn jacobian(&self, _t: f64, _y: &[f64], jac: &mut [f64]) {
+        jac[0] = -self.rate;
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Logistic growth `y' = rate * y * (1 - y / capacity)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Logistic {
+    pub rate: f64,
+    pub capacity: f64,
+}
+
+impl OdeSystem for Logistic {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = self.rate * y[0] * (1.0 - y[0] / self.capacity);
+    }
+
+    fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+        jac[0] = self.rate * (1.0 - 2.0 * y[0] / self.capacity);
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+/// Prothero-Robinson equation `y' = lambda * (y - sin t) + cos t`: every solution is
+/// drawn to `sin t` at the rate `lambda`, so a large negative `lambda` makes it stiff
+/// while the solution from `y(0) = 0` stays smooth. Shows the order reduction of
+/// implicit methods.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProtheroRobinson {
+    pub lambda: f64,
+}
+
+impl OdeSystem for ProtheroRobinson {
+    fn dimension(&self) -> usize {
+        1
+    }
+
+    fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        dydt[0] = self.lambda * (y[0] - t.sin()) + t.cos();
+    }
+
+    fn jacobian(&self, _t: f64, _y: &[f64], jac: &mut [f64]) {
+        jac[0] = self.lambda;
+    }
+
+    fn jacobian_rhs_evals(&self) -> usize {
+        0
+    }
+}
+
+// function: f(t,y) = -y
+pub fn exponential_decay() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "exponential-decay",
+        description: "y' = -y".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 1.0),
+        initial_state: vec![1.0],
+        system: Box::new(ExponentialDecay { rate: DECAY_RATE }),
+        reference: Some(Reference::Exact(|t0, y0, t| {
+            vec![y0[0] * (-DECAY_RATE * (t - t0)).exp()]
+        })),
+    }
+}
+
+// function: f(t,y) = y (1 - y)
+pub fn logistic() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "logistic",
+        description: "logistic growth y' = y (1 - y)".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 10.0),
+        initial_state: vec![0.1],
+        system: Box::new(Logistic {
+            rate: LOGISTIC_RATE,
+            capacity: LOGISTIC_CAPACITY,
+        }),
+        reference: Some(Reference::Exact(|t0, y0, t| {
+            let growth: f64 = (LOGISTIC_RATE * (t - t0)).exp();
+            vec![LOGISTIC_CAPACITY * y0[0] * growth / (LOGISTIC_CAPACITY + y0[0] * (growth - 1.0))]
+        })),
+    }
+}
+
+// function: f(t,y) = lambda (y - sin t) + cos t
+pub fn prothero_robinson() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "prothero-robinson",
+        description: "Prothero-Robinson equation with lambda = -1e6".to_string(),
+        stiff: true,
+        time_span: TimeSpan::new(0.0, 10.0),
+        initial_state: vec![0.0],
+        system: Box::new(ProtheroRobinson {
+            lambda: PROTHERO_ROBINSON_LAMBDA,
+        }),
+        // The distance to sin t decays exponentially
+        reference: Some(Reference::Exact(|t0, y0, t| {
+            vec![t.sin() + (y0[0] - t0.sin()) * (PROTHERO_ROBINSON_LAMBDA * (t - t0)).exp()]
+        })),
+    }
+}
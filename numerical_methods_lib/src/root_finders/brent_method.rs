@@ -0,0 +1,136 @@
+use crate::root_finders::newton_raphson_method::{RootResult, TerminationReason};
+
+// Method used from:
+// R. P. Brent, Algorithms for Minimization without Derivatives, Chapter 4 (zero)
+
+/// Brent's method for a scalar equation `f(x) = 0` on a bracket `[a, b]`.
+///
+/// Needs no derivative and cannot leave the bracket: it combines inverse quadratic
+/// interpolation and secant steps with bisection, so it converges at least as fast as
+/// bisection. `f(a)` and `f(b)` must have opposite signs (or one of them be zero),
+/// otherwise `InvalidBracket` is reported. Converged when the bracket is narrower than
+/// `tol` (plus a few ulps of the root) or the residual is exactly zero.
+pub fn brent_method_solve(
+    f: impl Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_iters: i32,
+) -> RootResult<f64> {
+    let (mut a, mut b) = (a, b);
+    let (mut f_a, mut f_b) = (f(a), f(b));
+
+    let stop = |root: f64, residual: f64, iterations: i32, reason: TerminationReason| RootResult {
+        root,
+        residual: residual.abs(),
+        iterations,
+        converged: matches!(
+            reason,
+            TerminationReason::ResidualTolerance | TerminationReason::StepTolerance
+        ),
+        reason,
+    };
+
+    if !(f_a.is_finite() && f_b.is_finite()) {
+        return stop(b, f_b, 0, TerminationReason::NonFinite);
+    }
+    if f_a == 0.0 {
+        return stop(a, f_a, 0, TerminationReason::ResidualTolerance);
+    }
+    if f_b == 0.0 {
+        return stop(b, f_b, 0, TerminationReason::ResidualTolerance);
+    }
+    if f_a.signum() == f_b.signum() {
+        return stop(b, f_b, 0, TerminationReason::InvalidBracket);
+    }
+
+    // b is the best estimate, a the previous one, c the other end of the bracket
+    let (mut c, mut f_c) = (a, f_a);
+    let mut d: f64 = b - a;
+    let mut e: f64 = d;
+
+    for iterations in 1..=max_iters {
+        if f_b.signum() == f_c.signum() {
+            c = a;
+            f_c = f_a;
+            d = b - a;
+            e = d;
+        }
+        if f_c.abs() < f_b.abs() {
+            a = b;
+            b = c;
+            c = a;
+            f_a = f_b;
+            f_b = f_c;
+            f_c = f_a;
+        }
+
+        let tol_b: f64 = 2.0 * f64::EPSILON * b.abs() + 0.5 * tol;
+        let m: f64 = 0.5 * (c - b);
+        if m.abs() <= tol_b {
+            return stop(b, f_b, iterations - 1, TerminationReason::StepTolerance);
+        }
+
+        if e.abs() >= tol_b && f_a.abs() > f_b.abs() {
+            // Interpolation: secant if only two distinct points, inverse quadratic otherwise
+            let s: f64 = f_b / f_a;
+            let (mut p, mut q): (f64, f64) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q: f64 = f_a / f_c;
+                let r: f64 = f_b / f_c;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            // Accept the interpolation only if it stays well inside the bracket
+            if 2.0 * p < (3.0 * m * q - (tol_b * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        f_a = f_b;
+        b += if d.abs() > tol_b {
+            d
+        } else {
+            tol_b.copysign(m)
+        };
+        f_b = f(b);
+
+        if !f_b.is_finite() {
+            return stop(b, f_b, iterations, TerminationReason::NonFinite);
+        }
+        if f_b == 0.0 {
+            return stop(b, f_b, iterations, TerminationReason::ResidualTolerance);
+        }
+    }
+
+    stop(b, f_b, max_iters, TerminationReason::MaxIterations)
+}
+
+/// Brent's method returning only the root, see [`brent_method_solve`].
+/// Returns `None` if `[a, b]` is not a bracket or the iteration does not converge.
+pub fn brent_method_root(
+    f: impl Fn(f64) -> f64,
+    a: f64,
+    b: f64,
+    tol: f64,
+    max_iters: i32,
+) -> Option<f64> {
+    let result = brent_method_solve(f, a, b, tol, max_iters);
+    result.converged.then_some(result.root)
+}
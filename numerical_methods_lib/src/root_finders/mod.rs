@@ -1 +1,2 @@
+pub mod brent_method;
 pub mod newton_raphson_method;
@@ -1,28 +1,317 @@
+use crate::{
+    differentiation::finite_difference::FiniteDifferenceJacobian,
+    linear_solvers::lu_decomposition::LuDecomposition,
+};
+
 // Method used from:
 // https://de.mathworks.com/matlabcentral/answers/1716860-using-implicit-euler-method-with-newton-raphson-method
 
+/// Why the root finding iteration stopped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TerminationReason {
+    /// The residual dropped below the residual tolerance.
+    ResidualTolerance,
+    /// The Newton step became smaller than the step tolerance.
+    StepTolerance,
+    /// The iteration limit was reached.
+    MaxIterations,
+    /// The derivative (Jacobian) is zero (singular), no Newton direction exists.
+    ZeroDerivative,
+    /// The residual became NaN or infinite.
+    NonFinite,
+    /// Damping could not find a step that reduces the residual.
+    LineSearchFailed,
+    /// The steps of the frozen Jacobian (chord) iteration stopped contracting.
+    Diverged,
+    /// The function has the same sign at both ends of the bracket.
+    InvalidBracket,
+}
+
+/// Outcome of a root finding run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootResult<T> {
+    /// Last iterate (the root if `converged`)
+    pub root: T,
+    /// Max norm of the residual at `root`
+    pub residual: f64,
+    pub iterations: i32,
+    pub converged: bool,
+    pub reason: TerminationReason,
+}
+
+/// Stopping criteria and damping settings of the Newton iteration.
+#[derive(Clone, Copy, Debug)]
+pub struct NewtonOptions {
+    /// Converged when the max norm of the residual is below this value
+    pub residual_tol: f64,
+    /// Converged when the max norm of the step is below `step_tol * (1 + |x|)`
+    pub step_tol: f64,
+    pub max_iters: i32,
+    /// Halve the step until the residual decreases
+    pub line_search: bool,
+    /// Smallest damping factor tried by the line search
+    pub min_damping: f64,
+}
+
+impl NewtonOptions {
+    pub fn new(tol: f64, max_iters: i32) -> Self {
+        NewtonOptions {
+            residual_tol: tol,
+            step_tol: tol,
+            max_iters,
+            line_search: true,
+            min_damping: 1.0 / 1024.0,
+        }
+    }
+}
+
+fn max_norm(values: &[f64]) -> f64 {
+    values.iter().fold(0.0_f64, |acc, v| acc.max(v.abs()))
+}
+
 // Why we are using the f: impl Fn(..) -> .. instead of f: fn(..) -> ..
 // Check https://www.reddit.com/r/learnrust/comments/xvxpy2/is_there_a_workaround_for_variable_capturing_in/
 pub fn newton_raphson_method_root(
     f: impl Fn(f64) -> f64,
     f_dash: impl Fn(f64) -> f64,
-    mut x_initial: f64,
+    x_initial: f64,
     tol: f64,
     max_iters: i32,
 ) -> f64 {
-    let mut num_iters = 0;
-    let f_abs = f(x_initial).abs();
+    newton_raphson_solve(f, f_dash, x_initial, &NewtonOptions::new(tol, max_iters)).root
+}
+
+/// Newton Raphson method for a scalar equation `f(x) = 0`, reporting diagnostics.
+pub fn newton_raphson_solve(
+    f: impl Fn(f64) -> f64,
+    f_dash: impl Fn(f64) -> f64,
+    x_initial: f64,
+    options: &NewtonOptions,
+) -> RootResult<f64> {
+    let result = newton_raphson_system_solve(
+        |x: &[f64], residual: &mut [f64]| residual[0] = f(x[0]),
+        |x: &[f64], jac: &mut [f64]| jac[0] = f_dash(x[0]),
+        vec![x_initial],
+        options,
+    );
+
+    RootResult {
+        root: result.root[0],
+        residual: result.residual,
+        iterations: result.iterations,
+        converged: result.converged,
+        reason: result.reason,
+    }
+}
+
+/// Newton Raphson method for a system of equations `f(x) = 0`.
+///
+/// `f` writes the residual into its second argument and `f_dash` writes the
+/// Jacobian (row-major, N x N). Each iteration solves `J dx = -f(x)`.
+/// Returns `None` if the iteration does not converge, see [`newton_raphson_system_solve`]
+/// for the reason.
+pub fn newton_raphson_system_root(
+    f: impl Fn(&[f64], &mut [f64]),
+    f_dash: impl Fn(&[f64], &mut [f64]),
+    x_initial: Vec<f64>,
+    tol: f64,
+    max_iters: i32,
+) -> Option<Vec<f64>> {
+    let result =
+        newton_raphson_system_solve(f, f_dash, x_initial, &NewtonOptions::new(tol, max_iters));
+    result.converged.then_some(result.root)
+}
 
-    while f_abs > tol {
-        let x_temp = x_initial - (f(x_initial) / f_dash(x_initial));
-        x_initial = x_temp;
-        num_iters += 1;
+/// Newton Raphson method for a system of equations `f(x) = 0`, reporting diagnostics.
+///
+/// Stops on a small residual or a small step (converged), or on the iteration limit,
+/// a singular Jacobian, a non-finite residual or a failed line search (not converged).
+pub fn newton_raphson_system_solve(
+    f: impl Fn(&[f64], &mut [f64]),
+    f_dash: impl Fn(&[f64], &mut [f64]),
+    x_initial: Vec<f64>,
+    options: &NewtonOptions,
+) -> RootResult<Vec<f64>> {
+    let dim = x_initial.len();
+    let mut x = x_initial;
+    let mut residual = vec![0.0; dim];
+    let mut jacobian = vec![0.0; dim * dim];
+    let mut x_trial = vec![0.0; dim];
+    let mut residual_trial = vec![0.0; dim];
+    let mut iterations = 0;
 
-        if num_iters > max_iters {
-            // println!("\n Max iterations {:?} reached", num_iters);
-            break;
+    f(&x, &mut residual);
+    let mut residual_norm = max_norm(&residual);
+
+    let stop =
+        |root: Vec<f64>, residual: f64, iterations: i32, reason: TerminationReason| RootResult {
+            root,
+            residual,
+            iterations,
+            converged: matches!(
+                reason,
+                TerminationReason::ResidualTolerance | TerminationReason::StepTolerance
+            ),
+            reason,
+        };
+
+    loop {
+        if !residual_norm.is_finite() {
+            return stop(x, residual_norm, iterations, TerminationReason::NonFinite);
+        }
+        if residual_norm <= options.residual_tol {
+            return stop(
+                x,
+                residual_norm,
+                iterations,
+                TerminationReason::ResidualTolerance,
+            );
+        }
+        if iterations >= options.max_iters {
+            return stop(
+                x,
+                residual_norm,
+                iterations,
+                TerminationReason::MaxIterations,
+            );
+        }
+
+        f_dash(&x, &mut jacobian);
+        let lu = match LuDecomposition::new(&jacobian, dim) {
+            Some(lu) => lu,
+            None => {
+                return stop(
+                    x,
+                    residual_norm,
+                    iterations,
+                    TerminationReason::ZeroDerivative,
+                )
+            }
+        };
+        let mut step: Vec<f64> = residual.iter().map(|r| -r).collect();
+        lu.solve(&mut step);
+
+        // Backtracking: halve the step until the residual decreases sufficiently
+        let mut damping = 1.0;
+        loop {
+            for j in 0..dim {
+                x_trial[j] = x[j] + damping * step[j];
+            }
+            f(&x_trial, &mut residual_trial);
+            let trial_norm = max_norm(&residual_trial);
+            if !options.line_search || trial_norm <= (1.0 - 1e-4 * damping) * residual_norm {
+                break;
+            }
+            damping *= 0.5;
+            if damping < options.min_damping {
+                return stop(
+                    x,
+                    residual_norm,
+                    iterations,
+                    TerminationReason::LineSearchFailed,
+                );
+            }
+        }
+
+        std::mem::swap(&mut x, &mut x_trial);
+        std::mem::swap(&mut residual, &mut residual_trial);
+        residual_norm = max_norm(&residual);
+        iterations += 1;
+
+        // The undamped step: a step cut short by the line search says nothing about
+        // the distance to the root
+        if residual_norm.is_finite() && max_norm(&step) <= options.step_tol * (1.0 + max_norm(&x)) {
+            return stop(
+                x,
+                residual_norm,
+                iterations,
+                TerminationReason::StepTolerance,
+            );
         }
     }
+}
+
+/// Newton Raphson method for a system of equations `f(x) = 0` when no Jacobian is
+/// available, the Jacobian is approximated by `jacobian` every iteration.
+pub fn newton_raphson_finite_difference_solve(
+    f: impl Fn(&[f64], &mut [f64]),
+    jacobian: &FiniteDifferenceJacobian,
+    x_initial: Vec<f64>,
+    options: &NewtonOptions,
+) -> RootResult<Vec<f64>> {
+    newton_raphson_system_solve(
+        &f,
+        |x: &[f64], jac: &mut [f64]| {
+            jacobian.jacobian(&f, x, jac);
+        },
+        x_initial,
+        options,
+    )
+}
 
-    x_initial
+/// Simplified (chord) Newton method for a system of equations `f(x) = 0`.
+///
+/// The Jacobian is factorised once by the caller and reused for every iteration, so
+/// each iteration costs one evaluation of `f` and one triangular solve. No damping is
+/// applied; the iteration stops with `Diverged` as soon as a step is not clearly smaller
+/// than the previous one, which usually means the Jacobian is out of date.
+pub fn newton_raphson_chord_solve(
+    f: impl Fn(&[f64], &mut [f64]),
+    jacobian: &LuDecomposition,
+    x_initial: Vec<f64>,
+    options: &NewtonOptions,
+) -> RootResult<Vec<f64>> {
+    // Contraction factor above which the iteration is considered divergent
+    const MAX_RATE: f64 = 0.9;
+
+    let dim = x_initial.len();
+    let mut x = x_initial;
+    let mut residual = vec![0.0; dim];
+    let mut step = vec![0.0; dim];
+    let mut previous_step_norm = f64::INFINITY;
+    let mut step_converged = false;
+    let mut iterations = 0;
+
+    loop {
+        f(&x, &mut residual);
+        let residual_norm = max_norm(&residual);
+
+        let reason = if !residual_norm.is_finite() {
+            TerminationReason::NonFinite
+        } else if residual_norm <= options.residual_tol {
+            TerminationReason::ResidualTolerance
+        } else if step_converged {
+            TerminationReason::StepTolerance
+        } else if iterations >= options.max_iters {
+            TerminationReason::MaxIterations
+        } else {
+            for j in 0..dim {
+                step[j] = -residual[j];
+            }
+            jacobian.solve(&mut step);
+            let step_norm = max_norm(&step);
+            if step_norm.is_nan() || step_norm > MAX_RATE * previous_step_norm {
+                TerminationReason::Diverged
+            } else {
+                for j in 0..dim {
+                    x[j] += step[j];
+                }
+                iterations += 1;
+                previous_step_norm = step_norm;
+                step_converged = step_norm <= options.step_tol * (1.0 + max_norm(&x));
+                continue;
+            }
+        };
+
+        return RootResult {
+            root: x,
+            residual: residual_norm,
+            iterations,
+            converged: matches!(
+                reason,
+                TerminationReason::ResidualTolerance | TerminationReason::StepTolerance
+            ),
+            reason,
+        };
+    }
 }
@@ -0,0 +1,229 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use numerical_methods_lib::comparison::method_comparison::{DEFAULT_NUM_STEPS, DEFAULT_TOLERANCES};
+use std::path::PathBuf;
+
+/// Solves ordinary differential equations with the methods of numerical_methods_lib.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Solves a problem with one method and writes the solution and its plot
+    Solve {
+        /// Method to use, see the `methods` subcommand
+        #[arg(short, long, default_value = "rk4")]
+        method: String,
+        /// Prints every time point of the solution
+        #[arg(long)]
+        print: bool,
+        #[command(flatten)]
+        problem: ProblemArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Solves a problem with several methods and overlays the solutions
+    Compare {
+        /// Methods to compare, all of them when not given
+        #[arg(short, long, value_delimiter = ',')]
+        methods: Vec<String>,
+        #[command(flatten)]
+        problem: ProblemArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Measures the error at the final time while halving the step size
+    Convergence {
+        /// Methods to measure, all fixed step methods when not given
+        #[arg(short, long, value_delimiter = ',')]
+        methods: Vec<String>,
+        /// Number of step sizes, starting from the step size of the problem
+        #[arg(long, default_value_t = 6)]
+        levels: u32,
+        #[command(flatten)]
+        problem: ProblemArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Measures error against work over a range of tolerances and step sizes, and writes
+    /// the work-precision table and diagram
+    Report {
+        /// Methods to compare, all of them (the implicit ones for stiff problems) when not
+        /// given
+        #[arg(short, long, value_delimiter = ',')]
+        methods: Vec<String>,
+        /// Relative tolerances of the adaptive methods, comma separated
+        #[arg(long, value_delimiter = ',', default_values_t = DEFAULT_TOLERANCES)]
+        tolerances: Vec<f64>,
+        /// Numbers of steps of the fixed step methods, comma separated
+        #[arg(long, value_delimiter = ',', default_values_t = DEFAULT_NUM_STEPS)]
+        steps: Vec<usize>,
+        /// Format of the report
+        #[arg(long, value_enum, default_value_t = ReportFileFormat::Md)]
+        report_format: ReportFileFormat,
+        #[command(flatten)]
+        problem: ProblemArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+    /// Runs the list of runs of a problem description file (.toml, .yaml or .yml)
+    Run {
+        /// Problem description file
+        config: PathBuf,
+    },
+    /// Lists the available methods
+    Methods,
+    /// Lists the built-in problems
+    Problems,
+}
+
+/// The problem to solve and the solver parameters. Unset values take the defaults of the
+/// problem, or of `OdeSolverParams`.
+#[derive(Clone, Debug, Args)]
+pub struct ProblemArgs {
+    /// Built-in problem, see the `problems` subcommand
+    #[arg(short, long, default_value = "quadratic-decay", conflicts_with = "rhs")]
+    pub problem: String,
+    /// Equation of the problem instead of a built-in one, repeated for a system (e.g.
+    /// `--rhs "dx = y" --rhs "dy = -x"`); a single right hand side is `y' = rhs`
+    #[arg(long, value_name = "EQUATION", allow_hyphen_values = true)]
+    pub rhs: Vec<String>,
+    /// Parameter of the equations, repeated for several (e.g. `--param mu=2`)
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = parse_parameter)]
+    pub parameters: Vec<(String, f64)>,
+    /// Initial time
+    #[arg(long, allow_hyphen_values = true)]
+    pub t0: Option<f64>,
+    /// Final time
+    #[arg(long, allow_hyphen_values = true)]
+    pub tf: Option<f64>,
+    /// Step size, the initial step of adaptive methods
+    #[arg(long = "step", short = 's')]
+    pub time_step: Option<f64>,
+    /// Newton tolerance, and absolute tolerance of adaptive methods
+    #[arg(long = "tol")]
+    pub tolerance: Option<f64>,
+    /// Relative tolerance of adaptive methods
+    #[arg(long = "rtol")]
+    pub rel_tolerance: Option<f64>,
+    /// Maximum number of Newton iterations of implicit methods
+    #[arg(long)]
+    pub max_iters: Option<i32>,
+    /// Initial state, comma separated (e.g. `--y0 2,0`)
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    pub y0: Vec<f64>,
+}
+
+/// Where and in which formats the results are written.
+#[derive(Clone, Debug, Args)]
+pub struct OutputArgs {
+    /// Directory of the solution files, plots go to its `images` subdirectory
+    #[arg(short, long, default_value = "solver_results")]
+    pub output_dir: PathBuf,
+    /// Format of the solution files
+    #[arg(long, value_enum, default_value_t = SolutionFormat::Csv)]
+    pub format: SolutionFormat,
+    /// Format of the plots
+    #[arg(long, value_enum, default_value_t = ImageFormat::Html)]
+    pub plot_format: ImageFormat,
+    /// Writes no solution files
+    #[arg(long)]
+    pub no_files: bool,
+    /// Draws no plots
+    #[arg(long)]
+    pub no_plot: bool,
+}
+
+fn parse_parameter(text: &str) -> Result<(String, f64), String> {
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=VALUE, found `{}`", text))?;
+    let value: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("`{}` is not a number", value.trim()))?;
+    Ok((name.trim().to_string(), value))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SolutionFormat {
+    Csv,
+    Jsonl,
+    Bin,
+}
+
+impl SolutionFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            SolutionFormat::Csv => "csv",
+            SolutionFormat::Jsonl => "jsonl",
+            SolutionFormat::Bin => "bin",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ReportFileFormat {
+    Md,
+    Csv,
+    Html,
+}
+
+impl ReportFileFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ReportFileFormat::Md => "md",
+            ReportFileFormat::Csv => "csv",
+            ReportFileFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ImageFormat {
+    Html,
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Html => "html",
+            ImageFormat::Svg => "svg",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl OutputArgs {
+    /// Path of the solution file of the run named `name`.
+    pub fn solution_path(&self, name: &str) -> PathBuf {
+        self.output_dir.join(format!(
+            "{}_ode_solver.{}",
+            name.replace('-', "_"),
+            self.format.extension()
+        ))
+    }
+
+    /// Path of the work-precision report of the problem `problem`.
+    pub fn report_path(&self, problem: &str, format: ReportFileFormat) -> PathBuf {
+        self.output_dir.join(format!(
+            "work_precision_{}.{}",
+            problem.replace('-', "_"),
+            format.extension()
+        ))
+    }
+
+    /// Path of the plot named `name`.
+    pub fn plot_path(&self, name: &str) -> PathBuf {
+        self.output_dir.join("images").join(format!(
+            "{}.{}",
+            name.replace('-', "_"),
+            self.plot_format.extension()
+        ))
+    }
+}
@@ -0,0 +1,409 @@
+use crate::{
+    cli::{OutputArgs, ProblemArgs, ReportFileFormat},
+    config::{load_config, Config, RunConfig},
+    problems::{equations_problem, problem, PROBLEM_IDS},
+};
+use numerical_methods_lib::{
+    comparison::{
+        method_comparison::{ComparisonSettings, MethodComparison, WorkMeasure},
+        report::{to_markdown, write_report},
+    },
+    ode_solvers::{
+        convergence::{fit_order, halving_step_sizes},
+        method_registry::{build_solver, find_method, MethodInfo, METHODS},
+        ode_error::OdeError,
+        ode_solution::OdeSolution,
+        ode_solver::{OdeSolver, OdeSolverParams, TimeSpan},
+    },
+    output::solution_file::{write_solution_file, RunMetadata},
+    plotting::{convergence_plot::ConvergencePlot, figure::PlotStyle, solution_plot::SolutionPlot},
+    problems::benchmark_problem::{BenchmarkProblem, Reference},
+};
+use std::{error::Error, path::Path, time::Instant};
+
+type CommandResult = Result<(), Box<dyn Error>>;
+
+// Tolerances of the reference solve of problems without a reference for the chosen
+// time span and initial state
+const REFERENCE_TOLERANCE: f64 = 1e-12;
+const REFERENCE_REL_TOLERANCE: f64 = 1e-12;
+
+/// A problem with the values of the command line applied.
+struct Setup {
+    problem: BenchmarkProblem,
+    y0: Vec<f64>,
+    params: OdeSolverParams,
+}
+
+impl Setup {
+    fn new(args: &ProblemArgs) -> Result<Self, Box<dyn Error>> {
+        let problem: BenchmarkProblem = if args.rhs.is_empty() {
+            problem(&args.problem).ok_or_else(|| {
+                format!(
+                    "unknown problem `{}` (expected one of: {})",
+                    args.problem,
+                    PROBLEM_IDS.join(", ")
+                )
+            })?
+        } else {
+            equations_problem(&args.rhs, &args.parameters)?
+        };
+        if args.y0.is_empty() && problem.initial_state.is_empty() {
+            return Err(
+                "the equations given with --rhs need an initial state, set with --y0".into(),
+            );
+        }
+        let defaults = OdeSolverParams::default();
+        let params = OdeSolverParams {
+            time_span: TimeSpan::new(
+                args.t0.unwrap_or(problem.time_span.t0),
+                args.tf.unwrap_or(problem.time_span.tf),
+            ),
+            time_step: args.time_step.unwrap_or(defaults.time_step),
+            tolerance: args.tolerance.unwrap_or(defaults.tolerance),
+            rel_tolerance: args.rel_tolerance.unwrap_or(defaults.rel_tolerance),
+            max_iters: args.max_iters.unwrap_or(defaults.max_iters),
+            ..defaults
+        };
+        let y0: Vec<f64> = if args.y0.is_empty() {
+            problem.initial_state.clone()
+        } else {
+            args.y0.clone()
+        };
+        Ok(Setup {
+            problem,
+            y0,
+            params,
+        })
+    }
+
+    fn solve(
+        &self,
+        method: &MethodInfo,
+        params: &OdeSolverParams,
+    ) -> Result<OdeSolution, OdeError> {
+        let solver = OdeSolver::new(method.name, self.problem.system.as_ref(), params);
+        build_solver(method.id, solver)?.solve(&self.y0)
+    }
+
+    /// Exact solution at the final time, the reference of the problem when the defaults
+    /// are kept, or else a tight approximation by Dormand-Prince (BDF for stiff problems).
+    fn reference_final_state(&self) -> Result<Vec<f64>, OdeError> {
+        let tf: f64 = self.params.time_span.tf;
+        if let Some(exact) = self.problem.exact() {
+            return Ok(exact(self.params.time_span.t0, &self.y0, tf));
+        }
+        if self.params.time_span == self.problem.time_span && self.y0 == self.problem.initial_state
+        {
+            if let Some(reference) = self.problem.reference_final_state() {
+                return Ok(reference);
+            }
+        }
+        let method: &str = if self.problem.stiff {
+            "bdf"
+        } else {
+            "dormand-prince"
+        };
+        let params = OdeSolverParams {
+            time_span: self.params.time_span,
+            tolerance: REFERENCE_TOLERANCE,
+            rel_tolerance: REFERENCE_REL_TOLERANCE,
+            ..Default::default()
+        };
+        let reference: OdeSolution = self.solve(lookup_method(method)?, &params)?;
+        Ok(reference.states.last().cloned().unwrap_or_default())
+    }
+
+    /// The problem with the time span and initial state of the command line, and a
+    /// reference for them.
+    fn into_problem(self) -> Result<BenchmarkProblem, OdeError> {
+        let reference: Reference = match self.problem.reference {
+            Some(Reference::Exact(exact)) => Reference::Exact(exact),
+            _ => Reference::FinalState(self.reference_final_state()?),
+        };
+        Ok(BenchmarkProblem {
+            time_span: self.params.time_span,
+            initial_state: self.y0,
+            reference: Some(reference),
+            ..self.problem
+        })
+    }
+
+    fn title(&self) -> String {
+        format!("{}: {}", self.problem.id, self.problem.description)
+    }
+}
+
+fn lookup_method(name: &str) -> Result<&'static MethodInfo, OdeError> {
+    find_method(name).ok_or_else(|| OdeError::InvalidParameter {
+        name: "method",
+        reason: format!(
+            "`{}` is not a known method, see the `methods` subcommand",
+            name
+        ),
+    })
+}
+
+/// Methods named on the command line, or the ones kept by `default` when none are.
+fn selected_methods(
+    names: &[String],
+    default: impl Fn(&MethodInfo) -> bool,
+) -> Result<Vec<&'static MethodInfo>, OdeError> {
+    if names.is_empty() {
+        Ok(METHODS.iter().filter(|method| default(method)).collect())
+    } else {
+        names.iter().map(|name| lookup_method(name)).collect()
+    }
+}
+
+/// Largest absolute difference between the components of two states.
+fn max_error(state: &[f64], reference: &[f64]) -> f64 {
+    state
+        .iter()
+        .zip(reference)
+        .map(|(value, reference)| (value - reference).abs())
+        .fold(0.0, f64::max)
+}
+
+fn write_results(
+    output: &OutputArgs,
+    name: &str,
+    method: &MethodInfo,
+    params: &OdeSolverParams,
+    solution: &OdeSolution,
+) -> CommandResult {
+    if output.no_files {
+        return Ok(());
+    }
+    let file_path = output.solution_path(name);
+    write_solution_file(&file_path, solution, &RunMetadata::new(method.name, params))?;
+    println!("Solution written to {}", file_path.display());
+    Ok(())
+}
+
+pub fn solve(
+    method: &str,
+    print: bool,
+    problem: &ProblemArgs,
+    output: &OutputArgs,
+) -> CommandResult {
+    let setup = Setup::new(problem)?;
+    let method: &MethodInfo = lookup_method(method)?;
+    let solution: OdeSolution = setup.solve(method, &setup.params)?;
+
+    if print {
+        for (time, value) in solution.times.iter().zip(&solution.states) {
+            println!("time: {:.3} \t value: {:.3?}", time, value)
+        }
+    }
+    if let Some((tf, state)) = solution.last() {
+        println!("{} reached t = {} with y = {:?}", method.name, tf, state);
+    }
+    println!(
+        "Accepted steps: {}, rejected steps: {}, RHS evaluations: {}, Jacobian evaluations: {}",
+        solution.stats.accepted_steps,
+        solution.stats.rejected_steps,
+        solution.stats.num_rhs_evals,
+        solution.stats.num_jacobian_evals
+    );
+
+    write_results(output, method.id, method, &setup.params, &solution)?;
+    if !output.no_plot {
+        let plot_path = output.plot_path(method.id);
+        SolutionPlot::new(&setup.title())
+            .with_solution(method.name, &solution)
+            .save(&plot_path)?;
+        println!("Plot written to {}", plot_path.display());
+    }
+    Ok(())
+}
+
+pub fn compare(methods: &[String], problem: &ProblemArgs, output: &OutputArgs) -> CommandResult {
+    let setup = Setup::new(problem)?;
+    let runs: Vec<RunConfig> = selected_methods(methods, |_| true)?
+        .into_iter()
+        .map(|method| RunConfig {
+            name: method.id.to_string(),
+            method,
+            params: setup.params.clone(),
+        })
+        .collect();
+    compare_runs(&setup, &runs, output)
+}
+
+/// Runs every entry of a problem description file, see [`load_config`].
+pub fn run(config_path: &Path) -> CommandResult {
+    let config: Config = load_config(config_path)?;
+    let setup = Setup::new(&config.problem)?;
+    compare_runs(&setup, &config.runs, &config.output)
+}
+
+/// Solves the problem of `setup` once per run, prints the statistics of the runs side by
+/// side and overlays their solutions.
+fn compare_runs(setup: &Setup, runs: &[RunConfig], output: &OutputArgs) -> CommandResult {
+    let reference: Vec<f64> = setup.reference_final_state()?;
+    let mut comparison = SolutionPlot::new(&setup.title()).with_style(PlotStyle::Lines);
+
+    println!(
+        "{:<18} {:>8} {:>8} {:>10} {:>10} {:>12} {:>10}",
+        "run", "steps", "rejected", "RHS evals", "Jac evals", "error at tf", "time [ms]"
+    );
+    for run in runs {
+        let start = Instant::now();
+        let result: Result<OdeSolution, OdeError> = setup.solve(run.method, &run.params);
+        let elapsed_ms: f64 = start.elapsed().as_secs_f64() * 1e3;
+        let solution: OdeSolution = match result {
+            Ok(solution) => solution,
+            Err(err) => {
+                println!("{:<18} failed: {}", run.name, err);
+                continue;
+            }
+        };
+        let error: f64 = solution
+            .states
+            .last()
+            .map_or(f64::NAN, |state| max_error(state, &reference));
+        println!(
+            "{:<18} {:>8} {:>8} {:>10} {:>10} {:>12.3e} {:>10.3}",
+            run.name,
+            solution.stats.accepted_steps,
+            solution.stats.rejected_steps,
+            solution.stats.num_rhs_evals,
+            solution.stats.num_jacobian_evals,
+            error,
+            elapsed_ms
+        );
+        write_results(output, &run.name, run.method, &run.params, &solution)?;
+        comparison.add_solution(&run.name, &solution);
+    }
+
+    if !output.no_plot {
+        let plot_path = output.plot_path("comparison");
+        comparison.save(&plot_path)?;
+        println!("Comparison plot written to {}", plot_path.display());
+    }
+    Ok(())
+}
+
+pub fn convergence(
+    methods: &[String],
+    levels: u32,
+    problem: &ProblemArgs,
+    output: &OutputArgs,
+) -> CommandResult {
+    let setup = Setup::new(problem)?;
+    let methods: Vec<&MethodInfo> = selected_methods(methods, |method| !method.adaptive)?;
+    let reference: Vec<f64> = setup.reference_final_state()?;
+    let step_sizes: Vec<f64> = halving_step_sizes(setup.params.time_step, levels as usize);
+    let mut plot = ConvergencePlot::new(&setup.title());
+
+    for method in methods {
+        if method.adaptive {
+            println!(
+                "{}: skipped, the method chooses its own step size",
+                method.id
+            );
+            continue;
+        }
+        println!("{} (order {})", method.id, method.order);
+        println!("{:>12} {:>12} {:>10}", "step", "error at tf", "order");
+        let mut errors: Vec<f64> = Vec::with_capacity(step_sizes.len());
+        for &time_step in &step_sizes {
+            let params = OdeSolverParams {
+                time_step,
+                ..setup.params.clone()
+            };
+            let solution: OdeSolution = setup.solve(method, &params)?;
+            let error: f64 = max_error(solution.states.last().unwrap(), &reference);
+            // Observed order from the error reduction of the last halving
+            let order: String = match errors.last() {
+                Some(&previous) if previous > 0.0 && error > 0.0 => {
+                    format!("{:.2}", (previous / error).log2())
+                }
+                _ => "-".to_string(),
+            };
+            println!("{:>12.3e} {:>12.3e} {:>10}", time_step, error, order);
+            errors.push(error);
+        }
+        println!("fitted order: {:.2}", fit_order(&step_sizes, &errors));
+        plot = plot.with_series(method.name, &step_sizes, &errors);
+    }
+
+    if !output.no_plot {
+        let plot_path = output.plot_path("convergence");
+        plot.save(&plot_path)?;
+        println!("Convergence plot written to {}", plot_path.display());
+    }
+    Ok(())
+}
+
+pub fn report(
+    methods: &[String],
+    settings: &ComparisonSettings,
+    report_format: ReportFileFormat,
+    problem: &ProblemArgs,
+    output: &OutputArgs,
+) -> CommandResult {
+    let setup = Setup::new(problem)?;
+    let stiff: bool = setup.problem.stiff;
+    // Explicit methods take too many steps on stiff problems to be compared by default
+    let methods: Vec<&MethodInfo> = selected_methods(methods, |method| !stiff || method.implicit)?;
+    let settings = ComparisonSettings {
+        newton_tolerance: setup.params.tolerance,
+        max_iters: setup.params.max_iters,
+        ..settings.clone()
+    };
+    let problem: BenchmarkProblem = setup.into_problem()?;
+    let comparison = MethodComparison::run(&problem, &methods, &settings)?;
+    print!("{}", to_markdown(&comparison));
+
+    if !output.no_files {
+        let report_path = output.report_path(problem.id, report_format);
+        write_report(&comparison, &report_path)?;
+        println!("Report written to {}", report_path.display());
+    }
+    if !output.no_plot {
+        let plot_path = output.plot_path(&format!("work_precision_{}", problem.id));
+        comparison
+            .work_precision_plot(WorkMeasure::RhsEvaluations)
+            .save(&plot_path)?;
+        println!("Work-precision plot written to {}", plot_path.display());
+    }
+    Ok(())
+}
+
+pub fn list_methods() {
+    println!(
+        "{:<18} {:>5} {:>8} {:>8}  description",
+        "method", "order", "adaptive", "implicit"
+    );
+    for method in METHODS.iter() {
+        let flag = |set: bool| if set { "yes" } else { "no" };
+        println!(
+            "{:<18} {:>5} {:>8} {:>8}  {}",
+            method.id,
+            method.order,
+            flag(method.adaptive),
+            flag(method.implicit),
+            method.description
+        );
+    }
+}
+
+pub fn list_problems() {
+    println!(
+        "{:<18} {:>9} {:>5}  description",
+        "problem", "dimension", "stiff"
+    );
+    for id in PROBLEM_IDS {
+        if let Some(problem) = problem(id) {
+            println!(
+                "{:<18} {:>9} {:>5}  {}",
+                problem.id,
+                problem.system.dimension(),
+                if problem.stiff { "yes" } else { "no" },
+                problem.description
+            );
+        }
+    }
+}
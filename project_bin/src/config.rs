@@ -0,0 +1,638 @@
+use crate::{
+    cli::{ImageFormat, OutputArgs, ProblemArgs, SolutionFormat},
+    problems::{equations_problem, problem, PROBLEM_IDS},
+};
+use numerical_methods_lib::{
+    expressions::expression_error::ExpressionError,
+    ode_solvers::{
+        method_registry::{find_method, MethodInfo},
+        ode_error::OdeError,
+        ode_solver::{OdeSolverParams, TimeSpan},
+    },
+    problems::benchmark_problem::BenchmarkProblem,
+};
+use std::{fmt, path::Path};
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// A problem description file: the problem, the solver parameters shared by the runs,
+/// the output settings and the list of runs.
+///
+/// ```toml
+/// problem = "van-der-pol"
+/// tf = 10.0
+/// y0 = [2.0, 0.0]
+/// rel_tolerance = 1e-8
+///
+/// [output]
+/// directory = "solver_results/van_der_pol"
+/// plot_format = "svg"
+///
+/// [[runs]]
+/// method = "dormand-prince"
+///
+/// [[runs]]
+/// name = "bdf-loose"
+/// method = "bdf"
+/// rel_tolerance = 1e-4
+/// ```
+///
+/// A `method` at the top level is the method of the runs that do not name one, and
+/// without `runs` the file describes a single run of that method:
+///
+/// ```toml
+/// problem = "robertson"
+/// method = "bdf"
+/// ```
+///
+/// Instead of `problem`, `rhs` gives the equations, a string or a list of strings, with
+/// the values of their `parameters` in a table:
+///
+/// ```toml
+/// rhs = ["dx = y", "dy = mu*(1 - x^2)*y - x"]
+/// y0 = [2.0, 0.0]
+///
+/// [parameters]
+/// mu = 5.0
+/// ```
+///
+/// The same keys are read from YAML files.
+#[derive(Debug)]
+pub struct Config {
+    pub problem: ProblemArgs,
+    pub output: OutputArgs,
+    pub runs: Vec<RunConfig>,
+}
+
+/// One solve of the problem, with its own method and optionally its own solver parameters.
+#[derive(Debug)]
+pub struct RunConfig {
+    /// Names the solution file and the plot series, the method id by default
+    pub name: String,
+    pub method: &'static MethodInfo,
+    pub params: OdeSolverParams,
+}
+
+/// Errors reported when loading a problem description file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Io(String, std::io::Error),
+    /// The file extension is neither `.toml` nor `.yaml`/`.yml`.
+    UnknownFormat(String),
+    /// The file is not valid TOML or YAML.
+    Syntax(String),
+    /// The value at `key` (e.g. `runs[1].time_step`) is missing or invalid.
+    Invalid { key: String, reason: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "cannot read `{}`: {}", path, err),
+            ConfigError::UnknownFormat(path) => write!(
+                f,
+                "unknown configuration format for `{}` (expected .toml, .yaml or .yml)",
+                path
+            ),
+            ConfigError::Syntax(reason) => write!(f, "syntax error: {}", reason),
+            ConfigError::Invalid { key, reason } => write!(f, "`{}`: {}", key, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+fn invalid<T>(key: &str, reason: impl Into<String>) -> Result<T, ConfigError> {
+    Err(ConfigError::Invalid {
+        key: key.to_string(),
+        reason: reason.into(),
+    })
+}
+
+/// Contents of a configuration file, the same for both file formats.
+#[derive(Debug)]
+enum Node {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<Node>),
+    Table(Vec<(String, Node)>),
+}
+
+impl Node {
+    fn kind(&self) -> &'static str {
+        match self {
+            Node::Bool(_) => "a boolean",
+            Node::Integer(_) => "an integer",
+            Node::Float(_) => "a number",
+            Node::String(_) => "a string",
+            Node::List(_) => "a list",
+            Node::Table(_) => "a table",
+        }
+    }
+
+    fn from_toml(value: toml::Value) -> Node {
+        match value {
+            toml::Value::Boolean(value) => Node::Bool(value),
+            toml::Value::Integer(value) => Node::Integer(value),
+            toml::Value::Float(value) => Node::Float(value),
+            toml::Value::String(value) => Node::String(value),
+            // Dates have no meaning here, the type check reports them as strings
+            toml::Value::Datetime(value) => Node::String(value.to_string()),
+            toml::Value::Array(values) => {
+                Node::List(values.into_iter().map(Node::from_toml).collect())
+            }
+            toml::Value::Table(table) => Node::Table(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, Node::from_toml(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn from_yaml(value: Yaml, key: &str) -> Result<Node, ConfigError> {
+        Ok(match value {
+            Yaml::Boolean(value) => Node::Bool(value),
+            Yaml::Integer(value) => Node::Integer(value),
+            Yaml::Real(text) => match text.parse::<f64>() {
+                Ok(value) => Node::Float(value),
+                // YAML spells the special values .inf and .nan
+                Err(_) => match Yaml::Real(text.clone()).as_f64() {
+                    Some(value) => Node::Float(value),
+                    None => return invalid(key, format!("`{}` is not a number", text)),
+                },
+            },
+            Yaml::String(value) => Node::String(value),
+            Yaml::Array(values) => Node::List(
+                values
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, value)| Node::from_yaml(value, &format!("{}[{}]", key, index)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Yaml::Hash(hash) => {
+                let mut entries: Vec<(String, Node)> = Vec::with_capacity(hash.len());
+                for (name, value) in hash {
+                    let name: String = match name {
+                        Yaml::String(name) => name,
+                        _ => return invalid(key, "keys must be strings"),
+                    };
+                    let path: String = join_key(key, &name);
+                    entries.push((name, Node::from_yaml(value, &path)?));
+                }
+                Node::Table(entries)
+            }
+            Yaml::Null => return invalid(key, "has no value"),
+            Yaml::Alias(_) | Yaml::BadValue => return invalid(key, "is not a valid value"),
+        })
+    }
+}
+
+fn join_key(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+/// A table of the file, with the path of its keys for the error messages.
+struct Section<'n> {
+    path: String,
+    entries: &'n [(String, Node)],
+}
+
+impl<'n> Section<'n> {
+    fn new(path: String, node: &'n Node) -> Result<Self, ConfigError> {
+        match node {
+            Node::Table(entries) => Ok(Section { path, entries }),
+            other => invalid(&path, format!("must be a table, found {}", other.kind())),
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        join_key(&self.path, name)
+    }
+
+    /// Rejects the keys not in `known`, which are most likely misspelt.
+    fn only_keys(&self, known: &[&str]) -> Result<(), ConfigError> {
+        match self
+            .entries
+            .iter()
+            .find(|(name, _)| !known.contains(&name.as_str()))
+        {
+            Some((name, _)) => invalid(
+                &self.key(name),
+                format!("unknown key (expected one of: {})", known.join(", ")),
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&'n Node> {
+        self.entries
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, node)| node)
+    }
+
+    fn string(&self, name: &str) -> Result<Option<&'n str>, ConfigError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(Node::String(value)) => Ok(Some(value)),
+            Some(other) => invalid(
+                &self.key(name),
+                format!("must be a string, found {}", other.kind()),
+            ),
+        }
+    }
+
+    fn bool(&self, name: &str) -> Result<Option<bool>, ConfigError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(Node::Bool(value)) => Ok(Some(*value)),
+            Some(other) => invalid(
+                &self.key(name),
+                format!("must be a boolean, found {}", other.kind()),
+            ),
+        }
+    }
+
+    fn float(&self, name: &str) -> Result<Option<f64>, ConfigError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(node) => number(node, &self.key(name)).map(Some),
+        }
+    }
+
+    fn integer(&self, name: &str) -> Result<Option<i32>, ConfigError> {
+        match self.get(name) {
+            None => Ok(None),
+            Some(Node::Integer(value)) => match i32::try_from(*value) {
+                Ok(value) => Ok(Some(value)),
+                Err(_) => invalid(&self.key(name), "is out of range"),
+            },
+            Some(other) => invalid(
+                &self.key(name),
+                format!("must be an integer, found {}", other.kind()),
+            ),
+        }
+    }
+
+    fn floats(&self, name: &str) -> Result<Option<Vec<f64>>, ConfigError> {
+        let key: String = self.key(name);
+        match self.get(name) {
+            None => Ok(None),
+            Some(Node::List(values)) => values
+                .iter()
+                .enumerate()
+                .map(|(index, node)| number(node, &format!("{}[{}]", key, index)))
+                .collect::<Result<_, _>>()
+                .map(Some),
+            // A scalar problem may give its initial value as a number
+            Some(node @ (Node::Float(_) | Node::Integer(_))) => Ok(Some(vec![number(node, &key)?])),
+            Some(other) => invalid(
+                &key,
+                format!("must be a list of numbers, found {}", other.kind()),
+            ),
+        }
+    }
+
+    /// A string, or a list of strings.
+    fn strings(&self, name: &str) -> Result<Option<Vec<String>>, ConfigError> {
+        let key: String = self.key(name);
+        let as_string = |node: &Node, key: &str| match node {
+            Node::String(value) => Ok(value.clone()),
+            other => invalid(key, format!("must be a string, found {}", other.kind())),
+        };
+        match self.get(name) {
+            None => Ok(None),
+            Some(Node::List(values)) => values
+                .iter()
+                .enumerate()
+                .map(|(index, node)| as_string(node, &format!("{}[{}]", key, index)))
+                .collect::<Result<_, _>>()
+                .map(Some),
+            Some(node) => Ok(Some(vec![as_string(node, &key)?])),
+        }
+    }
+
+    fn tables(&self, name: &str) -> Result<Vec<Section<'n>>, ConfigError> {
+        let key: String = self.key(name);
+        match self.get(name) {
+            None => Ok(Vec::new()),
+            Some(Node::List(values)) => values
+                .iter()
+                .enumerate()
+                .map(|(index, node)| Section::new(format!("{}[{}]", key, index), node))
+                .collect(),
+            Some(other) => invalid(
+                &key,
+                format!("must be a list of tables, found {}", other.kind()),
+            ),
+        }
+    }
+}
+
+fn number(node: &Node, key: &str) -> Result<f64, ConfigError> {
+    match node {
+        Node::Float(value) => Ok(*value),
+        Node::Integer(value) => Ok(*value as f64),
+        other => invalid(key, format!("must be a number, found {}", other.kind())),
+    }
+}
+
+const PARAMETER_KEYS: [&str; 4] = ["time_step", "tolerance", "rel_tolerance", "max_iters"];
+const TOP_LEVEL_KEYS: [&str; 13] = [
+    "problem",
+    "method",
+    "rhs",
+    "parameters",
+    "t0",
+    "tf",
+    "time_step",
+    "tolerance",
+    "rel_tolerance",
+    "max_iters",
+    "y0",
+    "output",
+    "runs",
+];
+const OUTPUT_KEYS: [&str; 5] = ["directory", "format", "plot_format", "files", "plot"];
+const RUN_KEYS: [&str; 6] = [
+    "name",
+    "method",
+    "time_step",
+    "tolerance",
+    "rel_tolerance",
+    "max_iters",
+];
+
+/// Loads and validates the problem description file at `file_path`, in the format given
+/// by its extension.
+pub fn load_config(file_path: &Path) -> Result<Config, ConfigError> {
+    let display: String = file_path.display().to_string();
+    let extension: String = file_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let text: String =
+        std::fs::read_to_string(file_path).map_err(|err| ConfigError::Io(display.clone(), err))?;
+    match extension.as_str() {
+        "toml" => parse_toml(&text),
+        "yaml" | "yml" => parse_yaml(&text),
+        _ => Err(ConfigError::UnknownFormat(display)),
+    }
+}
+
+/// Reads a configuration from TOML text.
+pub fn parse_toml(text: &str) -> Result<Config, ConfigError> {
+    let table: toml::Table = text
+        .parse()
+        .map_err(|err: toml::de::Error| ConfigError::Syntax(err.to_string()))?;
+    config_from_node(&Node::from_toml(toml::Value::Table(table)))
+}
+
+/// Reads a configuration from YAML text, the first document if there are several.
+pub fn parse_yaml(text: &str) -> Result<Config, ConfigError> {
+    let documents: Vec<Yaml> =
+        YamlLoader::load_from_str(text).map_err(|err| ConfigError::Syntax(err.to_string()))?;
+    match documents.into_iter().next() {
+        Some(document) => config_from_node(&Node::from_yaml(document, "")?),
+        None => invalid("runs", "is missing, the file is empty"),
+    }
+}
+
+fn config_from_node(root: &Node) -> Result<Config, ConfigError> {
+    let root = Section::new(String::new(), root)?;
+    root.only_keys(&TOP_LEVEL_KEYS)?;
+
+    let problem_id: &str = root.string("problem")?.unwrap_or("quadratic-decay");
+    let rhs: Vec<String> = root.strings("rhs")?.unwrap_or_default();
+    let mut parameters: Vec<(String, f64)> = Vec::new();
+    if let Some(node) = root.get("parameters") {
+        let section = Section::new("parameters".to_string(), node)?;
+        for (name, value) in section.entries {
+            parameters.push((name.clone(), number(value, &section.key(name))?));
+        }
+    }
+    let built_in: BenchmarkProblem = if rhs.is_empty() {
+        if !parameters.is_empty() {
+            return invalid("parameters", "only applies to the equations of `rhs`");
+        }
+        match problem(problem_id) {
+            Some(built_in) => built_in,
+            None => {
+                return invalid(
+                    "problem",
+                    format!(
+                        "unknown problem `{}` (expected one of: {})",
+                        problem_id,
+                        PROBLEM_IDS.join(", ")
+                    ),
+                )
+            }
+        }
+    } else {
+        if root.get("problem").is_some() {
+            return invalid("rhs", "cannot be given together with `problem`");
+        }
+        match equations_problem(&rhs, &parameters) {
+            Ok(equations) => equations,
+            // A bad parameter name is reported as an equation made of the name
+            Err(ExpressionError::InvalidEquation { equation, reason })
+                if parameters.iter().any(|(name, _)| *name == equation) =>
+            {
+                return invalid(&format!("parameters.{}", equation), reason)
+            }
+            Err(err) => return invalid("rhs", err.to_string()),
+        }
+    };
+    let problem_args = ProblemArgs {
+        problem: problem_id.to_string(),
+        rhs,
+        parameters,
+        t0: root.float("t0")?,
+        tf: root.float("tf")?,
+        time_step: root.float("time_step")?,
+        tolerance: root.float("tolerance")?,
+        rel_tolerance: root.float("rel_tolerance")?,
+        max_iters: root.integer("max_iters")?,
+        y0: root.floats("y0")?.unwrap_or_default(),
+    };
+    if let Some(index) = problem_args.y0.iter().position(|value| !value.is_finite()) {
+        return invalid(&format!("y0[{}]", index), "must be finite");
+    }
+    let dimension: usize = built_in.system.dimension();
+    if problem_args.y0.is_empty() && built_in.initial_state.is_empty() {
+        return invalid("y0", "is required with `rhs`");
+    }
+    if !problem_args.y0.is_empty() && problem_args.y0.len() != dimension {
+        return invalid(
+            "y0",
+            format!(
+                "has {} components but `{}` has dimension {}",
+                problem_args.y0.len(),
+                built_in.id,
+                dimension
+            ),
+        );
+    }
+
+    let output: OutputArgs = match root.get("output") {
+        Some(node) => output_from_section(&Section::new("output".to_string(), node)?)?,
+        None => output_from_section(&Section {
+            path: "output".to_string(),
+            entries: &[],
+        })?,
+    };
+
+    let shared = OdeSolverParams {
+        time_span: TimeSpan::new(
+            problem_args.t0.unwrap_or(built_in.time_span.t0),
+            problem_args.tf.unwrap_or(built_in.time_span.tf),
+        ),
+        ..OdeSolverParams::default()
+    };
+    let default_method: Option<&'static MethodInfo> = method_of(&root)?;
+    let mut run_sections: Vec<Section> = root.tables("runs")?;
+    if run_sections.is_empty() && default_method.is_some() {
+        // The top level is the only run, its keys are those of the file
+        run_sections.push(Section {
+            path: String::new(),
+            entries: &[],
+        });
+    }
+    let mut runs: Vec<RunConfig> = Vec::new();
+    for run in run_sections {
+        run.only_keys(&RUN_KEYS)?;
+        let method: &'static MethodInfo = match (method_of(&run)?, default_method) {
+            (Some(method), _) | (None, Some(method)) => method,
+            (None, None) => {
+                return invalid(
+                    &run.key("method"),
+                    "is missing, and there is no `method` at the top level",
+                )
+            }
+        };
+        let name: String = run.string("name")?.unwrap_or(method.id).to_string();
+        if runs.iter().any(|other| other.name == name) {
+            let key: String = match run.get("name") {
+                Some(_) => run.key("name"),
+                None => run.path.clone(),
+            };
+            return invalid(
+                &key,
+                format!(
+                    "another run is already named `{}`, runs write to files named after them",
+                    name
+                ),
+            );
+        }
+
+        // A run takes its parameters from its own keys, then the top level, then the defaults
+        let params = OdeSolverParams {
+            time_step: run
+                .float("time_step")?
+                .or(problem_args.time_step)
+                .unwrap_or(shared.time_step),
+            tolerance: run
+                .float("tolerance")?
+                .or(problem_args.tolerance)
+                .unwrap_or(shared.tolerance),
+            rel_tolerance: run
+                .float("rel_tolerance")?
+                .or(problem_args.rel_tolerance)
+                .unwrap_or(shared.rel_tolerance),
+            max_iters: run
+                .integer("max_iters")?
+                .or(problem_args.max_iters)
+                .unwrap_or(shared.max_iters),
+            ..shared.clone()
+        };
+        if let Err(OdeError::InvalidParameter {
+            name: parameter,
+            reason,
+        }) = params.validate()
+        {
+            let key: String = match parameter {
+                "time_span" if problem_args.tf.is_some() => "tf".to_string(),
+                "time_span" => "t0".to_string(),
+                _ if run.get(parameter).is_some() => run.key(parameter),
+                _ if PARAMETER_KEYS.contains(&parameter) => parameter.to_string(),
+                _ => run.path.clone(),
+            };
+            return invalid(&key, reason);
+        }
+        runs.push(RunConfig {
+            name,
+            method,
+            params,
+        });
+    }
+    if runs.is_empty() {
+        return invalid(
+            "runs",
+            "must list at least one run, or a `method` must be given at the top level",
+        );
+    }
+
+    Ok(Config {
+        problem: problem_args,
+        output,
+        runs,
+    })
+}
+
+/// The method named by the `method` key of `section`, if any.
+fn method_of(section: &Section) -> Result<Option<&'static MethodInfo>, ConfigError> {
+    match section.string("method")? {
+        None => Ok(None),
+        Some(method_name) => match find_method(method_name) {
+            Some(method) => Ok(Some(method)),
+            None => invalid(
+                &section.key("method"),
+                format!("`{}` is not a known method", method_name),
+            ),
+        },
+    }
+}
+
+fn output_from_section(output: &Section) -> Result<OutputArgs, ConfigError> {
+    output.only_keys(&OUTPUT_KEYS)?;
+    let format: SolutionFormat = match output.string("format")? {
+        None | Some("csv") => SolutionFormat::Csv,
+        Some("jsonl") => SolutionFormat::Jsonl,
+        Some("bin") => SolutionFormat::Bin,
+        Some(other) => {
+            return invalid(
+                &output.key("format"),
+                format!("unknown format `{}` (expected csv, jsonl or bin)", other),
+            )
+        }
+    };
+    let plot_format: ImageFormat = match output.string("plot_format")? {
+        None | Some("html") => ImageFormat::Html,
+        Some("svg") => ImageFormat::Svg,
+        Some("png") => ImageFormat::Png,
+        Some(other) => {
+            return invalid(
+                &output.key("plot_format"),
+                format!("unknown format `{}` (expected html, svg or png)", other),
+            )
+        }
+    };
+    Ok(OutputArgs {
+        output_dir: output
+            .string("directory")?
+            .unwrap_or("solver_results")
+            .into(),
+        format,
+        plot_format,
+        no_files: !output.bool("files")?.unwrap_or(true),
+        no_plot: !output.bool("plot")?.unwrap_or(true),
+    })
+}
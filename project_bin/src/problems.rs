@@ -0,0 +1,86 @@
+use numerical_methods_lib::{
+    differentiation::{auto_diff::AutoDiffClosure, dual_numbers::Dual},
+    expressions::{expression_error::ExpressionError, expression_system::ExpressionSystem},
+    ode_solvers::ode_solver::TimeSpan,
+    problems::benchmark_problem::{self, BenchmarkProblem, Reference},
+};
+
+/// Names of the problems the command line can solve: the example of this project, then
+/// the suite of `numerical_methods_lib::problems`.
+pub const PROBLEM_IDS: [&str; 12] = [
+    "quadratic-decay",
+    "exponential-decay",
+    "logistic",
+    "prothero-robinson",
+    "van-der-pol",
+    "van-der-pol-stiff",
+    "lorenz",
+    "brusselator",
+    "robertson",
+    "hires",
+    "kepler",
+    "pendulum",
+];
+
+/// The problem named `id`.
+pub fn problem(id: &str) -> Option<BenchmarkProblem> {
+    match id {
+        "quadratic-decay" => Some(quadratic_decay()),
+        _ => benchmark_problem::problem(id),
+    }
+}
+
+// function: f(t,y) = -20 t y^2
+fn quadratic_decay() -> BenchmarkProblem {
+    BenchmarkProblem {
+        id: "quadratic-decay",
+        description: "y' = -20 t y^2".to_string(),
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 1.0),
+        initial_state: vec![1.0],
+        system: Box::new(AutoDiffClosure::new(
+            1,
+            |t: Dual, y: &[Dual], dydt: &mut [Dual]| dydt[0] = -20.0 * t * y[0] * y[0],
+        )),
+        // 1/y grows like 10 t^2
+        reference: Some(Reference::Exact(|t0, y0, t| {
+            vec![1.0 / (1.0 / y0[0] + 10.0 * (t * t - t0 * t0))]
+        })),
+    }
+}
+
+/// Id of the problem given by its equations, see [`equations_problem`].
+pub const EQUATIONS_ID: &str = "equations";
+
+/// Problem given by its equations at runtime, e.g. `["dx = y", "dy = -x"]`, with the
+/// `parameters` they use. A single right hand side without `=` is the scalar equation
+/// `y' = rhs`. The initial state has no default and must be given.
+pub fn equations_problem(
+    equations: &[String],
+    parameters: &[(String, f64)],
+) -> Result<BenchmarkProblem, ExpressionError> {
+    let equations: Vec<&str> = equations.iter().map(String::as_str).collect();
+    let parameters: Vec<(&str, f64)> = parameters
+        .iter()
+        .map(|(name, value)| (name.as_str(), *value))
+        .collect();
+    let (system, description) = match equations.as_slice() {
+        [rhs] if !rhs.contains('=') => (
+            ExpressionSystem::scalar(rhs, &parameters)?,
+            format!("y' = {}", rhs.trim()),
+        ),
+        _ => (
+            ExpressionSystem::new(&equations, &parameters)?,
+            equations.join("; "),
+        ),
+    };
+    Ok(BenchmarkProblem {
+        id: EQUATIONS_ID,
+        description,
+        stiff: false,
+        time_span: TimeSpan::new(0.0, 1.0),
+        initial_state: Vec::new(),
+        system: Box::new(system),
+        reference: None,
+    })
+}
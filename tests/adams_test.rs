@@ -0,0 +1,146 @@
+use numerical_methods_lib::ode_solvers::{
+    adams_bashforth, adams_moulton, ode_error, ode_solution, ode_solver, ode_system,
+};
+use numerical_methods_lib::output::solution_file::{write_solution_file, RunMetadata};
+use ode_solver::Solve;
+use std::fs::read_to_string;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.01; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    const FILE_PATH: &str = "solver_results/adams_solver_test.csv";
+
+    // y' = -y, y(0) = 1 with exact solution exp(-t)
+    fn solve_decay(order: usize, corrector: bool, time_step: f64) -> ode_solution::OdeSolution {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver Adams Test", &system, &solver_params);
+
+        if corrector {
+            adams_moulton::AdamsMoultonSolver {
+                solver: Box::new(solver),
+                order,
+            }
+            .solve(&[INITIAL_SOLUTION])
+            .unwrap()
+        } else {
+            adams_bashforth::AdamsBashforthSolver {
+                solver: Box::new(solver),
+                order,
+            }
+            .solve(&[INITIAL_SOLUTION])
+            .unwrap()
+        }
+    }
+
+    fn final_error(order: usize, corrector: bool, time_step: f64) -> f64 {
+        let solution = solve_decay(order, corrector, time_step);
+        let (t, y) = solution.last().unwrap();
+        (y[0] - (-t).exp()).abs()
+    }
+
+    #[test]
+    fn adams_methods_reach_their_order() {
+        for corrector in [false, true] {
+            for order in 1..=adams_bashforth::MAX_ADAMS_ORDER {
+                let coarse = final_error(order, corrector, 0.04);
+                let fine = final_error(order, corrector, 0.02);
+                let observed_order = (coarse / fine).log2();
+                assert!(
+                    (observed_order - order as f64).abs() < 0.3,
+                    "order {} (corrector: {}) observed {}",
+                    order,
+                    corrector,
+                    observed_order
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn adams_rhs_evaluations_per_step() {
+        let order: usize = 4;
+        let num_steps: usize = 100;
+        // f at t0, then RK4 plus one evaluation for each of the order - 1 starting steps
+        let startup: usize = 1 + (order - 1) * 5;
+
+        let bashforth = solve_decay(order, false, TIME_STEP);
+        let moulton = solve_decay(order, true, TIME_STEP);
+
+        assert_eq!(bashforth.len(), num_steps + 1);
+        assert_eq!(
+            bashforth.stats.num_rhs_evals,
+            startup + (num_steps - order + 1)
+        );
+        assert_eq!(
+            moulton.stats.num_rhs_evals,
+            startup + 2 * (num_steps - order + 1)
+        );
+        // The corrector improves on the predictor of the same order
+        let exact = (-T_FINAL).exp();
+        assert!(
+            (moulton.last().unwrap().1[0] - exact).abs()
+                < (bashforth.last().unwrap().1[0] - exact).abs()
+        );
+    }
+
+    #[test]
+    fn invalid_order_is_rejected() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams::default();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Adams Test", &system, &solver_params);
+        let adams_bashforth_solver = adams_bashforth::AdamsBashforthSolver {
+            solver: Box::new(solver),
+            order: 6,
+        };
+
+        assert!(matches!(
+            adams_bashforth_solver.solve(&[INITIAL_SOLUTION]),
+            Err(ode_error::OdeError::InvalidParameter { name: "order", .. })
+        ));
+    }
+
+    #[test]
+    fn write_solution_initial_val() {
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver Adams Test", &system, &solver_params);
+        let adams_moulton_solver = adams_moulton::AdamsMoultonSolver {
+            solver: Box::new(solver),
+            order: 3,
+        };
+
+        let solution = adams_moulton_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let metadata = RunMetadata::new("Adams-Moulton", &solver_params);
+        write_solution_file(FILE_PATH, &solution, &metadata).unwrap();
+
+        let contents = read_to_string(FILE_PATH).unwrap();
+        let mut rows = contents.lines().filter(|line| !line.starts_with('#'));
+        assert_eq!(rows.next().unwrap(), "t,y0");
+        let first_row: Vec<f64> = rows
+            .next()
+            .unwrap()
+            .split(',')
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect();
+        assert_eq!(first_row, vec![T_INITIAL, INITIAL_SOLUTION]);
+    }
+}
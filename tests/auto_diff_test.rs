@@ -0,0 +1,125 @@
+use numerical_methods_lib::differentiation::{
+    auto_diff::{AutoDiff, AutoDiffClosure, AutoDiffSystem},
+    dual_numbers::{Dual, Scalar},
+};
+use numerical_methods_lib::ode_solvers::{implicit_euler_method, ode_solver, ode_system};
+use ode_solver::Solve;
+use ode_system::OdeSystem;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const TOLERANCE: f64 = 1e-8; // tol
+    const MAX_ITERATIONS: i32 = 100;
+
+    // Robertson chemical kinetics with the rate constants as parameters
+    struct Robertson {
+        k1: f64,
+        k2: f64,
+        k3: f64,
+    }
+
+    impl AutoDiffSystem for Robertson {
+        fn dimension(&self) -> usize {
+            3
+        }
+
+        fn rhs<S: Scalar>(&self, _t: S, y: &[S], dydt: &mut [S]) {
+            dydt[0] = y[0] * -self.k1 + y[1] * y[2] * self.k3;
+            dydt[1] = y[0] * self.k1 - y[1] * y[2] * self.k3 - y[1] * y[1] * self.k2;
+            dydt[2] = y[1] * y[1] * self.k2;
+        }
+    }
+
+    #[test]
+    fn dual_numbers_differentiate_exactly() {
+        let x = Dual::variable(0.7);
+
+        let product = x * x.sin() + 3.0;
+        assert_eq!(product.value, 0.7 * 0.7_f64.sin() + 3.0);
+        assert!((product.derivative - (0.7_f64.sin() + 0.7 * 0.7_f64.cos())).abs() < 1e-15);
+
+        let quotient = x.exp() / (1.0 + x * x);
+        let expected = 0.7_f64.exp() * (1.0 - 2.0 * 0.7 + 0.7 * 0.7) / (1.0 + 0.7 * 0.7).powi(2);
+        assert!((quotient.derivative - expected).abs() < 1e-14);
+
+        let chained = x.powi(3).ln() + (1.0 + x * x).sqrt();
+        let expected = 3.0 / 0.7 + 0.7 / (1.0 + 0.7 * 0.7_f64).sqrt();
+        assert!((chained.derivative - expected).abs() < 1e-14);
+
+        let power = x.powf(2.5).tanh();
+        let expected = (1.0 - 0.7_f64.powf(2.5).tanh().powi(2)) * 2.5 * 0.7_f64.powf(1.5);
+        assert!((power.derivative - expected).abs() < 1e-14);
+        // Constants carry no derivative
+        assert_eq!(Dual::from(2.0).sin().derivative, 0.0);
+    }
+
+    #[test]
+    fn auto_diff_jacobian_matches_analytic() {
+        let system = AutoDiff(Robertson {
+            k1: 0.04,
+            k2: 3e7,
+            k3: 1e4,
+        });
+        let y = [0.9, 2e-5, 0.1];
+        let mut jac = [0.0; 9];
+        system.jacobian(0.0, &y, &mut jac);
+
+        let expected = [
+            -0.04,
+            1e4 * y[2],
+            1e4 * y[1],
+            0.04,
+            -1e4 * y[2] - 6e7 * y[1],
+            -1e4 * y[1],
+            0.0,
+            6e7 * y[1],
+            0.0,
+        ];
+        for (value, exact) in jac.iter().zip(expected) {
+            assert!((value - exact).abs() <= 1e-14 * exact.abs());
+        }
+
+        // The plain rhs runs on f64
+        let mut dydt = [0.0; 3];
+        system.rhs(0.0, &y, &mut dydt);
+        assert_eq!(dydt[2], y[1] * y[1] * 3e7);
+    }
+
+    #[test]
+    fn implicit_euler_with_auto_diff_closure() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let f_dash = |x: f64, y: &[f64], jac: &mut [f64]| jac[0] = -40.0 * x * y[0]; // function: f'(t,y)
+        let f_dual = |x: Dual, y: &[Dual], dydt: &mut [Dual]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+
+        let hand_written = ode_system::ClosureSystem::with_jacobian(1, f, f_dash);
+        let auto_diff = AutoDiffClosure::new(1, f_dual);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERATIONS,
+            ..Default::default()
+        };
+
+        let solve = |system: &dyn OdeSystem| {
+            let solver =
+                ode_solver::OdeSolver::new("Implicit Euler Method Test", system, &solver_params);
+            implicit_euler_method::ImplicitEulerSolver {
+                solver: Box::new(solver),
+            }
+            .solve(&[INITIAL_SOLUTION])
+            .unwrap()
+        };
+        let expected = solve(&hand_written);
+        let solution = solve(&auto_diff);
+
+        assert_eq!(solution.states, expected.states);
+        assert_eq!(solution.stats, expected.stats);
+    }
+}
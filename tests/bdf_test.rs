@@ -0,0 +1,179 @@
+use numerical_methods_lib::ode_solvers::{bdf_method, ode_solution, ode_solver, ode_system};
+use numerical_methods_lib::output::solution_file::{write_solution_file, RunMetadata};
+use ode_solver::Solve;
+use ode_system::OdeSystem;
+use std::fs::read_to_string;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.01; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const TOLERANCE: f64 = 1e-8; // tol
+
+    const FILE_PATH: &str = "solver_results/bdf_solver_test.csv";
+
+    // Robertson chemical kinetics, stiff with rate constants spanning nine orders of magnitude
+    struct Robertson;
+
+    impl OdeSystem for Robertson {
+        fn dimension(&self) -> usize {
+            3
+        }
+
+        fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+            dydt[0] = -0.04 * y[0] + 1e4 * y[1] * y[2];
+            dydt[1] = 0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1];
+            dydt[2] = 3e7 * y[1] * y[1];
+        }
+
+        fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+            jac.copy_from_slice(&[
+                -0.04,
+                1e4 * y[2],
+                1e4 * y[1],
+                0.04,
+                -1e4 * y[2] - 6e7 * y[1],
+                -1e4 * y[1],
+                0.0,
+                6e7 * y[1],
+                0.0,
+            ]);
+        }
+
+        fn jacobian_rhs_evals(&self) -> usize {
+            0
+        }
+    }
+
+    fn test_fixture() -> ode_solution::OdeSolution {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let f_dash = |x: f64, y: &[f64], jac: &mut [f64]| jac[0] = -40.0 * x * y[0]; // function: f'(t,y)
+
+        let system = ode_system::ClosureSystem::with_jacobian(1, f, f_dash);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver BDF Test", &system, &solver_params);
+        let bdf_solver = bdf_method::BdfSolver {
+            solver: Box::new(solver),
+        };
+
+        bdf_solver.solve(&[INITIAL_SOLUTION]).unwrap()
+    }
+
+    #[test]
+    fn bdf_matches_exact_solution() {
+        let solution = test_fixture();
+
+        assert_eq!(solution.states[0][0], INITIAL_SOLUTION);
+        assert_eq!(*solution.times.last().unwrap(), T_FINAL);
+        // Exact solution of y' = -20 t y^2, y(0) = 1
+        for (t, y) in solution.times.iter().zip(&solution.states) {
+            let exact = 1.0 / (1.0 + 10.0 * t * t);
+            assert!((y[0] - exact).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn bdf_reuses_jacobian() {
+        let solution = test_fixture();
+
+        assert_eq!(solution.newton_steps.len(), solution.stats.accepted_steps);
+        assert!(2 * solution.stats.num_jacobian_evals < solution.stats.accepted_steps);
+    }
+
+    #[test]
+    fn bdf_solves_stiff_robertson() {
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(0.0, 40.0),
+            time_step: 1e-4,
+            tolerance: 1e-10,
+            rel_tolerance: 1e-6,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver BDF Test", &Robertson, &solver_params);
+        let bdf_solver = bdf_method::BdfSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = bdf_solver.solve(&[1.0, 0.0, 0.0]).unwrap();
+        let (t, y) = solution.last().unwrap();
+        // Reference values at t = 40
+        let reference = [0.7158270687, 9.185534764e-6, 0.2841637457];
+
+        assert_eq!(t, 40.0);
+        for (value, exact) in y.iter().zip(reference) {
+            assert!(((value - exact) / exact).abs() < 1e-3);
+        }
+        // The reactions conserve the total mass
+        assert!((y.iter().sum::<f64>() - 1.0).abs() < 1e-8);
+        // An explicit method would need tens of thousands of steps here
+        assert!(solution.stats.accepted_steps < 1000);
+    }
+
+    #[test]
+    fn write_solution_initial_val() {
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver BDF Test", &system, &solver_params);
+        let bdf_solver = bdf_method::BdfSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = bdf_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let metadata = RunMetadata::new("BDF", &solver_params);
+        write_solution_file(FILE_PATH, &solution, &metadata).unwrap();
+
+        let contents = read_to_string(FILE_PATH).unwrap();
+        let mut rows = contents.lines().filter(|line| !line.starts_with('#'));
+        assert_eq!(rows.next().unwrap(), "t,y0");
+        let first_row: Vec<f64> = rows
+            .next()
+            .unwrap()
+            .split(',')
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect();
+        assert_eq!(first_row, vec![T_INITIAL, INITIAL_SOLUTION]);
+    }
+
+    #[test]
+    fn bdf_large_first_step_ends_at_final_time() {
+        let f = |_x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        // The first step is clamped to tf - t0, where t0 + (tf - t0) rounds away from tf,
+        // and some spans cross zero
+        let spans = (0..200)
+            .map(|i| (0.0137 * i as f64 - 1.0, 0.852056 + 0.0091 * i as f64))
+            .chain([(0.2968, 0.852056)]);
+        for (t0, tf) in spans {
+            let solver_params = ode_solver::OdeSolverParams {
+                time_span: ode_solver::TimeSpan::new(t0, tf),
+                time_step: 100.0,
+                ..Default::default()
+            };
+            let solver = ode_solver::OdeSolver::new("ODE Solver BDF Test", &system, &solver_params);
+            let solution = bdf_method::BdfSolver {
+                solver: Box::new(solver),
+            }
+            .solve(&[0.0])
+            .unwrap();
+
+            assert_eq!(*solution.times.last().unwrap(), tf);
+            assert!((solution.states.last().unwrap()[0] - (tf - t0)).abs() < 1e-12);
+        }
+    }
+}
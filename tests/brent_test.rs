@@ -0,0 +1,74 @@
+use numerical_methods_lib::root_finders::brent_method::{brent_method_root, brent_method_solve};
+use numerical_methods_lib::root_finders::newton_raphson_method::TerminationReason;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-12; // tol
+    const MAX_ITERATIONS: i32 = 100;
+
+    #[test]
+    fn brent_finds_root_inside_bracket() {
+        let result = brent_method_solve(|x: f64| x * x - 2.0, 0.0, 2.0, TOLERANCE, MAX_ITERATIONS);
+
+        assert!(result.converged);
+        assert!((result.root - 2.0_f64.sqrt()).abs() < TOLERANCE);
+        assert!(result.residual < 1e-11);
+        // Much faster than the ~40 bisections this tolerance needs
+        assert!(result.iterations < 15);
+    }
+
+    #[test]
+    fn brent_handles_flat_and_steep_functions() {
+        // Triple root, where interpolation is poor and bisection steps take over
+        let cubic = brent_method_root(|x: f64| (x - 1.0).powi(3), 0.0, 3.0, TOLERANCE, 200);
+        assert!((cubic.unwrap() - 1.0).abs() < 1e-6);
+
+        let steep = brent_method_root(
+            |x: f64| (20.0 * (x - 0.3)).tanh(),
+            -1.0,
+            1.0,
+            TOLERANCE,
+            MAX_ITERATIONS,
+        );
+        assert!((steep.unwrap() - 0.3).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn brent_accepts_reversed_bracket_and_exact_end_points() {
+        let reversed = brent_method_root(|x: f64| x.cos(), 2.0, 1.0, TOLERANCE, MAX_ITERATIONS);
+        assert!((reversed.unwrap() - std::f64::consts::FRAC_PI_2).abs() < TOLERANCE);
+
+        let result = brent_method_solve(|x: f64| x - 1.0, 1.0, 5.0, TOLERANCE, MAX_ITERATIONS);
+        assert_eq!(result.root, 1.0);
+        assert_eq!(result.iterations, 0);
+        assert_eq!(result.reason, TerminationReason::ResidualTolerance);
+    }
+
+    #[test]
+    fn brent_reports_invalid_bracket() {
+        let result = brent_method_solve(|x: f64| x * x + 1.0, -1.0, 1.0, TOLERANCE, MAX_ITERATIONS);
+
+        assert!(!result.converged);
+        assert_eq!(result.reason, TerminationReason::InvalidBracket);
+        assert_eq!(
+            brent_method_root(|x: f64| x * x + 1.0, -1.0, 1.0, TOLERANCE, MAX_ITERATIONS),
+            None
+        );
+    }
+
+    #[test]
+    fn brent_stops_on_non_finite_values() {
+        let result = brent_method_solve(
+            |x: f64| if x > 0.5 { f64::NAN } else { x - 0.75 },
+            0.0,
+            1.0,
+            TOLERANCE,
+            MAX_ITERATIONS,
+        );
+
+        assert_eq!(result.reason, TerminationReason::NonFinite);
+        assert!(!result.converged);
+    }
+}
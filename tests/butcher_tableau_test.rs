@@ -0,0 +1,160 @@
+use numerical_methods_lib::ode_solvers::{
+    butcher_tableau::ButcherTableau, explicit_runge_kutta, ode_error, ode_solution, ode_solver,
+    ode_system, runge_kutta4,
+};
+use ode_solver::Solve;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    fn solve_with_tableau(tableau: ButcherTableau) -> ode_solution::OdeSolution {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Explicit RK Test", &system, &solver_params);
+        let explicit_rk_solver = explicit_runge_kutta::ExplicitRkSolver {
+            solver: Box::new(solver),
+            tableau,
+        };
+
+        explicit_rk_solver.solve(&[INITIAL_SOLUTION]).unwrap()
+    }
+
+    #[test]
+    fn built_in_tableaux_have_declared_order() {
+        for tableau in ButcherTableau::built_in() {
+            assert!(tableau.is_explicit(), "{} is not explicit", tableau.name);
+            assert!(tableau.validate().is_ok(), "{} is malformed", tableau.name);
+            assert!(
+                tableau.satisfies_order_conditions(tableau.order, 1e-12),
+                "{} fails its order conditions",
+                tableau.name
+            );
+            assert_eq!(tableau.achieved_order(6), tableau.order, "{}", tableau.name);
+        }
+    }
+
+    #[test]
+    fn malformed_tableaux_are_rejected() {
+        let heun = ButcherTableau::heun();
+        let new =
+            |a: Vec<Vec<f64>>, b: Vec<f64>, c: Vec<f64>| ButcherTableau::new("user", a, b, c, 2);
+        assert!(new(heun.a.clone(), heun.b.clone(), heun.c.clone()).is_ok());
+
+        let malformed = [
+            // c too short
+            new(vec![vec![], vec![1.0]], vec![0.5, 0.5], vec![0.0]),
+            // one row of a missing
+            new(vec![vec![]], vec![0.5, 0.5], vec![0.0, 1.0]),
+            // implicit
+            new(
+                vec![vec![0.0, 1.0], vec![1.0]],
+                vec![0.5, 0.5],
+                vec![0.0, 1.0],
+            ),
+            // c[1] is not the row sum
+            new(vec![vec![], vec![1.0]], vec![0.5, 0.5], vec![0.0, 0.5]),
+            // no stage
+            new(vec![], vec![], vec![]),
+        ];
+        for tableau in malformed {
+            assert!(matches!(
+                tableau,
+                Err(ode_error::OdeError::InvalidParameter {
+                    name: "tableau",
+                    ..
+                })
+            ));
+        }
+
+        // Tableaux edited after construction are checked by the solver
+        let mut tableau = ButcherTableau::rk4();
+        tableau.c.pop();
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams::default();
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Explicit RK Test", &system, &solver_params);
+        let explicit_rk_solver = explicit_runge_kutta::ExplicitRkSolver {
+            solver: Box::new(solver),
+            tableau,
+        };
+        assert!(explicit_rk_solver.solve(&[INITIAL_SOLUTION]).is_err());
+    }
+
+    #[test]
+    fn broken_tableau_fails_order_conditions() {
+        let mut tableau = ButcherTableau::rk4();
+        tableau.b[1] += 1e-3;
+        tableau.b[2] -= 1e-3;
+
+        assert!(tableau.satisfies_order_conditions(1, 1e-12));
+        assert!(!tableau.satisfies_order_conditions(4, 1e-12));
+    }
+
+    #[test]
+    fn rk4_tableau_matches_rk4_solver() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Runge Kutta 4 Test", &system, &solver_params);
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+        let solution = rungekutta_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let solution_tableau = solve_with_tableau(ButcherTableau::rk4());
+
+        assert_eq!(solution.times, solution_tableau.times);
+        assert_eq!(solution.states, solution_tableau.states);
+        assert_eq!(solution.stats, solution_tableau.stats);
+    }
+
+    #[test]
+    fn higher_order_tableau_is_more_accurate() {
+        // Exact solution of y' = -20 t y^2, y(0) = 1
+        let error = |solution: &ode_solution::OdeSolution| {
+            let (t, y) = solution.last().unwrap();
+            (y[0] - 1.0 / (1.0 + 10.0 * t * t)).abs()
+        };
+
+        let error_heun = error(&solve_with_tableau(ButcherTableau::heun()));
+        let error_rk3 = error(&solve_with_tableau(ButcherTableau::ssp_rk3()));
+        let error_rk5 = error(&solve_with_tableau(ButcherTableau::rk5_butcher()));
+
+        assert!(error_rk3 < error_heun);
+        assert!(error_rk5 < error_rk3);
+    }
+
+    #[test]
+    fn continuous_extensions_end_at_the_step_weights() {
+        for tableau in ButcherTableau::built_in() {
+            if let Some(dense) = &tableau.dense {
+                assert_eq!(dense.len(), tableau.stages());
+                // b_i(1) = b_i and b_i(0) = 0 by construction
+                for (weights, b) in dense.iter().zip(&tableau.b) {
+                    assert!((weights.iter().sum::<f64>() - b).abs() < 1e-15);
+                }
+            }
+        }
+        assert!(ButcherTableau::rk4().dense.is_some());
+        assert!(ButcherTableau::heun().dense.is_none());
+    }
+}
@@ -0,0 +1,209 @@
+use comparison_error::ComparisonError;
+use method_comparison::{ComparisonSettings, MethodComparison, RunSetting, WorkMeasure};
+use method_registry::{find_method, MethodInfo};
+use numerical_methods_lib::{
+    comparison::{comparison_error, method_comparison, report},
+    ode_solvers::method_registry,
+    problems::benchmark_problem::{problem, BenchmarkProblem},
+};
+use report::{to_csv, to_html, to_markdown, write_report};
+use std::path::PathBuf;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn methods(ids: &[&str]) -> Vec<&'static MethodInfo> {
+        ids.iter().map(|id| find_method(id).unwrap()).collect()
+    }
+
+    fn small_settings() -> ComparisonSettings {
+        ComparisonSettings {
+            rel_tolerances: vec![1e-4, 1e-6, 1e-8],
+            num_steps: vec![20, 40, 80],
+            ..Default::default()
+        }
+    }
+
+    fn decay_comparison() -> MethodComparison {
+        let decay: BenchmarkProblem = problem("exponential-decay").unwrap();
+        MethodComparison::run(
+            &decay,
+            &methods(&["explicit-euler", "rk4", "dormand-prince"]),
+            &small_settings(),
+        )
+        .unwrap()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ode_solver_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn every_method_runs_at_every_setting() {
+        let comparison = decay_comparison();
+
+        assert_eq!(comparison.runs.len(), 9);
+        assert_eq!(comparison.num_failures(), 0);
+        assert_eq!(
+            comparison
+                .methods()
+                .iter()
+                .map(|method| method.id)
+                .collect::<Vec<_>>(),
+            vec!["explicit-euler", "rk4", "dormand-prince"]
+        );
+        assert_eq!(comparison.runs[0].setting, RunSetting::TimeStep(0.05));
+        assert_eq!(comparison.runs[6].setting, RunSetting::RelTolerance(1e-4));
+
+        // RK4 evaluates the right hand side four times per step, and its error falls
+        // sixteen times when the steps are halved
+        let rk4 = find_method("rk4").unwrap();
+        let (work, errors) = comparison.work_precision(rk4, WorkMeasure::RhsEvaluations);
+        assert_eq!(work, vec![80.0, 160.0, 320.0]);
+        for pair in errors.windows(2) {
+            assert!((pair[0] / pair[1] - 16.0).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn tighter_tolerances_cost_more_and_are_more_accurate() {
+        let comparison = decay_comparison();
+        let dormand_prince = find_method("dormand-prince").unwrap();
+        let (work, errors) = comparison.work_precision(dormand_prince, WorkMeasure::RhsEvaluations);
+
+        assert_eq!(work.len(), 3);
+        assert!(work.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(errors.windows(2).all(|pair| pair[0] > pair[1]));
+        assert!(errors[2] < 1e-8);
+    }
+
+    #[test]
+    fn failed_runs_are_recorded() {
+        // Explicit Euler is unstable on the stiff Prothero-Robinson equation, and
+        // overflows within a hundred steps
+        let stiff: BenchmarkProblem = problem("prothero-robinson").unwrap();
+        let settings = ComparisonSettings {
+            num_steps: vec![100, 200, 400],
+            ..Default::default()
+        };
+        let comparison = MethodComparison::run(
+            &stiff,
+            &methods(&["explicit-euler", "implicit-euler"]),
+            &settings,
+        )
+        .unwrap();
+
+        assert_eq!(comparison.num_failures(), 3);
+        for run in &comparison.runs {
+            assert_eq!(run.outcome.is_err(), run.method.id == "explicit-euler");
+        }
+        let implicit_euler = find_method("implicit-euler").unwrap();
+        let (_, errors) = comparison.work_precision(implicit_euler, WorkMeasure::WallTime);
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn problem_without_reference_is_rejected() {
+        let mut decay: BenchmarkProblem = problem("exponential-decay").unwrap();
+        decay.reference = None;
+        let comparison = MethodComparison::run(&decay, &methods(&["rk4"]), &small_settings());
+
+        assert!(
+            matches!(comparison, Err(ComparisonError::NoReference(id)) if id == "exponential-decay")
+        );
+    }
+
+    #[test]
+    fn reports_list_every_run() {
+        let comparison = decay_comparison();
+
+        let markdown: String = to_markdown(&comparison);
+        assert!(markdown.starts_with("# Work-precision: exponential-decay\n"));
+        assert!(markdown.contains("| method | setting | steps |"));
+        assert!(markdown.contains("| rk4 | h=5e-2 | 20 | 0 | 80 |"));
+        assert_eq!(
+            markdown
+                .lines()
+                .filter(|line| line.starts_with('|'))
+                .count(),
+            2 + 9
+        );
+
+        let csv: String = to_csv(&comparison);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 9);
+        assert_eq!(
+            lines[0],
+            "problem,method,setting,steps,rejected,RHS evals,Jac evals,error at tf,time [ms],status"
+        );
+        assert!(lines[4].starts_with("exponential-decay,rk4,h=5e-2,20,0,80,0,"));
+        assert!(lines
+            .iter()
+            .skip(1)
+            .all(|line| line.split(',').count() == 10));
+
+        let html: String = to_html(&comparison);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("<tr>").count(), 1 + 9);
+        assert!(html.contains("<th>error at tf</th>"));
+        // The chart is inlined, with one legend entry per method
+        let chart: &str = &html[html.find("<figure>\n<svg").unwrap()..];
+        assert!(chart.contains("</svg>\n</figure>"));
+        for method in ["Explicit Euler", "RK4", "Dormand-Prince"] {
+            assert!(chart.contains(method), "{} is not in the chart", method);
+        }
+    }
+
+    #[test]
+    fn failures_are_shown_in_reports() {
+        let stiff: BenchmarkProblem = problem("prothero-robinson").unwrap();
+        let settings = ComparisonSettings {
+            num_steps: vec![100],
+            ..Default::default()
+        };
+        let comparison =
+            MethodComparison::run(&stiff, &methods(&["explicit-euler"]), &settings).unwrap();
+
+        assert!(to_markdown(&comparison).contains("1 runs, 1 failed."));
+        assert!(to_csv(&comparison)
+            .lines()
+            .nth(1)
+            .unwrap()
+            .contains(",,,,,,failed: "));
+        let html: String = to_html(&comparison);
+        assert!(html.contains("<tr class=\"failed\">"));
+        // Nothing to draw
+        assert!(!html.contains("<svg"));
+    }
+
+    #[test]
+    fn report_format_follows_the_extension() {
+        let comparison = decay_comparison();
+        for (extension, expected) in [
+            ("md", to_markdown(&comparison)),
+            ("csv", to_csv(&comparison)),
+            ("html", to_html(&comparison)),
+        ] {
+            let file_path = temp_path(&format!("report.{}", extension));
+            write_report(&comparison, &file_path).unwrap();
+            assert_eq!(std::fs::read_to_string(&file_path).unwrap(), expected);
+            std::fs::remove_file(&file_path).unwrap();
+        }
+
+        assert!(matches!(
+            write_report(&comparison, temp_path("report.txt")),
+            Err(ComparisonError::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn work_precision_plot_has_one_curve_per_method() {
+        let plot = decay_comparison().work_precision_plot(WorkMeasure::RhsEvaluations);
+
+        assert_eq!(plot.x_label, "RHS evaluations");
+        assert_eq!(plot.series.len(), 3);
+        assert_eq!(plot.series[1].name, "RK4");
+        assert_eq!(plot.series[1].x, vec![80.0, 160.0, 320.0]);
+    }
+}
@@ -0,0 +1,182 @@
+use convergence::{fit_order, halving_step_sizes, ConvergenceStudy, ErrorNorm, GlobalErrors};
+use method_registry::METHODS;
+use numerical_methods_lib::ode_solvers::{
+    convergence, method_registry, ode_error, ode_solution, ode_solver, ode_system,
+};
+use ode_error::OdeError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const LEVELS: usize = 5;
+    const ORDER_TOLERANCE: f64 = 0.1;
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            tolerance: 1e-12,
+            ..Default::default()
+        }
+    }
+
+    /// Observed orders of `method` on y' = -20 t y^2, whose solution is 1 / (1 + 10 t^2).
+    fn quadratic_decay_orders(method: &str, initial_step: f64) -> [f64; 3] {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let study = ConvergenceStudy::run(
+            method,
+            &system,
+            &[INITIAL_SOLUTION],
+            |t| vec![INITIAL_SOLUTION / (1.0 + 10.0 * INITIAL_SOLUTION * t * t)],
+            &solver_params(),
+            &halving_step_sizes(initial_step, LEVELS),
+        )
+        .unwrap();
+        [ErrorNorm::Max, ErrorNorm::L2, ErrorNorm::FinalTime].map(|norm| study.observed_order(norm))
+    }
+
+    fn assert_order(method: &str, initial_step: f64, expected: f64) {
+        for order in quadratic_decay_orders(method, initial_step) {
+            assert!(
+                (order - expected).abs() < ORDER_TOLERANCE,
+                "{}: observed order {} instead of {}",
+                method,
+                order,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn explicit_euler_is_first_order() {
+        assert_order("explicit-euler", 0.01, 1.0);
+    }
+
+    #[test]
+    fn heun_is_second_order() {
+        assert_order("heun", 0.02, 2.0);
+    }
+
+    #[test]
+    fn runge_kutta4_is_fourth_order() {
+        assert_order("rk4", 0.05, 4.0);
+    }
+
+    #[test]
+    fn implicit_euler_is_first_order() {
+        assert_order("implicit-euler", 0.01, 1.0);
+    }
+
+    #[test]
+    fn every_fixed_step_method_reaches_its_order() {
+        // x' = v, v' = -x, solved by x = cos t, v = -sin t
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+
+        for method in METHODS.iter().filter(|method| !method.adaptive) {
+            let study = ConvergenceStudy::run(
+                method.id,
+                &system,
+                &[1.0, 0.0],
+                |t| vec![t.cos(), -t.sin()],
+                &solver_params(),
+                &halving_step_sizes(0.1, LEVELS),
+            )
+            .unwrap();
+            let order: f64 = study.observed_order(ErrorNorm::Max);
+            assert!(
+                (order - method.order as f64).abs() < 2.0 * ORDER_TOLERANCE,
+                "{}: observed order {} instead of {}",
+                method.id,
+                order,
+                method.order
+            );
+            for successive in study.successive_orders(ErrorNorm::Max) {
+                assert!(
+                    (successive - method.order as f64).abs() < 0.5,
+                    "{}",
+                    method.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn global_errors_of_a_known_solution() {
+        let mut solution = ode_solution::OdeSolution::new(0.0, &[3.0, 0.0]);
+        solution.push(1.0, vec![0.0, 4.0]);
+        solution.push(3.0, vec![0.0, 0.0]);
+        // Pointwise errors 3, 4 and 0 against the zero solution
+        let errors = GlobalErrors::new(&solution, |_t| vec![0.0, 0.0]);
+
+        assert_eq!(errors.max, 4.0);
+        assert_eq!(errors.final_time, 0.0);
+        // 0.5 * 1 * (9 + 16) + 0.5 * 2 * (16 + 0)
+        assert!((errors.l2 - 28.5f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fitted_order_of_exact_power_law() {
+        let step_sizes: Vec<f64> = halving_step_sizes(0.1, 4);
+        let errors: Vec<f64> = step_sizes.iter().map(|h| 7.0 * h.powi(3)).collect();
+
+        assert_eq!(step_sizes, vec![0.1, 0.05, 0.025, 0.0125]);
+        assert!((fit_order(&step_sizes, &errors) - 3.0).abs() < 1e-12);
+        assert!(fit_order(&step_sizes, &[1.0, 0.0, 0.0, 0.0]).is_nan());
+        assert!(fit_order(&[0.1], &[1.0]).is_nan());
+    }
+
+    #[test]
+    fn study_needs_two_step_sizes() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let study = ConvergenceStudy::run(
+            "rk4",
+            &system,
+            &[INITIAL_SOLUTION],
+            |t| vec![(-t).exp()],
+            &solver_params(),
+            &[0.1],
+        );
+
+        assert!(matches!(
+            study,
+            Err(OdeError::InvalidParameter {
+                name: "step_sizes",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn study_rejects_adaptive_methods() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        for method in METHODS.iter().filter(|method| method.adaptive) {
+            let study = ConvergenceStudy::run(
+                method.id,
+                &system,
+                &[INITIAL_SOLUTION],
+                |t| vec![(-t).exp()],
+                &solver_params(),
+                &halving_step_sizes(0.1, LEVELS),
+            );
+
+            assert!(
+                matches!(
+                    study,
+                    Err(OdeError::InvalidParameter { name: "method", .. })
+                ),
+                "{}",
+                method.id
+            );
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use numerical_methods_lib::ode_solvers::{
+    bdf_method, dormand_prince, events, explicit_euler_method, heun_method, implicit_euler_method,
+    ode_error, ode_solution, ode_solver, ode_system, runge_kutta4,
+};
+use ode_solution::OdeSolution;
+use ode_solver::Solve;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    // y' = -20 t y^2, y(0) = 1 with exact solution 1 / (1 + 10 t^2)
+    fn exact(t: f64) -> f64 {
+        1.0 / (1.0 + 10.0 * t * t)
+    }
+
+    fn solve(
+        method: &str,
+        time_step: f64,
+        tolerance: f64,
+        t_eval: &[f64],
+    ) -> Result<OdeSolution, ode_error::OdeError> {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step,
+            tolerance,
+            rel_tolerance: tolerance,
+            dense_output: true,
+            ..Default::default()
+        };
+        let solver = Box::new(
+            ode_solver::OdeSolver::new("ODE Solver Dense Output Test", &system, &solver_params)
+                .with_t_eval(t_eval),
+        );
+
+        match method {
+            "Explicit Euler" => {
+                explicit_euler_method::ExplicitEulerSolver { solver }.solve(&[INITIAL_SOLUTION])
+            }
+            "Heun" => heun_method::HeunSolver { solver }.solve(&[INITIAL_SOLUTION]),
+            "RK4" => runge_kutta4::RungeKuttaSolver { solver }.solve(&[INITIAL_SOLUTION]),
+            "Implicit Euler" => {
+                implicit_euler_method::ImplicitEulerSolver { solver }.solve(&[INITIAL_SOLUTION])
+            }
+            "Dormand Prince" => {
+                dormand_prince::DormandPrinceSolver { solver }.solve(&[INITIAL_SOLUTION])
+            }
+            "BDF" => bdf_method::BdfSolver { solver }.solve(&[INITIAL_SOLUTION]),
+            _ => unreachable!(),
+        }
+    }
+
+    // Largest interpolation error at a fraction `theta` of every step
+    fn interior_error(solution: &OdeSolution, theta: f64) -> f64 {
+        solution
+            .times
+            .windows(2)
+            .map(|t| {
+                let t_inner = t[0] + theta * (t[1] - t[0]);
+                (solution.interpolate(t_inner).unwrap()[0] - exact(t_inner)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn dense_output_is_continuous_and_matches_the_steps() {
+        for method in [
+            "Explicit Euler",
+            "Heun",
+            "RK4",
+            "Implicit Euler",
+            "Dormand Prince",
+            "BDF",
+        ] {
+            let solution = solve(method, TIME_STEP, 1e-6, &[]).unwrap();
+            let dense = solution.dense_output.as_ref().unwrap();
+
+            assert_eq!(dense.pieces.len(), solution.len() - 1, "{}", method);
+            for (i, piece) in dense.pieces.iter().enumerate() {
+                assert_eq!(piece.t_start, solution.times[i]);
+                assert_eq!(piece.t_end, solution.times[i + 1]);
+                assert_eq!(piece.evaluate(piece.t_start), solution.states[i]);
+                let end = piece.evaluate(piece.t_end)[0];
+                assert!(
+                    (end - solution.states[i + 1][0]).abs() < 1e-13,
+                    "{} step {} ends at {} instead of {}",
+                    method,
+                    i,
+                    end,
+                    solution.states[i + 1][0]
+                );
+            }
+            assert_eq!(solution.interpolate(T_INITIAL).unwrap(), [INITIAL_SOLUTION]);
+            let at_final = solution.interpolate(T_FINAL).unwrap()[0];
+            assert!((at_final - solution.states.last().unwrap()[0]).abs() < 1e-13);
+            assert_eq!(solution.interpolate(T_FINAL + 0.1), None);
+            assert_eq!(solution.interpolate(T_INITIAL - 0.1), None);
+        }
+    }
+
+    #[test]
+    fn rk4_continuous_extension_keeps_the_order() {
+        // A third order extension has a local error of O(h^4), like the global error of the
+        // steps, so the interpolated values converge at fourth order too
+        let coarse = interior_error(&solve("RK4", 0.02, 1e-6, &[]).unwrap(), 0.25);
+        let fine = interior_error(&solve("RK4", 0.01, 1e-6, &[]).unwrap(), 0.25);
+        let observed_order = (coarse / fine).log2();
+
+        assert!(
+            (observed_order - 4.0).abs() < 0.3,
+            "observed {}",
+            observed_order
+        );
+    }
+
+    #[test]
+    fn dormand_prince_interpolant_keeps_the_tolerance() {
+        let solution = solve("Dormand Prince", TIME_STEP, 1e-10, &[]).unwrap();
+        let step_error = solution
+            .times
+            .iter()
+            .zip(&solution.states)
+            .map(|(&t, y)| (y[0] - exact(t)).abs())
+            .fold(0.0, f64::max);
+
+        assert!(step_error < 1e-8);
+        assert!(interior_error(&solution, 0.5) < 1e-8);
+        assert!(interior_error(&solution, 0.25) < 1e-8);
+    }
+
+    #[test]
+    fn solution_at_t_eval() {
+        let t_eval: Vec<f64> = (0..=40).map(|i| 0.025 * i as f64).collect();
+
+        for (method, tol) in [("RK4", 1e-3), ("Dormand Prince", 1e-6), ("BDF", 1e-3)] {
+            let solution = solve(method, TIME_STEP, 1e-7, &t_eval).unwrap();
+
+            assert_eq!(solution.times, t_eval);
+            for (&t, y) in solution.times.iter().zip(&solution.states) {
+                assert!((y[0] - exact(t)).abs() < tol, "{} at {}", method, t);
+            }
+            // The steps are still counted
+            assert!(solution.stats.accepted_steps > 0);
+        }
+    }
+
+    #[test]
+    fn dense_output_is_only_kept_on_request() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams::default();
+        let t_eval = [0.5 * T_FINAL, T_FINAL];
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dense Output Test", &system, &solver_params);
+
+        let plain = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+        assert!(plain.dense_output.is_none());
+        assert_eq!(plain.interpolate(0.5 * T_FINAL), None);
+
+        let resampled = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver.with_t_eval(&t_eval)),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+        assert!(resampled.dense_output.is_none());
+        assert_eq!(resampled.times, t_eval);
+        assert_eq!(resampled.states.last(), plain.states.last());
+    }
+
+    #[test]
+    fn t_eval_backwards_in_time() {
+        // y' = -y from y(1) = exp(-1) back to t = 0
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_FINAL, T_INITIAL),
+            tolerance: 1e-10,
+            rel_tolerance: 1e-10,
+            ..Default::default()
+        };
+        let t_eval = [0.9, 0.55, 0.3, 0.0];
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dense Output Test", &system, &solver_params)
+                .with_t_eval(&t_eval);
+
+        let solution = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[(-T_FINAL).exp()])
+        .unwrap();
+
+        assert_eq!(solution.times, t_eval);
+        for (&t, y) in solution.times.iter().zip(&solution.states) {
+            assert!((y[0] - (-t).exp()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn invalid_t_eval_is_rejected() {
+        for t_eval in [vec![0.5, 1.5], vec![0.5, 0.2], vec![f64::NAN]] {
+            let error = solve("RK4", TIME_STEP, 1e-6, &t_eval).unwrap_err();
+            assert!(matches!(
+                error,
+                ode_error::OdeError::InvalidParameter { name: "t_eval", .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn events_are_located_within_large_steps() {
+        // y = [sin t, cos t] with a loose tolerance, so DP takes steps of about one unit
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 10.0),
+            tolerance: 1e-5,
+            rel_tolerance: 1e-5,
+            dense_output: true,
+            ..Default::default()
+        };
+        let zeros = [events::Event::new(
+            |_t: f64, y: &[f64]| y[0],
+            events::EventAction::Record,
+        )];
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dense Output Test", &system, &solver_params)
+                .with_events(&zeros);
+
+        let solution = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[0.0, 1.0])
+        .unwrap();
+
+        let max_step = solution
+            .times
+            .windows(2)
+            .map(|t| t[1] - t[0])
+            .fold(0.0, f64::max);
+        assert!(max_step > 0.3, "largest step {}", max_step);
+        assert_eq!(solution.events.len(), 3);
+        for (n, record) in solution.events.iter().enumerate() {
+            let t_exact = (n + 1) as f64 * std::f64::consts::PI;
+            assert!((record.t - t_exact).abs() < 1e-4);
+            // The event state comes from the same interpolant as the dense output
+            assert_eq!(Some(record.state.clone()), solution.interpolate(record.t));
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use numerical_methods_lib::ode_solvers::{dormand_prince, ode_solution, ode_solver, ode_system};
+use numerical_methods_lib::output::solution_file::{write_solution_file, RunMetadata};
+use ode_solver::Solve;
+use std::fs::read_to_string;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const TOLERANCE: f64 = 1e-8; // tol
+
+    const FILE_PATH: &str = "solver_results/dormand_prince_solver_test.csv";
+
+    fn test_fixture() -> ode_solution::OdeSolution {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dormand Prince Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+
+        dormand_prince_solver.solve(&[INITIAL_SOLUTION]).unwrap()
+    }
+
+    #[test]
+    fn dormand_prince_initial_value() {
+        let solution = test_fixture();
+
+        assert_eq!(solution.states[0][0], INITIAL_SOLUTION);
+        assert_eq!(solution.times[0], T_INITIAL);
+    }
+
+    #[test]
+    fn dormand_prince_non_uniform_grid() {
+        let solution = test_fixture();
+
+        assert_eq!(solution.times.len(), solution.states.len());
+        assert_eq!(*solution.times.last().unwrap(), T_FINAL);
+        assert!(solution.times.windows(2).all(|t| t[1] > t[0]));
+
+        let steps: Vec<f64> = solution.times.windows(2).map(|t| t[1] - t[0]).collect();
+        let (min_step, max_step) = steps
+            .iter()
+            .fold((f64::MAX, 0.0_f64), |(lo, hi), h| (lo.min(*h), hi.max(*h)));
+        assert!(max_step > 1.5 * min_step);
+    }
+
+    #[test]
+    fn dormand_prince_matches_exact_solution() {
+        let solution = test_fixture();
+
+        // Exact solution of y' = -20 t y^2, y(0) = 1
+        for (t, y) in solution.times.iter().zip(&solution.states) {
+            let exact = 1.0 / (1.0 + 10.0 * t * t);
+            assert!((y[0] - exact).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn dormand_prince_recovers_from_nan_trial_step() {
+        // y' = -y, undefined for negative y: the first trial step overshoots below zero
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = if y[0] < 0.0 { f64::NAN } else { -y[0] }
+        }; // function: f(t,y)
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 10.0),
+            time_step: 5.0,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dormand Prince Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = dormand_prince_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let (tf, y) = solution.last().unwrap();
+        assert_eq!(tf, 10.0);
+        assert!((y[0] - (-10.0_f64).exp()).abs() < 1e-8);
+        assert!(solution.stats.rejected_steps > 0);
+    }
+
+    #[test]
+    fn write_solution_initial_val() {
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
+
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dormand Prince Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = dormand_prince_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let metadata = RunMetadata::new("Dormand-Prince", &solver_params);
+        write_solution_file(FILE_PATH, &solution, &metadata).unwrap();
+
+        let contents = read_to_string(FILE_PATH).unwrap();
+        let mut rows = contents.lines().filter(|line| !line.starts_with('#'));
+        assert_eq!(rows.next().unwrap(), "t,y0");
+        let first_row: Vec<f64> = rows
+            .next()
+            .unwrap()
+            .split(',')
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect();
+        assert_eq!(first_row, vec![T_INITIAL, INITIAL_SOLUTION]);
+    }
+
+    #[test]
+    fn dormand_prince_large_first_step_ends_at_final_time() {
+        let f = |_x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        // The first step is clamped to tf - t0, where t0 + (tf - t0) rounds away from tf,
+        // and some spans cross zero
+        let spans = (0..200)
+            .map(|i| (0.0137 * i as f64 - 1.0, 0.852056 + 0.0091 * i as f64))
+            .chain([(0.2968, 0.852056)]);
+        for (t0, tf) in spans {
+            let solver_params = ode_solver::OdeSolverParams {
+                time_span: ode_solver::TimeSpan::new(t0, tf),
+                time_step: 100.0,
+                ..Default::default()
+            };
+            let solver = ode_solver::OdeSolver::new(
+                "ODE Solver Dormand Prince Test",
+                &system,
+                &solver_params,
+            );
+            let solution = dormand_prince::DormandPrinceSolver {
+                solver: Box::new(solver),
+            }
+            .solve(&[0.0])
+            .unwrap();
+
+            assert_eq!(*solution.times.last().unwrap(), tf);
+            assert!((solution.states.last().unwrap()[0] - (tf - t0)).abs() < 1e-12);
+        }
+    }
+}
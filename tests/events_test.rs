@@ -0,0 +1,274 @@
+use events::{Event, EventAction, EventDirection};
+use numerical_methods_lib::ode_solvers::{
+    adams_bashforth, bdf_method, dormand_prince, events, implicit_euler_method, ode_solution,
+    ode_solver, ode_system, runge_kutta4,
+};
+use ode_solution::{OdeSolution, SolverStatus};
+use ode_solver::Solve;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.5; // tf
+    const TIME_STEP: f64 = 0.01; // h
+    const GRAVITY: f64 = 9.81;
+    const HEIGHT: f64 = 1.0; // initial height of the ball
+    const RESTITUTION: f64 = 0.9;
+
+    // y = [height, velocity] of a ball falling under gravity
+    fn bouncing_ball(solve: impl Fn(&ode_solver::OdeSolver) -> OdeSolution) -> OdeSolution {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -GRAVITY;
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: 1e-10,
+            rel_tolerance: 1e-10,
+            ..Default::default()
+        };
+        let bounce = [Event::new(
+            |_t: f64, y: &[f64]| y[0],
+            EventAction::reset(|_t: f64, y: &mut [f64]| y[1] *= -RESTITUTION),
+        )
+        .with_direction(EventDirection::Decreasing)];
+        let solver = ode_solver::OdeSolver::new("ODE Solver Events Test", &system, &solver_params)
+            .with_events(&bounce);
+        solve(&solver)
+    }
+
+    // Bounce times of the ball dropped from HEIGHT at rest
+    fn exact_bounce_times(count: usize) -> Vec<f64> {
+        let mut times: Vec<f64> = vec![(2.0 * HEIGHT / GRAVITY).sqrt()];
+        let mut speed: f64 = GRAVITY * times[0];
+        while times.len() < count {
+            speed *= RESTITUTION;
+            times.push(times.last().unwrap() + 2.0 * speed / GRAVITY);
+        }
+        times
+    }
+
+    fn check_bounces(solution: &OdeSolution, tol: f64) {
+        let exact = exact_bounce_times(solution.events.len());
+        // At 0.45 s and 1.26 s
+        assert_eq!(solution.events.len(), 2);
+        for (record, t_exact) in solution.events.iter().zip(exact) {
+            assert_eq!(record.event, 0);
+            assert!(
+                (record.t - t_exact).abs() < tol,
+                "bounce at {} expected {}",
+                record.t,
+                t_exact
+            );
+            assert!(record.state[0].abs() < tol * GRAVITY);
+            assert!(record.state[1] < 0.0);
+        }
+        // The state before and after each reset are both stored at the event time
+        for record in &solution.events {
+            let index = solution.times.iter().position(|&t| t == record.t).unwrap();
+            assert_eq!(solution.times[index + 1], record.t);
+            assert_eq!(solution.states[index], record.state);
+            assert!((solution.states[index + 1][1] + RESTITUTION * record.state[1]).abs() < 1e-12);
+        }
+        assert_eq!(solution.status, SolverStatus::Success);
+        assert_eq!(*solution.times.last().unwrap(), T_FINAL);
+        assert!(solution.component(0).iter().all(|&h| h > -1e-8));
+    }
+
+    #[test]
+    fn bouncing_ball_with_runge_kutta() {
+        let solution = bouncing_ball(|solver| {
+            runge_kutta4::RungeKuttaSolver {
+                solver: Box::new(*solver),
+            }
+            .solve(&[HEIGHT, 0.0])
+            .unwrap()
+        });
+        // Piecewise quadratic trajectory, so the Hermite interpolant is exact
+        check_bounces(&solution, 1e-10);
+    }
+
+    #[test]
+    fn bouncing_ball_with_dormand_prince() {
+        let solution = bouncing_ball(|solver| {
+            dormand_prince::DormandPrinceSolver {
+                solver: Box::new(*solver),
+            }
+            .solve(&[HEIGHT, 0.0])
+            .unwrap()
+        });
+        check_bounces(&solution, 1e-10);
+    }
+
+    #[test]
+    fn bouncing_ball_with_multistep_solvers() {
+        let bdf = bouncing_ball(|solver| {
+            bdf_method::BdfSolver {
+                solver: Box::new(*solver),
+            }
+            .solve(&[HEIGHT, 0.0])
+            .unwrap()
+        });
+        check_bounces(&bdf, 1e-6);
+
+        let adams = bouncing_ball(|solver| {
+            adams_bashforth::AdamsBashforthSolver {
+                solver: Box::new(*solver),
+                order: 3,
+            }
+            .solve(&[HEIGHT, 0.0])
+            .unwrap()
+        });
+        check_bounces(&adams, 1e-10);
+    }
+
+    #[test]
+    fn terminal_event_stops_the_integration() {
+        // y' = 1 - y, y(0) = 0 reaches 1/2 at ln 2
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0 - y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 5.0),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let half_way = [Event::new(
+            |_t: f64, y: &[f64]| y[0] - 0.5,
+            EventAction::Terminate,
+        )];
+        let solver = ode_solver::OdeSolver::new("ODE Solver Events Test", &system, &solver_params)
+            .with_events(&half_way);
+
+        let solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[0.0])
+        .unwrap();
+        let (t, y) = solution.last().unwrap();
+
+        assert_eq!(solution.status, SolverStatus::EventTerminated);
+        assert_eq!(solution.events.len(), 1);
+        assert_eq!(solution.events[0].t, t);
+        assert!((t - 2.0_f64.ln()).abs() < 1e-8);
+        assert!((y[0] - 0.5).abs() < 1e-8);
+        assert!(solution.times.iter().all(|&s| s <= t));
+    }
+
+    #[test]
+    fn recorded_events_do_not_change_the_solution() {
+        // y = [sin t, cos t], zeros of sin t at multiples of pi
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 10.0),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let zeros = [
+            Event::new(|_t: f64, y: &[f64]| y[0], EventAction::Record),
+            Event::new(|_t: f64, y: &[f64]| y[0], EventAction::Record)
+                .with_direction(EventDirection::Increasing),
+        ];
+        let plain = ode_solver::OdeSolver::new("ODE Solver Events Test", &system, &solver_params);
+        let with_events = plain.with_events(&zeros);
+
+        let reference = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(plain),
+        }
+        .solve(&[0.0, 1.0])
+        .unwrap();
+        let solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(with_events),
+        }
+        .solve(&[0.0, 1.0])
+        .unwrap();
+
+        assert_eq!(solution.times, reference.times);
+        assert_eq!(solution.states, reference.states);
+        // pi, 2 pi, 3 pi for the first event, only the upward crossing 2 pi for the second
+        let found: Vec<(usize, f64)> = solution.events.iter().map(|e| (e.event, e.t)).collect();
+        let expected = [
+            (0, std::f64::consts::PI),
+            (0, 2.0 * std::f64::consts::PI),
+            (1, 2.0 * std::f64::consts::PI),
+            (0, 3.0 * std::f64::consts::PI),
+        ];
+        assert_eq!(found.len(), expected.len());
+        for ((event, t), (expected_event, expected_t)) in found.into_iter().zip(expected) {
+            assert_eq!(event, expected_event);
+            assert!((t - expected_t).abs() < 1e-8);
+        }
+        // Located on the continuous extension of RK4, without extra evaluations of f
+        assert_eq!(solution.stats.num_rhs_evals, reference.stats.num_rhs_evals);
+    }
+
+    #[test]
+    fn unlocated_event_is_recorded_at_the_step_end() {
+        let f = |_x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 1.0),
+            time_step: 0.1,
+            ..Default::default()
+        };
+        // y = t crosses 0.55 within the step from 0.5 to 0.6, but g is only defined at
+        // the grid points, so the crossing cannot be located inside the step
+        let on_grid = [Event::new(
+            |t: f64, y: &[f64]| {
+                if (10.0 * t - (10.0 * t).round()).abs() < 1e-9 {
+                    y[0] - 0.55
+                } else {
+                    f64::NAN
+                }
+            },
+            EventAction::Record,
+        )];
+        let solver = ode_solver::OdeSolver::new("ODE Solver Events Test", &system, &solver_params)
+            .with_events(&on_grid);
+
+        let solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[0.0])
+        .unwrap();
+
+        assert_eq!(solution.events.len(), 1);
+        assert!((solution.events[0].t - 0.6).abs() < 1e-12);
+        assert!(solution.times.contains(&solution.events[0].t));
+        assert_eq!(solution.status, SolverStatus::Success);
+    }
+
+    #[test]
+    fn events_work_backwards_in_time() {
+        // y' = 1 - y backwards from y(2) = 1 - exp(-2), crosses 1/2 at ln 2
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0 - y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(2.0, T_INITIAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let half_way = [
+            Event::new(|_t: f64, y: &[f64]| y[0] - 0.5, EventAction::Terminate)
+                .with_direction(EventDirection::Decreasing),
+        ];
+        let solver = ode_solver::OdeSolver::new("ODE Solver Events Test", &system, &solver_params)
+            .with_events(&half_way);
+
+        let solution = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[1.0 - (-2.0_f64).exp()])
+        .unwrap();
+
+        assert_eq!(solution.status, SolverStatus::EventTerminated);
+        assert!((solution.events[0].t - 2.0_f64.ln()).abs() < 1e-2);
+    }
+}
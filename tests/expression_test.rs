@@ -0,0 +1,210 @@
+use numerical_methods_lib::differentiation::{auto_diff::AutoDiffClosure, dual_numbers::Dual};
+use numerical_methods_lib::expressions::{
+    expression_error::ExpressionError, expression_system::ExpressionSystem,
+    parser::parse_expression,
+};
+use numerical_methods_lib::ode_solvers::{implicit_euler_method, ode_solver, ode_system};
+use ode_solver::Solve;
+use ode_system::OdeSystem;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    fn eval(text: &str) -> f64 {
+        parse_expression(text, &["t", "y"])
+            .unwrap()
+            .eval(&[0.5, 2.0])
+    }
+
+    #[test]
+    fn operators_follow_the_usual_precedence() {
+        assert_eq!(eval("1 + 2*3 - 4/2"), 5.0);
+        assert_eq!(eval("-2^2"), -4.0);
+        assert_eq!(eval("2^3^2"), 512.0);
+        assert_eq!(eval("2**-1"), 0.5);
+        assert_eq!(eval("(1 + 2)*3"), 9.0);
+        assert_eq!(eval("-20*t*y^2"), -40.0);
+        assert_eq!(eval("1.5e2 + .5 - 2E-1"), 150.3);
+        assert!((eval("sin(pi/2) + ln(e) + sqrt(y^2) + abs(-t)") - 4.5).abs() < 1e-15);
+    }
+
+    #[test]
+    fn syntax_errors_report_the_position() {
+        let syntax_position = |text: &str| match parse_expression(text, &["t", "y"]) {
+            Err(ExpressionError::Syntax { position, .. }) => position,
+            other => panic!("expected a syntax error for `{}`, got {:?}", text, other),
+        };
+        assert_eq!(syntax_position("2*(t + 1"), 8);
+        assert_eq!(syntax_position("2 +"), 3);
+        assert_eq!(syntax_position("t y"), 2);
+        assert_eq!(syntax_position("t + 1)"), 5);
+        assert_eq!(syntax_position("3 * # 2"), 4);
+
+        assert_eq!(
+            parse_expression("t + z", &["t", "y"]),
+            Err(ExpressionError::UnknownName {
+                text: "t + z".to_string(),
+                name: "z".to_string()
+            })
+        );
+        assert!(matches!(
+            parse_expression("erf(t)", &["t"]),
+            Err(ExpressionError::UnknownFunction { .. })
+        ));
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let names = ["x", "a"];
+        let expressions = [
+            "x^3 - 2*x + 1",
+            "a*x/(1 + x^2)",
+            "sin(x)*cos(2*x) + tan(x/3)",
+            "exp(-a*x)*sqrt(x)",
+            "ln(x) + log10(x)*a",
+            "x^x",
+            "2^x + x^a",
+            "asin(x/2) + acos(x/3) + atan(x)",
+            "sinh(x) - cosh(x)*tanh(x)",
+            "abs(x - 2)*x",
+        ];
+        let (x, a, h) = (0.7, 1.3, 1e-6);
+        for text in expressions {
+            let expr = parse_expression(text, &names).unwrap();
+            let derivative = expr.derivative(0);
+            let central = (expr.eval(&[x + h, a]) - expr.eval(&[x - h, a])) / (2.0 * h);
+            assert!(
+                (derivative.eval(&[x, a]) - central).abs() < 1e-7,
+                "d/dx {} = {}",
+                text,
+                derivative.format(&names)
+            );
+            // The printed derivative parses back to the same expression
+            assert_eq!(
+                parse_expression(&derivative.format(&names), &names).unwrap(),
+                derivative
+            );
+        }
+    }
+
+    #[test]
+    fn derivatives_are_simplified() {
+        let names = ["t", "y"];
+        let derivative = |text: &str| {
+            parse_expression(text, &names)
+                .unwrap()
+                .derivative(1)
+                .format(&names)
+        };
+        assert_eq!(derivative("-20*t*y^2"), "-20*t*(2*y)");
+        assert_eq!(derivative("3*y + t"), "3");
+        assert_eq!(derivative("sin(t)"), "0");
+        assert_eq!(derivative("exp(2*y)"), "2*exp(2*y)");
+    }
+
+    #[test]
+    fn system_matches_closure_and_jacobian() {
+        let lorenz = ExpressionSystem::new(
+            &[
+                "dx = sigma*(y - x)",
+                "y' = x*(rho - z) - y",
+                "dz/dt = x*y - beta*z",
+            ],
+            &[("sigma", 10.0), ("rho", 28.0), ("beta", 8.0 / 3.0)],
+        )
+        .unwrap();
+        let reference = AutoDiffClosure::new(3, |_t: Dual, y: &[Dual], dydt: &mut [Dual]| {
+            dydt[0] = 10.0 * (y[1] - y[0]);
+            dydt[1] = y[0] * (28.0 - y[2]) - y[1];
+            dydt[2] = y[0] * y[1] - 8.0 / 3.0 * y[2];
+        });
+        assert_eq!(lorenz.dimension(), 3);
+        assert_eq!(lorenz.state_names(), vec!["x", "y", "z"]);
+
+        let state = [1.5, -0.5, 20.0];
+        let (mut dydt, mut expected) = ([0.0; 3], [0.0; 3]);
+        lorenz.rhs(0.0, &state, &mut dydt);
+        reference.rhs(0.0, &state, &mut expected);
+        for (value, expected) in dydt.iter().zip(&expected) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+
+        let (mut jac, mut expected_jac) = ([0.0; 9], [0.0; 9]);
+        lorenz.jacobian(0.0, &state, &mut jac);
+        reference.jacobian(0.0, &state, &mut expected_jac);
+        assert_eq!(jac, expected_jac);
+        assert_eq!(lorenz.jacobian_text(1, 2), "-x");
+    }
+
+    #[test]
+    fn parameters_can_be_changed() {
+        let mut decay = ExpressionSystem::scalar("-k*y", &[("k", 1.0)]).unwrap();
+        let mut dydt = [0.0];
+        decay.set_parameter("k", 3.0).unwrap();
+        decay.rhs(0.0, &[2.0], &mut dydt);
+        assert_eq!(dydt[0], -6.0);
+        assert!(decay.set_parameter("y", 1.0).is_err());
+    }
+
+    #[test]
+    fn invalid_equations_are_rejected() {
+        let invalid = |equations: &[&str], parameters: &[(&str, f64)]| {
+            matches!(
+                ExpressionSystem::new(equations, parameters),
+                Err(ExpressionError::InvalidEquation { .. })
+            )
+        };
+        assert!(invalid(&[], &[]));
+        assert!(invalid(&["x + 1"], &[]));
+        assert!(invalid(&["x = 1"], &[]));
+        assert!(invalid(&["dt = 1"], &[]));
+        assert!(invalid(&["dx = 1", "x' = 2"], &[]));
+        assert!(invalid(&["dx = k*x"], &[("x", 1.0)]));
+        assert!(invalid(&["dsin = 1"], &[]));
+        assert!(matches!(
+            ExpressionSystem::new(&["dx = k*x"], &[]),
+            Err(ExpressionError::UnknownName { .. })
+        ));
+    }
+
+    #[test]
+    fn implicit_euler_uses_the_symbolic_jacobian() {
+        let parsed = ExpressionSystem::scalar("-20*t*y^2", &[]).unwrap();
+        let compiled = AutoDiffClosure::new(1, |t: Dual, y: &[Dual], dydt: &mut [Dual]| {
+            dydt[0] = -20.0 * t * y[0] * y[0]
+        });
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solve = |system: &dyn ode_system::OdeSystem| {
+            implicit_euler_method::ImplicitEulerSolver {
+                solver: Box::new(ode_solver::OdeSolver::new(
+                    "ODE Solver Implicit Euler Expression Test",
+                    system,
+                    &solver_params,
+                )),
+            }
+            .solve(&[INITIAL_SOLUTION])
+            .unwrap()
+        };
+
+        let parsed_solution = solve(&parsed);
+        let compiled_solution = solve(&compiled);
+        assert_eq!(parsed_solution.times, compiled_solution.times);
+        for (parsed, compiled) in parsed_solution.states.iter().zip(&compiled_solution.states) {
+            assert!((parsed[0] - compiled[0]).abs() < 1e-12);
+        }
+        assert_eq!(
+            parsed_solution.stats.num_newton_iters,
+            compiled_solution.stats.num_newton_iters
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use numerical_methods_lib::differentiation::finite_difference::{
+    DifferenceScheme, FiniteDifferenceJacobian, FiniteDifferenceSystem, SparsityPattern,
+};
+use numerical_methods_lib::ode_solvers::{
+    implicit_euler_method, ode_error::OdeError, ode_solver, ode_system,
+};
+use numerical_methods_lib::root_finders::newton_raphson_method::{
+    newton_raphson_finite_difference_solve, NewtonOptions,
+};
+use ode_solver::Solve;
+use ode_system::OdeSystem;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 0.1; // tf
+    const TIME_STEP: f64 = 0.01; // h
+    const TOLERANCE: f64 = 1e-10; // tol
+    const MAX_ITERATIONS: i32 = 50;
+    const NUM_POINTS: usize = 40;
+
+    // Method of lines for u_t = u_xx - u^2 on (0, 1) with u = 0 on the boundary
+    struct ReactionDiffusion;
+
+    impl OdeSystem for ReactionDiffusion {
+        fn dimension(&self) -> usize {
+            NUM_POINTS
+        }
+
+        fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+            let dx: f64 = 1.0 / (NUM_POINTS + 1) as f64;
+            for i in 0..NUM_POINTS {
+                let left: f64 = if i == 0 { 0.0 } else { y[i - 1] };
+                let right: f64 = if i == NUM_POINTS - 1 { 0.0 } else { y[i + 1] };
+                dydt[i] = (left - 2.0 * y[i] + right) / (dx * dx) - y[i] * y[i];
+            }
+        }
+
+        fn jacobian(&self, _t: f64, y: &[f64], jac: &mut [f64]) {
+            let dx: f64 = 1.0 / (NUM_POINTS + 1) as f64;
+            jac.fill(0.0);
+            for i in 0..NUM_POINTS {
+                jac[i * NUM_POINTS + i] = -2.0 / (dx * dx) - 2.0 * y[i];
+                if i > 0 {
+                    jac[i * NUM_POINTS + i - 1] = 1.0 / (dx * dx);
+                }
+                if i < NUM_POINTS - 1 {
+                    jac[i * NUM_POINTS + i + 1] = 1.0 / (dx * dx);
+                }
+            }
+        }
+
+        fn jacobian_rhs_evals(&self) -> usize {
+            0
+        }
+    }
+
+    fn initial_state() -> Vec<f64> {
+        (0..NUM_POINTS)
+            .map(|i| (std::f64::consts::PI * (i + 1) as f64 / (NUM_POINTS + 1) as f64).sin())
+            .collect()
+    }
+
+    fn max_difference(a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .fold(0.0_f64, |acc, (x, y)| acc.max((x - y).abs()))
+    }
+
+    #[test]
+    fn central_differences_are_more_accurate() {
+        let f = |x: &[f64], out: &mut [f64]| {
+            out[0] = x[0].sin() * x[1];
+            out[1] = (x[0] * x[1]).exp();
+        };
+        let x = [0.3, 1.7];
+        let exact = [
+            0.3_f64.cos() * 1.7,
+            0.3_f64.sin(),
+            1.7 * (0.3_f64 * 1.7).exp(),
+            0.3 * (0.3_f64 * 1.7).exp(),
+        ];
+        let mut forward = [0.0; 4];
+        let mut central = [0.0; 4];
+
+        let forward_evals =
+            FiniteDifferenceJacobian::new(DifferenceScheme::Forward).jacobian(f, &x, &mut forward);
+        let central_evals =
+            FiniteDifferenceJacobian::new(DifferenceScheme::Central).jacobian(f, &x, &mut central);
+
+        assert_eq!(forward_evals, 3);
+        assert_eq!(central_evals, 4);
+        assert!(max_difference(&forward, &exact) < 1e-6);
+        assert!(max_difference(&central, &exact) < 1e-9);
+    }
+
+    #[test]
+    fn column_groups_of_a_band() {
+        let pattern = SparsityPattern::banded(NUM_POINTS, 1, 1);
+        let groups = pattern.column_groups();
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[0],
+            (0..NUM_POINTS).step_by(3).collect::<Vec<usize>>()
+        );
+        assert_eq!(
+            SparsityPattern::new(3, &[(0, 0), (1, 1), (2, 2), (0, 2)])
+                .unwrap()
+                .column_groups(),
+            vec![vec![0, 1], vec![2]]
+        );
+        assert_eq!(SparsityPattern::dense(4).column_groups().len(), 4);
+    }
+
+    #[test]
+    fn sparse_jacobian_needs_few_evaluations() {
+        let y = initial_state();
+        let mut exact = vec![0.0; NUM_POINTS * NUM_POINTS];
+        let mut dense = vec![0.0; NUM_POINTS * NUM_POINTS];
+        let mut sparse = vec![0.0; NUM_POINTS * NUM_POINTS];
+        ReactionDiffusion.jacobian(0.0, &y, &mut exact);
+
+        let dense_evals = FiniteDifferenceJacobian::default().ode_jacobian(
+            &ReactionDiffusion,
+            0.0,
+            &y,
+            &mut dense,
+        );
+        let banded = FiniteDifferenceJacobian::default()
+            .with_sparsity(SparsityPattern::banded(NUM_POINTS, 1, 1));
+        let sparse_evals = banded.ode_jacobian(&ReactionDiffusion, 0.0, &y, &mut sparse);
+
+        assert_eq!(dense_evals, NUM_POINTS + 1);
+        assert_eq!(sparse_evals, 4);
+        assert_eq!(sparse_evals, banded.evaluations_per_jacobian(NUM_POINTS));
+        // Entries are of size 2 / dx^2 ~ 3400
+        assert!(max_difference(&sparse, &exact) < 1e-4);
+        assert!(max_difference(&sparse, &dense) < 1e-4);
+    }
+
+    #[test]
+    fn implicit_euler_with_finite_difference_jacobian() {
+        let system = FiniteDifferenceSystem::new(
+            ReactionDiffusion,
+            FiniteDifferenceJacobian::new(DifferenceScheme::Central)
+                .with_sparsity(SparsityPattern::banded(NUM_POINTS, 1, 1)),
+        )
+        .unwrap();
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            max_iters: MAX_ITERATIONS,
+            ..Default::default()
+        };
+
+        let solve = |system: &dyn OdeSystem| {
+            let solver =
+                ode_solver::OdeSolver::new("Implicit Euler Method Test", system, &solver_params);
+            implicit_euler_method::ImplicitEulerSolver {
+                solver: Box::new(solver),
+            }
+            .solve(&initial_state())
+            .unwrap()
+        };
+        let expected = solve(&ReactionDiffusion);
+        let solution = solve(&system);
+
+        let (_, y) = solution.last().unwrap();
+        let (_, y_expected) = expected.last().unwrap();
+        assert!(max_difference(y, y_expected) < 1e-8);
+
+        // The evaluations of the difference quotients are part of the solver's work
+        let steps: usize = solution.len() - 1;
+        assert_eq!(system.jacobian_rhs_evals(), 6);
+        assert_eq!(
+            solution.stats.num_rhs_evals,
+            solution.stats.num_newton_iters + steps + 6 * solution.stats.num_jacobian_evals
+        );
+        assert_eq!(
+            expected.stats.num_rhs_evals,
+            expected.stats.num_newton_iters + steps
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "sparsity pattern of dimension 3 used for a state of dimension 4")]
+    fn pattern_of_another_dimension_is_rejected() {
+        let jacobian = FiniteDifferenceJacobian::new(DifferenceScheme::Forward)
+            .with_sparsity(SparsityPattern::banded(3, 1, 1));
+        let mut jac = [0.0; 16];
+        jacobian.jacobian(
+            |x: &[f64], out: &mut [f64]| out.copy_from_slice(x),
+            &[1.0; 4],
+            &mut jac,
+        );
+    }
+
+    #[test]
+    fn invalid_patterns_are_errors() {
+        assert!(matches!(
+            SparsityPattern::new(3, &[(0, 0), (3, 1)]),
+            Err(OdeError::InvalidParameter {
+                name: "sparsity",
+                ..
+            })
+        ));
+
+        let system = FiniteDifferenceSystem::new(
+            ReactionDiffusion,
+            FiniteDifferenceJacobian::default().with_sparsity(SparsityPattern::banded(3, 1, 1)),
+        );
+        assert!(matches!(
+            system,
+            Err(OdeError::InvalidParameter {
+                name: "sparsity",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn newton_with_finite_difference_jacobian() {
+        // x0^2 + x1^2 = 4, x0 = x1
+        let f = |x: &[f64], residual: &mut [f64]| {
+            residual[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+            residual[1] = x[0] - x[1];
+        };
+
+        let result = newton_raphson_finite_difference_solve(
+            f,
+            &FiniteDifferenceJacobian::default(),
+            vec![1.0, 2.0],
+            &NewtonOptions::new(TOLERANCE, MAX_ITERATIONS),
+        );
+
+        assert!(result.converged);
+        assert!((result.root[0] - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((result.root[1] - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+}
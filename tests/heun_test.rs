@@ -1,5 +1,6 @@
-use numerical_methods_lib::ode_solvers::{heun_method, ode_solver};
-use ode_solver::{Solve, WriteSolution};
+use numerical_methods_lib::ode_solvers::{heun_method, ode_solution, ode_solver, ode_system};
+use numerical_methods_lib::output::solution_file::{write_solution_file, RunMetadata};
+use ode_solver::Solve;
 use std::fs::read_to_string;
 
 #[cfg(test)]
@@ -7,65 +8,67 @@ mod tests {
 
     use super::*;
 
-    const T_INITIAL: i32 = 0; // t0
-    const T_FINAL: i32 = 1; // tf
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
     const TIME_STEP: f64 = 0.1; // h
     const INITIAL_SOLUTION: f64 = 1.0; // S0
 
-    const FILE_PATH: &str = "solver_results/heun_solver_test.txt";
+    const FILE_PATH: &str = "solver_results/heun_solver_test.csv";
 
-    fn test_fixture(solution: &mut Vec<f64>) {
-        let num_steps: i32 = ((T_FINAL - T_INITIAL) as f64 / TIME_STEP) as i32;
-        let f = |x: f64, _y: f64| 2.0 * x; // function: f(t,x)  // y marked as _y for now
+    fn test_fixture() -> ode_solution::OdeSolution {
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
 
+        let system = ode_system::ClosureSystem::new(1, f);
         let solver_params = ode_solver::OdeSolverParams {
-            f,
-            num_steps,
-            t_initial: T_INITIAL,
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
             time_step: TIME_STEP,
             ..Default::default()
         };
-        let solver_object = ode_solver::OdeSolver::new("ODE Solver Heun Test", &solver_params);
+        let solver_object =
+            ode_solver::OdeSolver::new("ODE Solver Heun Test", &system, &solver_params);
         let heun_method_solver = heun_method::HeunSolver {
             solver: Box::new(solver_object),
         };
 
-        heun_method_solver.solve(solution);
+        heun_method_solver.solve(&[INITIAL_SOLUTION]).unwrap()
     }
 
     #[test]
     fn heun_initial_value() {
-        let mut solution: Vec<f64> = vec![INITIAL_SOLUTION];
-        test_fixture(&mut solution);
+        let solution = test_fixture();
 
-        assert_eq!(*solution.get(0).unwrap(), INITIAL_SOLUTION);
+        assert_eq!(solution.states[0][0], INITIAL_SOLUTION);
     }
 
     #[test]
     fn write_solution_initial_val() {
-        let f = |x: f64, _y: f64| 2.0 * x; // function: f(t,x)  // y marked as _y for now
-
-        let num_steps: i32 = ((T_FINAL - T_INITIAL) as f64 / TIME_STEP) as i32;
-        let mut solution: Vec<f64> = vec![INITIAL_SOLUTION];
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
 
+        let system = ode_system::ClosureSystem::new(1, f);
         let solver_params = ode_solver::OdeSolverParams {
-            f,
-            num_steps,
-            t_initial: T_INITIAL,
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
             time_step: TIME_STEP,
             ..Default::default()
         };
-        let solver_object = ode_solver::OdeSolver::new("ODE Solver Heun Test", &solver_params);
+        let solver_object =
+            ode_solver::OdeSolver::new("ODE Solver Heun Test", &system, &solver_params);
         let heun_method_solver = heun_method::HeunSolver {
             solver: Box::new(solver_object),
         };
 
-        heun_method_solver.solve(&mut solution);
-        let _write_result = heun_method_solver.write_solution(FILE_PATH, &solution);
-
-        let line = read_to_string(FILE_PATH).unwrap();
-        let first_line = line.lines().next().unwrap();
+        let solution = heun_method_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        let metadata = RunMetadata::new("Heun", &solver_params);
+        write_solution_file(FILE_PATH, &solution, &metadata).unwrap();
 
-        assert_eq!(first_line.parse::<f64>().unwrap(), INITIAL_SOLUTION);
+        let contents = read_to_string(FILE_PATH).unwrap();
+        let mut rows = contents.lines().filter(|line| !line.starts_with('#'));
+        assert_eq!(rows.next().unwrap(), "t,y0");
+        let first_row: Vec<f64> = rows
+            .next()
+            .unwrap()
+            .split(',')
+            .map(|value| value.parse::<f64>().unwrap())
+            .collect();
+        assert_eq!(first_row, vec![T_INITIAL, INITIAL_SOLUTION]);
     }
 }
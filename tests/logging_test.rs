@@ -0,0 +1,185 @@
+use numerical_methods_lib::ode_solvers::{
+    dormand_prince, implicit_euler_method, ode_solver, ode_system, runge_kutta4,
+};
+use ode_solver::Solve;
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const TOLERANCE: f64 = 1e-8; // tol
+    const MAX_ITERATIONS: i32 = 100;
+
+    /// Log output shared between the subscriber and the test
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Runs `solve` with every level enabled and returns the formatted log lines.
+    fn capture_logs(solve: impl FnOnce()) -> Vec<String> {
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing_subscriber::filter::LevelFilter::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let guard = subscriber.set_default();
+        solve();
+        drop(guard);
+        let output = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+        output.lines().map(|line| line.to_string()).collect()
+    }
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            max_iters: MAX_ITERATIONS,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn every_accepted_step_is_traced_inside_the_solve_span() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Logging Test", &system, &solver_params);
+        let rk4_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        let mut steps: usize = 0;
+        let lines = capture_logs(|| {
+            steps = rk4_solver.solve(&[INITIAL_SOLUTION]).unwrap().len() - 1;
+        });
+
+        assert!(lines[0].contains("INFO solve{method=\"RK4\"}"));
+        assert!(lines[0].contains("starting integration"));
+        let traced: Vec<&String> = lines
+            .iter()
+            .filter(|line| line.contains("accepted step"))
+            .collect();
+        assert_eq!(traced.len(), steps);
+        assert!(traced
+            .iter()
+            .all(|line| line.contains("TRACE solve{method=\"RK4\"}")));
+        assert!(traced[0].contains("step=1 t=0.1 h=0.1"));
+        assert!(lines.last().unwrap().contains("integration finished"));
+        assert!(lines
+            .last()
+            .unwrap()
+            .contains(&format!("accepted_steps={}", steps)));
+    }
+
+    #[test]
+    fn step_attempts_report_the_error_estimate() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Logging Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+
+        let mut stats = None;
+        let lines = capture_logs(|| {
+            stats = Some(
+                dormand_prince_solver
+                    .solve(&[INITIAL_SOLUTION])
+                    .unwrap()
+                    .stats,
+            );
+        });
+        let stats = stats.unwrap();
+
+        let attempts: Vec<&String> = lines
+            .iter()
+            .filter(|line| line.contains("step attempt"))
+            .collect();
+        assert_eq!(attempts.len(), stats.accepted_steps + stats.rejected_steps);
+        assert!(attempts.iter().all(|line| line.contains("error=")));
+        assert_eq!(
+            attempts
+                .iter()
+                .filter(|line| line.contains("accepted=false"))
+                .count(),
+            stats.rejected_steps
+        );
+    }
+
+    #[test]
+    fn newton_iterations_are_traced_per_implicit_step() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let f_dash = |x: f64, y: &[f64], jac: &mut [f64]| jac[0] = -40.0 * x * y[0]; // function: f'(t,y)
+        let system = ode_system::ClosureSystem::with_jacobian(1, f, f_dash);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Logging Test", &system, &solver_params);
+        let implicit_euler_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+
+        let mut newton_iters: usize = 0;
+        let lines = capture_logs(|| {
+            newton_iters = implicit_euler_solver
+                .solve(&[INITIAL_SOLUTION])
+                .unwrap()
+                .stats
+                .num_newton_iters;
+        });
+
+        assert!(lines[0].contains("root_finder=\"Newton Raphson\""));
+        let logged: usize = lines
+            .iter()
+            .filter(|line| line.contains("Newton step"))
+            .map(|line| {
+                let field = line.split("newton_iterations=").nth(1).unwrap();
+                field.split(' ').next().unwrap().parse::<usize>().unwrap()
+            })
+            .sum();
+        assert_eq!(logged, newton_iters);
+    }
+
+    #[test]
+    fn nothing_is_logged_without_a_subscriber_level() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Logging Test", &system, &solver_params);
+        let rk4_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing_subscriber::filter::LevelFilter::WARN)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = subscriber.set_default();
+        rk4_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+        assert!(captured.0.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use method_registry::{build_solver, find_method, METHODS};
+use numerical_methods_lib::ode_solvers::{method_registry, ode_error, ode_solver, ode_system};
+use ode_error::OdeError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.01; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    #[test]
+    fn every_method_is_built_and_solves() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+
+        for method in METHODS.iter() {
+            let solver = ode_solver::OdeSolver::new(method.name, &system, &solver_params);
+            let solution = build_solver(method.id, solver)
+                .unwrap()
+                .solve(&[INITIAL_SOLUTION])
+                .unwrap();
+            let (t, y) = solution.last().unwrap();
+            assert_eq!(t, T_FINAL, "{}", method.id);
+            // The first order methods are the least accurate, about h/2 * t * exp(-t)
+            assert!((y[0] - (-T_FINAL).exp()).abs() < 2e-3, "{}", method.id);
+        }
+    }
+
+    #[test]
+    fn methods_are_found_by_display_name() {
+        assert_eq!(find_method("Dormand Prince").unwrap().id, "dormand-prince");
+        assert_eq!(find_method("implicit_euler").unwrap().id, "implicit-euler");
+        assert_eq!(find_method(" BDF ").unwrap().id, "bdf");
+        assert!(find_method("Heun Euler").is_none());
+    }
+
+    #[test]
+    fn method_ids_are_unique() {
+        for (index, method) in METHODS.iter().enumerate() {
+            assert!(METHODS[index + 1..]
+                .iter()
+                .all(|other| other.id != method.id));
+            assert_eq!(find_method(method.id), Some(method));
+        }
+    }
+
+    #[test]
+    fn unknown_method_is_rejected() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams::default();
+        let solver = ode_solver::OdeSolver::new("ODE Solver", &system, &solver_params);
+
+        assert!(matches!(
+            build_solver("Simpson", solver),
+            Err(OdeError::InvalidParameter { name: "method", .. })
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+use numerical_methods_lib::linear_solvers::lu_decomposition::LuDecomposition;
+use numerical_methods_lib::ode_solvers::{implicit_euler_method, ode_solver, ode_system};
+use numerical_methods_lib::root_finders::newton_raphson_method::{
+    newton_raphson_chord_solve, newton_raphson_method_root, newton_raphson_solve,
+    newton_raphson_system_solve, NewtonOptions, TerminationReason,
+};
+use ode_solver::Solve;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-10; // tol
+    const MAX_ITERATIONS: i32 = 50;
+
+    #[test]
+    fn scalar_root_converges_on_residual() {
+        let result = newton_raphson_solve(
+            |x: f64| x * x - 2.0,
+            |x: f64| 2.0 * x,
+            1.0,
+            &NewtonOptions::new(TOLERANCE, MAX_ITERATIONS),
+        );
+
+        assert!(result.converged);
+        assert!((result.root - 2.0_f64.sqrt()).abs() < 1e-10);
+        assert!(result.residual <= TOLERANCE);
+        assert!(result.iterations > 0 && result.iterations < 10);
+        // The old entry point reaches the same root
+        let root = newton_raphson_method_root(
+            |x: f64| x * x - 2.0,
+            |x: f64| 2.0 * x,
+            1.0,
+            TOLERANCE,
+            MAX_ITERATIONS,
+        );
+        assert_eq!(root, result.root);
+    }
+
+    #[test]
+    fn zero_derivative_is_guarded() {
+        let result = newton_raphson_solve(
+            |x: f64| x * x - 1.0,
+            |x: f64| 2.0 * x,
+            0.0,
+            &NewtonOptions::new(TOLERANCE, MAX_ITERATIONS),
+        );
+
+        assert!(!result.converged);
+        assert_eq!(result.reason, TerminationReason::ZeroDerivative);
+        assert_eq!(result.iterations, 0);
+    }
+
+    #[test]
+    fn iteration_limit_is_reported() {
+        // Double root: Newton only halves the error every iteration
+        let result = newton_raphson_solve(
+            |x: f64| x * x,
+            |x: f64| 2.0 * x,
+            1.0,
+            &NewtonOptions::new(TOLERANCE, 3),
+        );
+
+        assert!(!result.converged);
+        assert_eq!(result.reason, TerminationReason::MaxIterations);
+        assert_eq!(result.iterations, 3);
+        assert_eq!(result.root, 0.125);
+    }
+
+    #[test]
+    fn line_search_prevents_divergence() {
+        // Undamped Newton diverges on atan(x) from |x0| > 1.39
+        let options = NewtonOptions::new(TOLERANCE, MAX_ITERATIONS);
+        let undamped = NewtonOptions {
+            line_search: false,
+            ..options
+        };
+        let f = |x: f64| x.atan();
+        let f_dash = |x: f64| 1.0 / (1.0 + x * x);
+
+        let damped_result = newton_raphson_solve(f, f_dash, 2.0, &options);
+        let undamped_result = newton_raphson_solve(f, f_dash, 2.0, &undamped);
+
+        assert!(damped_result.converged);
+        assert!(damped_result.root.abs() < 1e-8);
+        assert!(!undamped_result.converged);
+    }
+
+    #[test]
+    fn step_tolerance_stops_slow_convergence() {
+        // Double root: Newton converges linearly, the residual x^2 stays above a zero tolerance
+        let options = NewtonOptions {
+            residual_tol: 0.0,
+            step_tol: 1e-6,
+            ..NewtonOptions::new(TOLERANCE, 200)
+        };
+        let result = newton_raphson_system_solve(
+            |x: &[f64], residual: &mut [f64]| residual[0] = x[0] * x[0],
+            |x: &[f64], jac: &mut [f64]| jac[0] = 2.0 * x[0],
+            vec![1.0],
+            &options,
+        );
+
+        assert!(result.converged);
+        assert_eq!(result.reason, TerminationReason::StepTolerance);
+        assert!(result.root[0].abs() < 1e-5);
+    }
+
+    #[test]
+    fn damped_step_is_not_step_convergence() {
+        // From (0, 0) the full Newton step to (1, 0) overshoots the valley y = x^2, so the
+        // line search takes 1/32 of it, far from the root (1, 1)
+        let options = NewtonOptions {
+            step_tol: 0.05,
+            ..NewtonOptions::new(TOLERANCE, 1)
+        };
+        let f = |x: &[f64], residual: &mut [f64]| {
+            residual[0] = x[0] - 1.0;
+            residual[1] = 1e3 * (x[1] - x[0] * x[0]);
+        };
+        let f_dash = |x: &[f64], jac: &mut [f64]| {
+            jac.copy_from_slice(&[1.0, 0.0, -2e3 * x[0], 1e3]);
+        };
+
+        let result = newton_raphson_system_solve(f, f_dash, vec![0.0, 0.0], &options);
+        assert!(!result.converged);
+        assert_eq!(result.reason, TerminationReason::MaxIterations);
+        assert!(result.residual > 0.5);
+
+        let options = NewtonOptions {
+            max_iters: MAX_ITERATIONS,
+            ..options
+        };
+        // Short damped steps along the valley are never taken for convergence
+        let result = newton_raphson_system_solve(f, f_dash, vec![0.0, 0.0], &options);
+        assert!(!result.converged || result.residual <= TOLERANCE);
+    }
+
+    #[test]
+    fn chord_iteration_reuses_jacobian() {
+        // x0^2 + x1^2 = 4, x0 = x1 with the Jacobian frozen at the initial guess
+        let f = |x: &[f64], residual: &mut [f64]| {
+            residual[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+            residual[1] = x[0] - x[1];
+        };
+        let jacobian = LuDecomposition::new(&[3.0, 3.0, 1.0, -1.0], 2).unwrap();
+
+        let result = newton_raphson_chord_solve(
+            f,
+            &jacobian,
+            vec![1.5, 1.5],
+            &NewtonOptions::new(TOLERANCE, MAX_ITERATIONS),
+        );
+
+        assert!(result.converged);
+        assert!((result.root[0] - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((result.root[1] - 2.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn chord_iteration_detects_divergence() {
+        // A frozen slope of 0.4 for x - 2 overshoots by a factor 1.5 every iteration
+        let jacobian = LuDecomposition::new(&[0.4], 1).unwrap();
+
+        let result = newton_raphson_chord_solve(
+            |x: &[f64], residual: &mut [f64]| residual[0] = x[0] - 2.0,
+            &jacobian,
+            vec![1.0],
+            &NewtonOptions::new(TOLERANCE, MAX_ITERATIONS),
+        );
+
+        assert!(!result.converged);
+        assert_eq!(result.reason, TerminationReason::Diverged);
+        assert_eq!(result.iterations, 1);
+    }
+
+    #[test]
+    fn implicit_euler_reports_newton_diagnostics() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let f_dash = |x: f64, y: &[f64], jac: &mut [f64]| jac[0] = -40.0 * x * y[0]; // function: f'(t,y)
+        let system = ode_system::ClosureSystem::with_jacobian(1, f, f_dash);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(0.0, 1.0),
+            time_step: 0.1,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("Implicit Euler Method Test", &system, &solver_params);
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+        let solution = implicit_solver.solve(&[1.0]).unwrap();
+
+        assert_eq!(solution.newton_steps.len(), solution.len() - 1);
+        for (step, t) in solution.newton_steps.iter().zip(&solution.times[1..]) {
+            assert_eq!(step.t, *t);
+            assert!(step.iterations >= 1);
+            assert_ne!(step.reason, TerminationReason::MaxIterations);
+        }
+        let total: i32 = solution.newton_steps.iter().map(|s| s.iterations).sum();
+        assert_eq!(solution.stats.num_newton_iters, total as usize);
+    }
+}
@@ -0,0 +1,134 @@
+use numerical_methods_lib::ode_solvers::{
+    dormand_prince, explicit_euler_method, implicit_euler_method, ode_error, ode_solver, ode_system,
+};
+use ode_error::OdeError;
+use ode_solver::Solve;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    fn explicit_euler_result(
+        system: &dyn ode_system::OdeSystem,
+        solver_params: &ode_solver::OdeSolverParams,
+        initial_state: &[f64],
+    ) -> Result<(), OdeError> {
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Explicit Euler Test", system, solver_params);
+        let explicit_euler_solver = explicit_euler_method::ExplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+        explicit_euler_solver.solve(initial_state).map(|_| ())
+    }
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        let f = |x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 2.0 * x; // function: f(t,y)  // y marked as _y for now
+        let system = ode_system::ClosureSystem::new(1, f);
+        let zero_step = ode_solver::OdeSolverParams {
+            time_step: 0.0,
+            ..Default::default()
+        };
+        let empty_span = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_INITIAL),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            explicit_euler_result(&system, &zero_step, &[INITIAL_SOLUTION]),
+            Err(OdeError::InvalidParameter {
+                name: "time_step",
+                ..
+            })
+        ));
+        assert!(matches!(
+            explicit_euler_result(&system, &empty_span, &[INITIAL_SOLUTION]),
+            Err(OdeError::InvalidParameter {
+                name: "time_span",
+                ..
+            })
+        ));
+        assert!(matches!(
+            explicit_euler_result(&system, &Default::default(), &[]),
+            Err(OdeError::InvalidParameter {
+                name: "initial_state",
+                ..
+            })
+        ));
+        assert!(matches!(
+            explicit_euler_result(&system, &Default::default(), &[1.0, 2.0]),
+            Err(OdeError::InvalidParameter {
+                name: "initial_state",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn non_finite_state_is_reported() {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = 1e100 * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+
+        let result = explicit_euler_result(&system, &solver_params, &[INITIAL_SOLUTION]);
+        assert!(matches!(result, Err(OdeError::NonFiniteState { .. })));
+        assert!(matches!(
+            explicit_euler_result(&system, &solver_params, &[f64::NAN]),
+            Err(OdeError::NonFiniteState { .. })
+        ));
+    }
+
+    #[test]
+    fn newton_non_convergence_is_reported() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let f_dash = |x: f64, y: &[f64], jac: &mut [f64]| jac[0] = -40.0 * x * y[0]; // function: f'(t,y)
+        let system = ode_system::ClosureSystem::with_jacobian(1, f, f_dash);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            max_iters: 1,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("Implicit Euler Method Test", &system, &solver_params);
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+
+        assert!(matches!(
+            implicit_solver.solve(&[INITIAL_SOLUTION]),
+            Err(OdeError::NewtonNonConvergence { iterations: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn step_size_underflow_is_reported() {
+        // y' = y^2, y(0) = 1 blows up at t = 1
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 2.0 * T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Dormand Prince Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+
+        let error = dormand_prince_solver
+            .solve(&[INITIAL_SOLUTION])
+            .unwrap_err();
+        assert!(matches!(error, OdeError::StepSizeUnderflow { t, .. } if (t - 1.0).abs() < 1e-3));
+    }
+}
@@ -0,0 +1,123 @@
+use numerical_methods_lib::ode_solvers::{
+    dormand_prince, implicit_euler_method, ode_solution, ode_solver, ode_system, runge_kutta4,
+};
+use ode_solution::SolverStatus;
+use ode_solver::Solve;
+use ode_system::OdeSystem;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    // y' = -20 t y^2
+    struct QuadraticDecay;
+
+    impl OdeSystem for QuadraticDecay {
+        fn dimension(&self) -> usize {
+            1
+        }
+
+        fn rhs(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+            dydt[0] = -20.0 * t * y[0] * y[0];
+        }
+
+        fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+            jac[0] = -40.0 * t * y[0];
+        }
+
+        fn jacobian_rhs_evals(&self) -> usize {
+            0
+        }
+    }
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn rk4_solution_time_grid_and_stats() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new(
+            "ODE Solver Runge Kutta 4 Test",
+            &QuadraticDecay,
+            &solver_params,
+        );
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+        let solution = rungekutta_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+
+        let steps = solution.len() - 1;
+        assert_eq!(solution.status, SolverStatus::Success);
+        assert_eq!(solution.stats.accepted_steps, steps);
+        assert_eq!(solution.stats.rejected_steps, 0);
+        assert_eq!(solution.stats.num_rhs_evals, 4 * steps);
+        assert_eq!(solution.stats.num_jacobian_evals, 0);
+        for (index, t) in solution.times.iter().enumerate() {
+            assert!((t - (T_INITIAL + index as f64 * TIME_STEP)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn implicit_euler_counts_newton_work() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new(
+            "Implicit Euler Method Test",
+            &QuadraticDecay,
+            &solver_params,
+        );
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+        let solution = implicit_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+
+        let steps = solution.len() - 1;
+        assert_eq!(solution.stats.accepted_steps, steps);
+        assert!(solution.stats.num_newton_iters >= steps);
+        assert_eq!(
+            solution.stats.num_jacobian_evals,
+            solution.stats.num_newton_iters
+        );
+        // Every Newton solve evaluates the residual once more than it iterates
+        assert_eq!(
+            solution.stats.num_rhs_evals,
+            solution.stats.num_newton_iters + steps
+        );
+    }
+
+    #[test]
+    fn dormand_prince_counts_attempted_steps() {
+        let solver_params = ode_solver::OdeSolverParams {
+            time_step: 0.5, // large initial step to force rejections
+            ..solver_params()
+        };
+        let solver = ode_solver::OdeSolver::new(
+            "ODE Solver Dormand Prince Test",
+            &QuadraticDecay,
+            &solver_params,
+        );
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+        let solution = dormand_prince_solver.solve(&[INITIAL_SOLUTION]).unwrap();
+
+        let stats = solution.stats;
+        assert_eq!(solution.status, SolverStatus::Success);
+        assert_eq!(stats.accepted_steps, solution.len() - 1);
+        assert!(stats.rejected_steps > 0);
+        // FSAL: one initial evaluation, then six per attempted step
+        assert_eq!(
+            stats.num_rhs_evals,
+            1 + 6 * (stats.accepted_steps + stats.rejected_steps)
+        );
+    }
+}
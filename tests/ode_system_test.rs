@@ -0,0 +1,183 @@
+use numerical_methods_lib::ode_solvers::{
+    explicit_euler_method, implicit_euler_method, ode_solver, ode_system, runge_kutta4,
+};
+use ode_solver::Solve;
+use ode_system::{ClosureSystem, OdeSystem};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: [f64; 2] = [1.0, 0.0]; // S0
+
+    // Harmonic oscillator: y'' = -omega^2 y written as y0' = y1, y1' = -omega^2 y0
+    struct Oscillator {
+        omega: f64,
+    }
+
+    impl OdeSystem for Oscillator {
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn rhs(&self, _t: f64, y: &[f64], dydt: &mut [f64]) {
+            dydt[0] = y[1];
+            dydt[1] = -self.omega * self.omega * y[0];
+        }
+
+        fn jacobian(&self, _t: f64, _y: &[f64], jac: &mut [f64]) {
+            jac.copy_from_slice(&[0.0, 1.0, -self.omega * self.omega, 0.0]);
+        }
+
+        fn jacobian_rhs_evals(&self) -> usize {
+            0
+        }
+    }
+
+    const OSCILLATOR: Oscillator = Oscillator { omega: 1.0 };
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn explicit_euler_keeps_dimension() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new(
+            "ODE System Explicit Euler Test",
+            &OSCILLATOR,
+            &solver_params,
+        );
+        let explicit_euler_solver = explicit_euler_method::ExplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = explicit_euler_solver.solve(&INITIAL_SOLUTION).unwrap();
+
+        assert_eq!(solution.len(), solver_params.time_grid().len());
+        assert_eq!(solution.dimension(), INITIAL_SOLUTION.len());
+        assert!(solution
+            .states
+            .iter()
+            .all(|y| y.len() == INITIAL_SOLUTION.len()));
+        // First step: y1 = y0 + h * f(t0, y0)
+        assert_eq!(solution.states[1], vec![1.0, -TIME_STEP]);
+    }
+
+    #[test]
+    fn rk4_oscillator_matches_exact_solution() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE System RK4 Test", &OSCILLATOR, &solver_params);
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = rungekutta_solver.solve(&INITIAL_SOLUTION).unwrap();
+
+        for (t, y) in solution.times.iter().zip(&solution.states) {
+            assert!((y[0] - t.cos()).abs() < 1e-5);
+            assert!((y[1] + t.sin()).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn implicit_euler_oscillator_is_damped() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new(
+            "ODE System Implicit Euler Test",
+            &OSCILLATOR,
+            &solver_params,
+        );
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+
+        let solution = implicit_solver.solve(&INITIAL_SOLUTION).unwrap();
+
+        // Implicit Euler on a linear system: (I - hA) y_{i+1} = y_i, so the energy decays
+        // by a factor 1 / (1 + h^2) every step
+        for pair in solution.states.windows(2) {
+            let energy_i = pair[0][0].powi(2) + pair[0][1].powi(2);
+            let energy_next = pair[1][0].powi(2) + pair[1][1].powi(2);
+            assert!((energy_next - energy_i / (1.0 + TIME_STEP * TIME_STEP)).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn closure_system_parameter_sweep() {
+        let solver_params = solver_params();
+
+        // Decay rates are captured by the closures, no globals needed
+        for rate in [0.5, 1.0, 2.0] {
+            let system = ClosureSystem::with_jacobian(
+                1,
+                move |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -rate * y[0],
+                move |_x: f64, _y: &[f64], jac: &mut [f64]| jac[0] = -rate,
+            );
+            let solver =
+                ode_solver::OdeSolver::new("ODE System Sweep Test", &system, &solver_params);
+            let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+                solver: Box::new(solver),
+            };
+            let solution = rungekutta_solver.solve(&[1.0]).unwrap();
+
+            let (t, y) = solution.last().unwrap();
+            assert!((y[0] - (-rate * t).exp()).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn finite_difference_jacobian_fallback() {
+        let solver_params = solver_params();
+        let system = ClosureSystem::new(2, |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        });
+
+        let mut jac = vec![0.0; 4];
+        system.jacobian(0.0, &INITIAL_SOLUTION, &mut jac);
+        for (approx, exact) in jac.iter().zip([0.0, 1.0, -1.0, 0.0]) {
+            assert!((approx - exact).abs() < 1e-7);
+        }
+
+        // Implicit Euler without a user Jacobian matches the exact one
+        let solver =
+            ode_solver::OdeSolver::new("ODE System Implicit Euler Test", &system, &solver_params);
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+        let solution = implicit_solver.solve(&INITIAL_SOLUTION).unwrap();
+        let solver = ode_solver::OdeSolver::new(
+            "ODE System Implicit Euler Test",
+            &OSCILLATOR,
+            &solver_params,
+        );
+        let implicit_solver = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        };
+        let solution_exact = implicit_solver.solve(&INITIAL_SOLUTION).unwrap();
+
+        for (y, y_exact) in solution.states.iter().zip(&solution_exact.states) {
+            assert!((y[0] - y_exact[0]).abs() < 1e-8);
+            assert!((y[1] - y_exact[1]).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn initial_state_dimension_is_checked() {
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE System RK4 Test", &OSCILLATOR, &solver_params);
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        assert!(rungekutta_solver.solve(&[1.0]).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use numerical_methods_lib::{
+    ode_solvers::{dormand_prince, ode_solution, ode_solver, ode_system},
+    output::{
+        binary_format, csv_format, jsonl_format,
+        output_error::OutputError,
+        solution_file::{read_solution_file, write_solution_file, OutputFormat, RunMetadata},
+    },
+};
+use ode_solver::Solve;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 2.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const TOLERANCE: f64 = 1e-8; // tol
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            ..Default::default()
+        }
+    }
+
+    // Damped oscillator y0' = y1, y1' = -y0 - 0.1 y1, solved with Dormand-Prince so the
+    // times are not round numbers
+    fn oscillator_solution() -> (ode_solution::OdeSolution, RunMetadata) {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0] - 0.1 * y[1];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Output Test", &system, &solver_params);
+        let dormand_prince_solver = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        };
+        let solution = dormand_prince_solver.solve(&[1.0, 0.0]).unwrap();
+        (solution, RunMetadata::new("Dormand-Prince", &solver_params))
+    }
+
+    // The tests run from two packages at once, keep their files apart
+    fn output_path(file_name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("ode_solver_output_{}", std::process::id()))
+            .join(file_name)
+    }
+
+    #[test]
+    fn every_format_round_trips_exactly() {
+        let (solution, metadata) = oscillator_solution();
+        for file_name in ["oscillator.csv", "oscillator.jsonl", "oscillator.bin"] {
+            let path = output_path(file_name);
+            write_solution_file(&path, &solution, &metadata).unwrap();
+            let record = read_solution_file(&path).unwrap();
+
+            assert_eq!(record.metadata.as_ref(), Some(&metadata), "{}", file_name);
+            assert_eq!(record.solution.times, solution.times, "{}", file_name);
+            assert_eq!(record.solution.states, solution.states, "{}", file_name);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn csv_has_a_time_column_and_a_header() {
+        let (solution, metadata) = oscillator_solution();
+        let mut buffer: Vec<u8> = Vec::new();
+        csv_format::write_csv(&mut buffer, &solution, &metadata).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        let mut lines = text.lines();
+        assert_eq!(lines.next().unwrap(), "# method: Dormand-Prince");
+        let mut rows = text.lines().filter(|line| !line.starts_with('#'));
+        assert_eq!(rows.next().unwrap(), "t,y0,y1");
+        assert_eq!(rows.next().unwrap(), "0,1,0");
+        assert_eq!(rows.count(), solution.len() - 1);
+    }
+
+    #[test]
+    fn csv_without_metadata_can_be_read() {
+        let text = "t,y0,y1\n0,1,2\n0.5,3,4\n";
+        let record = csv_format::read_csv(text.as_bytes()).unwrap();
+
+        assert_eq!(record.metadata, None);
+        assert_eq!(record.solution.times, vec![0.0, 0.5]);
+        assert_eq!(record.solution.states, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    }
+
+    #[test]
+    fn jsonl_starts_with_the_run_metadata() {
+        let (solution, metadata) = oscillator_solution();
+        let mut buffer: Vec<u8> = Vec::new();
+        jsonl_format::write_jsonl(&mut buffer, &solution, &metadata).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+
+        let mut lines = text.lines();
+        let first = lines.next().unwrap();
+        assert!(first.starts_with("{\"metadata\":{\"method\":\"Dormand-Prince\""));
+        assert!(first.contains("\"time_step\":0.1"));
+        assert!(first.contains("\"rel_tolerance\":1e-8"));
+        assert_eq!(lines.next().unwrap(), "{\"t\":0.0,\"y\":[1.0,0.0]}");
+        assert_eq!(lines.count(), solution.len() - 1);
+    }
+
+    #[test]
+    fn binary_is_compact() {
+        let (solution, metadata) = oscillator_solution();
+        let mut buffer: Vec<u8> = Vec::new();
+        binary_format::write_binary(&mut buffer, &solution, &metadata).unwrap();
+
+        assert_eq!(&buffer[..4], &binary_format::MAGIC);
+        let metadata_len = u32::from_le_bytes(buffer[8..12].try_into().unwrap()) as usize;
+        // Header, then 8 bytes per time and per state component
+        assert_eq!(
+            buffer.len(),
+            4 + 4 + 4 + metadata_len + 4 + 8 + 8 * 3 * solution.len()
+        );
+    }
+
+    #[test]
+    fn malformed_files_are_reported() {
+        let result = csv_format::read_csv("t,y0\n0,1\n0.1,abc\n".as_bytes());
+        assert!(matches!(result, Err(OutputError::Parse { line: 3, .. })));
+
+        let result = csv_format::read_csv("t,y0\n0,1,2\n".as_bytes());
+        assert!(matches!(result, Err(OutputError::Parse { line: 2, .. })));
+
+        let result = csv_format::read_csv("t,y0\n".as_bytes());
+        assert!(matches!(result, Err(OutputError::Parse { .. })));
+
+        let result = jsonl_format::read_jsonl("{\"t\":0.0,\"y\":[1.0]}\n".as_bytes());
+        assert!(matches!(result, Err(OutputError::Parse { line: 1, .. })));
+
+        let result = binary_format::read_binary(&b"CSV!"[..]);
+        assert!(matches!(result, Err(OutputError::InvalidBinary(_))));
+
+        let (solution, metadata) = oscillator_solution();
+        let mut buffer: Vec<u8> = Vec::new();
+        binary_format::write_binary(&mut buffer, &solution, &metadata).unwrap();
+        buffer.truncate(buffer.len() - 1);
+        let result = binary_format::read_binary(buffer.as_slice());
+        assert!(matches!(result, Err(OutputError::InvalidBinary(_))));
+    }
+
+    #[test]
+    fn format_follows_the_file_extension() {
+        assert_eq!(
+            OutputFormat::from_path(Path::new("a/b.csv")),
+            Some(OutputFormat::Csv)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("b.jsonl")),
+            Some(OutputFormat::JsonLines)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("b.BIN")),
+            Some(OutputFormat::Binary)
+        );
+        assert_eq!(OutputFormat::from_path(Path::new("b.txt")), None);
+
+        let (solution, metadata) = oscillator_solution();
+        let result = write_solution_file(output_path("solution.txt"), &solution, &metadata);
+        assert!(matches!(result, Err(OutputError::UnknownFormat(_))));
+    }
+}
@@ -0,0 +1,260 @@
+use numerical_methods_lib::{
+    ode_solvers::{dormand_prince, ode_solution, ode_solver, ode_system, runge_kutta4},
+    plotting::{
+        convergence_plot::ConvergencePlot,
+        figure::{AxisScale, PlotFormat, PlotStyle},
+        phase_portrait::PhasePortrait,
+        plot_error::PlotError,
+        solution_plot::SolutionPlot,
+        static_export::register_font_file,
+    },
+};
+use ode_solver::Solve;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const TOLERANCE: f64 = 1e-6; // tol
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            ..Default::default()
+        }
+    }
+
+    // Harmonic oscillator y0' = y1, y1' = -y0
+    fn oscillator_solution() -> ode_solution::OdeSolution {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = solver_params();
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Plotting Test", &system, &solver_params);
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+        rungekutta_solver.solve(&[1.0, 0.0]).unwrap()
+    }
+
+    // The tests run from two packages at once, keep their files apart
+    fn output_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("ode_solver_{}_{}", name, std::process::id()))
+    }
+
+    // Lorenz system with the classical parameters
+    fn lorenz_solution() -> ode_solution::OdeSolution {
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = 10.0 * (y[1] - y[0]);
+            dydt[1] = y[0] * (28.0 - y[2]) - y[1];
+            dydt[2] = y[0] * y[1] - 8.0 / 3.0 * y[2];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(3, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_step: 0.01,
+            ..solver_params()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Plotting Test", &system, &solver_params);
+        let rungekutta_solver = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+        rungekutta_solver.solve(&[1.0, 1.0, 1.0]).unwrap()
+    }
+
+    #[test]
+    fn time_is_on_the_x_axis_by_default() {
+        let plot = SolutionPlot::new("Test Plot");
+        assert_eq!(plot.x_label, "time");
+        assert_eq!(plot.y_label, "solution");
+
+        let plot = plot.with_x_label("t [s]").with_y_label("position [m]");
+        assert_eq!(plot.x_label, "t [s]");
+        assert_eq!(plot.y_label, "position [m]");
+    }
+
+    #[test]
+    fn one_series_per_component() {
+        let solution = oscillator_solution();
+        let plot = SolutionPlot::new("Oscillator").with_solution("RK4", &solution);
+
+        assert_eq!(plot.series.len(), 2);
+        assert_eq!(plot.series[0].name, "RK4 y0");
+        assert_eq!(plot.series[1].name, "RK4 y1");
+        assert_eq!(plot.series[0].times, solution.times);
+        assert_eq!(plot.series[1].values, solution.component(1));
+    }
+
+    #[test]
+    fn solver_runs_are_overlaid() {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Plotting Test", &system, &solver_params);
+        let rk4_solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[1.0])
+        .unwrap();
+        let dp_solution = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[1.0])
+        .unwrap();
+
+        let plot = SolutionPlot::new("Comparison")
+            .with_style(PlotStyle::Lines)
+            .with_solution("RK4", &rk4_solution)
+            .with_solution("Dormand-Prince", &dp_solution);
+
+        // A scalar solution is a single series named after the run
+        assert_eq!(plot.series.len(), 2);
+        assert_eq!(plot.series[0].name, "RK4");
+        assert_eq!(plot.series[1].name, "Dormand-Prince");
+        assert_eq!(plot.series[1].times, dp_solution.times);
+        assert_eq!(plot.to_plotly().data().len(), 2);
+    }
+
+    #[test]
+    fn write_html_creates_the_output_path() {
+        let solution = oscillator_solution();
+        let dir = output_dir("html");
+        let path = dir.join("nested").join("oscillator.html");
+
+        SolutionPlot::new("Oscillator Plot")
+            .with_x_label("t")
+            .with_y_label("y")
+            .with_solution("RK4", &solution)
+            .write_html(&path)
+            .unwrap();
+
+        let html = std::fs::read_to_string(&path).unwrap();
+        assert!(html.contains("Oscillator Plot"));
+        assert!(html.contains("RK4 y1"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn format_follows_the_file_extension() {
+        assert_eq!(
+            PlotFormat::from_path(Path::new("a/b.html")),
+            Some(PlotFormat::Html)
+        );
+        assert_eq!(
+            PlotFormat::from_path(Path::new("b.SVG")),
+            Some(PlotFormat::Svg)
+        );
+        assert_eq!(
+            PlotFormat::from_path(Path::new("b.png")),
+            Some(PlotFormat::Png)
+        );
+        assert_eq!(PlotFormat::from_path(Path::new("b.pdf")), None);
+        assert_eq!(PlotFormat::from_path(Path::new("b")), None);
+
+        let result = SolutionPlot::new("Test Plot").save(output_dir("unknown").join("plot.pdf"));
+        assert!(matches!(result, Err(PlotError::UnknownFormat(_))));
+    }
+
+    #[test]
+    fn static_images_are_written_without_a_browser() {
+        let solution = oscillator_solution();
+        let dir = output_dir("static");
+        let plot = SolutionPlot::new("Oscillator Plot")
+            .with_y_label("displacement")
+            .with_solution("RK4", &solution);
+
+        plot.save(dir.join("oscillator.svg")).unwrap();
+        plot.save(dir.join("oscillator.png")).unwrap();
+
+        let svg = std::fs::read_to_string(dir.join("oscillator.svg")).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Oscillator Plot"));
+        assert!(svg.contains("displacement"));
+        assert!(svg.contains("RK4 y1"));
+        let png = std::fs::read(dir.join("oscillator.png")).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unreadable_font_files_are_rejected() {
+        assert!(matches!(
+            register_font_file(output_dir("fonts").join("missing.ttf")),
+            Err(PlotError::FontUnavailable(_))
+        ));
+        // Not a TrueType font
+        assert!(matches!(
+            register_font_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml")),
+            Err(PlotError::FontUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn phase_portraits_plot_components_against_each_other() {
+        let solution = oscillator_solution();
+        let planar = PhasePortrait::planar("Oscillator Phase Portrait", 0, 1)
+            .with_labels(&["position", "velocity"])
+            .with_solution("RK4", &solution)
+            .to_figure();
+        assert!(!planar.is_3d());
+        assert_eq!(planar.x_label, "position");
+        assert_eq!(planar.y_label, "velocity");
+        assert_eq!(planar.traces[0].x, solution.component(0));
+        assert_eq!(planar.traces[0].y, solution.component(1));
+
+        let solution = lorenz_solution();
+        let portrait =
+            PhasePortrait::spatial("Lorenz Attractor", 0, 1, 2).with_solution("RK4", &solution);
+        let spatial = portrait.to_figure();
+        assert!(spatial.is_3d());
+        assert_eq!(spatial.z_label, "y2");
+        assert_eq!(spatial.traces[0].z, Some(solution.component(2)));
+
+        let dir = output_dir("phase");
+        portrait.save(dir.join("lorenz.svg")).unwrap();
+        portrait.save(dir.join("lorenz.png")).unwrap();
+        portrait.save(dir.join("lorenz.html")).unwrap();
+        let html = std::fs::read_to_string(dir.join("lorenz.html")).unwrap();
+        assert!(html.contains("scatter3d"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn convergence_plots_are_log_log_with_reference_slopes() {
+        let step_sizes = [0.1, 0.05, 0.025];
+        let errors = [1e-4, 6.25e-6, 3.9e-7];
+        let plot = ConvergencePlot::new("Convergence")
+            .with_series("RK4", &step_sizes, &errors)
+            .with_reference_slope(4);
+        let figure = plot.to_figure();
+
+        assert_eq!(figure.x_scale, AxisScale::Log);
+        assert_eq!(figure.y_scale, AxisScale::Log);
+        assert_eq!(figure.x_label, "step size");
+        assert_eq!(figure.traces.len(), 2);
+        assert_eq!(figure.traces[1].name, "O(h^4)");
+        // Through the last point of the first series, slope 4
+        assert_eq!(figure.traces[1].y[2], errors[2]);
+        assert!((figure.traces[1].y[0] / errors[2] - 16.0f64.powi(2)).abs() < 1e-9);
+
+        let dir = output_dir("convergence");
+        plot.save(dir.join("convergence.svg")).unwrap();
+        plot.save(dir.join("convergence.png")).unwrap();
+        let html_path = dir.join("convergence.html");
+        plot.save(&html_path).unwrap();
+        assert!(std::fs::read_to_string(&html_path)
+            .unwrap()
+            .contains("\"log\""));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use benchmark_problem::{all_problems, problem, BenchmarkProblem, Reference, PROBLEM_IDS};
+use finite_difference::{DifferenceScheme, FiniteDifferenceJacobian};
+use mechanics::Pendulum;
+use numerical_methods_lib::{
+    differentiation::finite_difference,
+    ode_solvers::{method_registry::build_solver, ode_solution::OdeSolution, ode_solver},
+    problems::{benchmark_problem, mechanics},
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(problem: &BenchmarkProblem, method: &str, tolerance: f64) -> OdeSolution {
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: problem.time_span,
+            time_step: 1e-6,
+            tolerance,
+            rel_tolerance: tolerance,
+            ..Default::default()
+        };
+        let solver = ode_solver::OdeSolver::new(method, problem.system.as_ref(), &solver_params);
+        let solution: OdeSolution = build_solver(method, solver)
+            .unwrap()
+            .solve(&problem.initial_state)
+            .unwrap();
+        solution
+    }
+
+    /// Checks every component of `state` against `reference` to the relative tolerance
+    /// `rel_tolerance`, and to `abs_tolerance` for the components near zero.
+    fn assert_close(
+        id: &str,
+        state: &[f64],
+        reference: &[f64],
+        rel_tolerance: f64,
+        abs_tolerance: f64,
+    ) {
+        for (index, (value, reference)) in state.iter().zip(reference).enumerate() {
+            assert!(
+                (value - reference).abs() <= rel_tolerance * reference.abs() + abs_tolerance,
+                "{}: y[{}] = {} instead of {}",
+                id,
+                index,
+                value,
+                reference
+            );
+        }
+    }
+
+    #[test]
+    fn problems_are_found_by_id() {
+        for id in PROBLEM_IDS {
+            let problem = problem(id).unwrap();
+            assert_eq!(problem.id, id);
+            let dimension: usize = problem.system.dimension();
+            assert_eq!(problem.initial_state.len(), dimension, "{}", id);
+            assert_eq!(
+                problem.reference_final_state().unwrap().len(),
+                dimension,
+                "{}",
+                id
+            );
+        }
+        assert_eq!(all_problems().len(), PROBLEM_IDS.len());
+        assert!(problem("three-body").is_none());
+    }
+
+    #[test]
+    fn jacobians_match_finite_differences() {
+        let differences = FiniteDifferenceJacobian::new(DifferenceScheme::Central);
+        for problem in all_problems() {
+            let dimension: usize = problem.system.dimension();
+            let t: f64 = 0.5 * (problem.time_span.t0 + problem.time_span.tf);
+            // Away from the initial state, where some problems have zero entries
+            let y: Vec<f64> = problem
+                .initial_state
+                .iter()
+                .zip(problem.reference_final_state().unwrap())
+                .map(|(y0, yf)| 0.5 * (y0 + yf) + 0.1)
+                .collect();
+            let mut jac: Vec<f64> = vec![0.0; dimension * dimension];
+            let mut approximation: Vec<f64> = vec![0.0; dimension * dimension];
+            problem.system.jacobian(t, &y, &mut jac);
+            differences.ode_jacobian(problem.system.as_ref(), t, &y, &mut approximation);
+
+            let scale: f64 = jac.iter().fold(1.0, |scale, entry| scale.max(entry.abs()));
+            for (index, (exact, approximate)) in jac.iter().zip(&approximation).enumerate() {
+                assert!(
+                    (exact - approximate).abs() < 1e-6 * scale,
+                    "{}: entry {} is {} instead of about {}",
+                    problem.id,
+                    index,
+                    exact,
+                    approximate
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exact_solutions_solve_their_equations() {
+        for problem in all_problems() {
+            let exact = match problem.reference {
+                Some(Reference::Exact(exact)) => exact,
+                _ => continue,
+            };
+            let (t0, y0) = (problem.time_span.t0, &problem.initial_state);
+            assert_close(problem.id, &exact(t0, y0, t0), y0, 0.0, 1e-15);
+
+            // The central difference of the solution against the right hand side
+            let (t, dt): (f64, f64) = (t0 + 0.3 * problem.time_span.length(), 1e-5);
+            let slope: Vec<f64> = exact(t0, y0, t + dt)
+                .iter()
+                .zip(exact(t0, y0, t - dt))
+                .map(|(after, before)| (after - before) / (2.0 * dt))
+                .collect();
+            let mut dydt: Vec<f64> = vec![0.0; y0.len()];
+            problem.system.rhs(t, &exact(t0, y0, t), &mut dydt);
+            assert_close(problem.id, &dydt, &slope, 1e-6, 1e-8);
+        }
+    }
+
+    #[test]
+    fn non_stiff_references_are_reproduced() {
+        for problem in all_problems().iter().filter(|problem| !problem.stiff) {
+            let solution: OdeSolution = solve(problem, "dormand-prince", 1e-11);
+            let (tf, state) = solution.last().unwrap();
+            assert_eq!(tf, problem.time_span.tf, "{}", problem.id);
+            // The Lorenz system amplifies the errors about 1e4 times
+            assert_close(
+                problem.id,
+                state,
+                &problem.reference_final_state().unwrap(),
+                1e-5,
+                1e-6,
+            );
+        }
+    }
+
+    #[test]
+    fn stiff_references_are_reproduced() {
+        for problem in all_problems().iter().filter(|problem| problem.stiff) {
+            let solution: OdeSolution = solve(problem, "bdf", 1e-9);
+            let (tf, state) = solution.last().unwrap();
+            assert_eq!(tf, problem.time_span.tf, "{}", problem.id);
+            assert_close(
+                problem.id,
+                state,
+                &problem.reference_final_state().unwrap(),
+                1e-4,
+                1e-10,
+            );
+        }
+    }
+
+    #[test]
+    fn pendulum_period_grows_with_the_amplitude() {
+        let pendulum = Pendulum { omega: 2.0 };
+        // Small oscillations have the period 2 pi / omega
+        assert!((pendulum.period(1e-6) - std::f64::consts::PI).abs() < 1e-10);
+        // 4 K(sin(pi/4)) = 7.416298709205487 for the amplitude pi/2
+        assert!(
+            (pendulum.period(std::f64::consts::FRAC_PI_2) - 7.416298709205487 / 2.0).abs() < 1e-12
+        );
+    }
+}
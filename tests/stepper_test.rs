@@ -0,0 +1,278 @@
+use numerical_methods_lib::ode_solvers::{
+    adams_moulton, bdf_method, dormand_prince, events, implicit_euler_method, ode_error,
+    ode_solution, ode_solver, ode_system, runge_kutta4,
+};
+use ode_solution::SolverStatus;
+use ode_solver::Solve;
+use std::ops::ControlFlow;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.0; // t0
+    const T_FINAL: f64 = 1.0; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+    const TOLERANCE: f64 = 1e-8; // tol
+
+    fn solver_params() -> ode_solver::OdeSolverParams {
+        ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            tolerance: TOLERANCE,
+            rel_tolerance: TOLERANCE,
+            ..Default::default()
+        }
+    }
+
+    // Every solver on y' = -20 t y^2, y(0) = 1
+    fn for_each_solver(test: impl Fn(&str, &dyn Solve)) {
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Stepper Test", &system, &solver_params);
+
+        test(
+            "RK4",
+            &runge_kutta4::RungeKuttaSolver {
+                solver: Box::new(solver),
+            },
+        );
+        test(
+            "Implicit Euler",
+            &implicit_euler_method::ImplicitEulerSolver {
+                solver: Box::new(solver),
+            },
+        );
+        test(
+            "Dormand Prince",
+            &dormand_prince::DormandPrinceSolver {
+                solver: Box::new(solver),
+            },
+        );
+        test(
+            "BDF",
+            &bdf_method::BdfSolver {
+                solver: Box::new(solver),
+            },
+        );
+        test(
+            "Adams Moulton",
+            &adams_moulton::AdamsMoultonSolver {
+                solver: Box::new(solver),
+                order: 4,
+            },
+        );
+    }
+
+    #[test]
+    fn stepper_yields_the_steps_of_solve() {
+        for_each_solver(|name, solver| {
+            let solution = solver.solve(&[INITIAL_SOLUTION]).unwrap();
+            let mut stepper = solver.stepper(&[INITIAL_SOLUTION]).unwrap();
+            let steps: Vec<(f64, Vec<f64>)> = stepper.by_ref().map(|step| step.unwrap()).collect();
+
+            assert_eq!(steps.len(), solution.len() - 1, "{}", name);
+            for ((t, y), (t_solve, y_solve)) in steps
+                .iter()
+                .zip(solution.times[1..].iter().zip(&solution.states[1..]))
+            {
+                assert_eq!(t, t_solve);
+                assert_eq!(y, y_solve);
+            }
+            assert_eq!(stepper.stats(), solution.stats, "{}", name);
+            assert_eq!(stepper.status(), SolverStatus::Success);
+            assert_eq!(stepper.time(), T_FINAL);
+            assert!(stepper.step().is_none());
+        });
+    }
+
+    #[test]
+    fn stepper_only_keeps_the_history_it_needs() {
+        for_each_solver(|name, solver| {
+            let mut stepper = solver.stepper(&[INITIAL_SOLUTION]).unwrap();
+            while let Some(step) = stepper.step() {
+                step.unwrap();
+                // BDF reads up to seven points, the other solvers only the last one
+                assert!(stepper.solution().len() <= 7, "{}", name);
+            }
+            let solution = stepper.into_solution();
+            assert_eq!(*solution.times.last().unwrap(), T_FINAL);
+            assert!(solution.newton_steps.len() <= 1);
+        });
+    }
+
+    #[test]
+    fn observers_see_every_accepted_step() {
+        for_each_solver(|name, solver| {
+            let mut seen: Vec<f64> = Vec::new();
+            let solution = solver
+                .stepper(&[INITIAL_SOLUTION])
+                .unwrap()
+                .keep_solution()
+                .with_observer(|t, _y| {
+                    seen.push(t);
+                    ControlFlow::Continue(())
+                })
+                .run()
+                .unwrap();
+
+            assert_eq!(seen, solution.times[1..], "{}", name);
+            assert_eq!(seen.len(), solution.stats.accepted_steps);
+        });
+    }
+
+    #[test]
+    fn observer_can_stop_the_integration() {
+        for_each_solver(|name, solver| {
+            let solution = solver
+                .stepper(&[INITIAL_SOLUTION])
+                .unwrap()
+                .keep_solution()
+                .with_observer(|_t, y| {
+                    if y[0] < 0.5 {
+                        ControlFlow::Break(())
+                    } else {
+                        ControlFlow::Continue(())
+                    }
+                })
+                .run()
+                .unwrap();
+            let (t, y) = solution.last().unwrap();
+
+            assert_eq!(solution.status, SolverStatus::Stopped, "{}", name);
+            assert!(y[0] < 0.5);
+            assert!(t < T_FINAL);
+            assert!(solution.states[..solution.len() - 1]
+                .iter()
+                .all(|y| y[0] >= 0.5));
+        });
+
+        // Nothing after the stop is kept: the dense output ends at the stop time, and only
+        // the events before it are recorded
+        let f = |x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -20.0 * x * y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            dense_output: true,
+            ..solver_params()
+        };
+        let levels = [0.8, 0.3].map(|level| {
+            events::Event::new(
+                move |_t: f64, y: &[f64]| y[0] - level,
+                events::EventAction::Record,
+            )
+        });
+        let solver = ode_solver::OdeSolver::new("ODE Solver Stepper Test", &system, &solver_params)
+            .with_events(&levels);
+        let solution = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        }
+        .stepper(&[INITIAL_SOLUTION])
+        .unwrap()
+        .keep_solution()
+        .with_observer(|_t, y| {
+            if y[0] < 0.5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .run()
+        .unwrap();
+        let (t_stop, _) = solution.last().unwrap();
+
+        assert_eq!(solution.events.len(), 1);
+        assert!(solution.events[0].t < t_stop);
+        let dense = solution.dense_output.as_ref().unwrap();
+        assert_eq!(dense.time_span(), Some((T_INITIAL, t_stop)));
+        assert!(dense.evaluate(t_stop + 1e-3).is_none());
+    }
+
+    #[test]
+    fn steppers_can_be_interleaved() {
+        // Two oscillators advanced in lock step, e.g. for co-simulation
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| {
+            dydt[0] = y[1];
+            dydt[1] = -y[0];
+        }; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(2, f);
+        let solver_params = solver_params();
+        let solver = ode_solver::OdeSolver::new("ODE Solver Stepper Test", &system, &solver_params);
+        let rk4 = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        let mut first = rk4.stepper(&[0.0, 1.0]).unwrap();
+        let mut second = rk4.stepper(&[1.0, 0.0]).unwrap();
+        let mut steps: usize = 0;
+        while let (Some(a), Some(b)) = (first.step(), second.step()) {
+            let ((t_a, y_a), (t_b, y_b)) = (a.unwrap(), b.unwrap());
+            assert_eq!(t_a, t_b);
+            // sin^2 + cos^2 = 1 across the two solutions
+            assert!((y_a[0] * y_a[0] + y_b[0] * y_b[0] - 1.0).abs() < 1e-6);
+            steps += 1;
+        }
+        assert_eq!(steps, 10);
+        assert!((first.state()[0] - T_FINAL.sin()).abs() < 1e-6);
+        assert!((second.state()[0] - T_FINAL.cos()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stepper_reports_errors_once() {
+        // y' = y^2, y(0) = 1 blows up at t = 1
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = y[0] * y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: ode_solver::TimeSpan::new(T_INITIAL, 2.0 * T_FINAL),
+            ..solver_params()
+        };
+        let solver = ode_solver::OdeSolver::new("ODE Solver Stepper Test", &system, &solver_params);
+        let rk4 = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        assert!(rk4.stepper(&[]).is_err());
+        let results: Vec<_> = rk4.stepper(&[INITIAL_SOLUTION]).unwrap().collect();
+        assert!(results[..results.len() - 1].iter().all(|r| r.is_ok()));
+        assert!(matches!(
+            results.last().unwrap(),
+            Err(ode_error::OdeError::NonFiniteState { .. })
+        ));
+    }
+
+    #[test]
+    fn stepper_yields_both_sides_of_a_reset() {
+        // y' = 1, y reset to 0 when it reaches 0.35
+        let f = |_x: f64, _y: &[f64], dydt: &mut [f64]| dydt[0] = 1.0; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = solver_params();
+        let sawtooth = [events::Event::new(
+            |_t: f64, y: &[f64]| y[0] - 0.35,
+            events::EventAction::reset(|_t: f64, y: &mut [f64]| y[0] = 0.0),
+        )
+        .with_direction(events::EventDirection::Increasing)];
+        let solver = ode_solver::OdeSolver::new("ODE Solver Stepper Test", &system, &solver_params)
+            .with_events(&sawtooth);
+        let rk4 = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        };
+
+        let steps: Vec<(f64, Vec<f64>)> = rk4
+            .stepper(&[0.0])
+            .unwrap()
+            .map(|step| step.unwrap())
+            .collect();
+        let resets: Vec<&[(f64, Vec<f64>)]> = steps
+            .windows(2)
+            .filter(|pair| pair[0].0 == pair[1].0)
+            .collect();
+
+        assert_eq!(resets.len(), 2);
+        for pair in resets {
+            assert!((pair[0].1[0] - 0.35).abs() < 1e-12);
+            assert_eq!(pair[1].1[0], 0.0);
+        }
+        assert_eq!(steps.last().unwrap().0, T_FINAL);
+    }
+}
@@ -0,0 +1,143 @@
+use numerical_methods_lib::ode_solvers::{
+    dormand_prince, explicit_euler_method, implicit_euler_method, ode_solver, ode_system,
+    runge_kutta4,
+};
+use ode_solver::{Solve, TimeSpan};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const T_INITIAL: f64 = 0.25; // t0
+    const T_FINAL: f64 = 3.7; // tf
+    const TIME_STEP: f64 = 0.1; // h
+    const INITIAL_SOLUTION: f64 = 1.0; // S0
+
+    #[test]
+    fn grid_lands_on_final_time() {
+        let span = TimeSpan::new(T_INITIAL, T_FINAL);
+        let grid = span.grid(TIME_STEP);
+
+        assert_eq!(span.num_steps(TIME_STEP), 35);
+        assert_eq!(grid[0], T_INITIAL);
+        assert_eq!(*grid.last().unwrap(), T_FINAL);
+        assert!(grid.windows(2).all(|t| t[1] > t[0]));
+        assert!((grid[34] - 3.65).abs() < 1e-12);
+
+        // Spans that are a multiple of the step up to round-off get no sliver step
+        assert_eq!(TimeSpan::new(0.0, 1.0).num_steps(0.1), 10);
+    }
+
+    #[test]
+    fn span_shorter_than_round_off_keeps_initial_time() {
+        let span = TimeSpan::new(T_INITIAL, T_INITIAL + 1e-12);
+
+        assert_eq!(span.num_steps(TIME_STEP), 1);
+        assert_eq!(span.grid(TIME_STEP), vec![T_INITIAL, T_INITIAL + 1e-12]);
+        assert_eq!(TimeSpan::new(T_INITIAL, T_INITIAL).num_steps(TIME_STEP), 0);
+
+        // y' = -y
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: span,
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Time Span Test", &system, &solver_params);
+        let solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+
+        assert_eq!(solution.times, vec![T_INITIAL, T_INITIAL + 1e-12]);
+        assert_eq!(solution.states[0][0], INITIAL_SOLUTION);
+    }
+
+    #[test]
+    fn backward_grid() {
+        let span = TimeSpan::new(T_FINAL, T_INITIAL);
+        let grid = span.grid(TIME_STEP);
+
+        assert_eq!(span.direction(), -1.0);
+        assert_eq!(grid[0], T_FINAL);
+        assert_eq!(*grid.last().unwrap(), T_INITIAL);
+        assert!(grid.windows(2).all(|t| t[1] < t[0]));
+    }
+
+    #[test]
+    fn fixed_step_solvers_honor_span() {
+        // y' = -y
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = -y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: TimeSpan::new(T_INITIAL, T_FINAL),
+            time_step: TIME_STEP,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Time Span Test", &system, &solver_params);
+        let exact = INITIAL_SOLUTION * (-(T_FINAL - T_INITIAL)).exp();
+
+        let rk4_solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+        let euler_solution = explicit_euler_method::ExplicitEulerSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+        let implicit_solution = implicit_euler_method::ImplicitEulerSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&[INITIAL_SOLUTION])
+        .unwrap();
+
+        for solution in [&rk4_solution, &euler_solution, &implicit_solution] {
+            let (t, y) = solution.last().unwrap();
+            assert_eq!(t, T_FINAL);
+            assert_eq!(solution.len(), 36);
+            assert!((y[0] - exact).abs() < 5e-2);
+        }
+        assert!((rk4_solution.last().unwrap().1[0] - exact).abs() < 1e-6);
+    }
+
+    #[test]
+    fn backward_integration() {
+        // y' = y, integrated from t = 1 back to t = 0
+        let f = |_x: f64, y: &[f64], dydt: &mut [f64]| dydt[0] = y[0]; // function: f(t,y)
+        let system = ode_system::ClosureSystem::new(1, f);
+        let solver_params = ode_solver::OdeSolverParams {
+            time_span: TimeSpan::new(1.0, 0.0),
+            time_step: TIME_STEP,
+            tolerance: 1e-10,
+            rel_tolerance: 1e-10,
+            ..Default::default()
+        };
+        let solver =
+            ode_solver::OdeSolver::new("ODE Solver Time Span Test", &system, &solver_params);
+        let initial_state = [1.0_f64.exp()];
+
+        let rk4_solution = runge_kutta4::RungeKuttaSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&initial_state)
+        .unwrap();
+        let dormand_prince_solution = dormand_prince::DormandPrinceSolver {
+            solver: Box::new(solver),
+        }
+        .solve(&initial_state)
+        .unwrap();
+
+        for solution in [&rk4_solution, &dormand_prince_solution] {
+            let (t, y) = solution.last().unwrap();
+            assert_eq!(t, 0.0);
+            assert!(solution.times.windows(2).all(|t| t[1] < t[0]));
+            assert!((y[0] - 1.0).abs() < 1e-6);
+        }
+    }
+}